@@ -34,6 +34,40 @@ block_decryptor_bench!(
     aes256_decrypt_blocks,
 );
 
+// Compare the software backends used when AES instructions are unavailable
+block_encryptor_bench!(
+    { aes::Aes128::new_with_backend(&Default::default(), aes::Backend::Soft).unwrap() },
+    aes::Aes128,
+    aes128_soft_encrypt_block,
+    aes128_soft_encrypt_blocks,
+);
+block_decryptor_bench!(
+    { aes::Aes128::new_with_backend(&Default::default(), aes::Backend::Soft).unwrap() },
+    aes::Aes128,
+    aes128_soft_decrypt_block,
+    aes128_soft_decrypt_blocks,
+);
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"),
+    not(aes_backend = "soft")
+))]
+block_encryptor_bench!(
+    { aes::Aes128::new_with_backend(&Default::default(), aes::Backend::Vperm).unwrap() },
+    aes::Aes128,
+    aes128_vperm_encrypt_block,
+    aes128_vperm_encrypt_blocks,
+);
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"),
+    not(aes_backend = "soft")
+))]
+block_decryptor_bench!(
+    { aes::Aes128::new_with_backend(&Default::default(), aes::Backend::Vperm).unwrap() },
+    aes::Aes128,
+    aes128_vperm_decrypt_block,
+    aes128_vperm_decrypt_blocks,
+);
+
 #[bench]
 fn aes128_new(bh: &mut test::Bencher) {
     bh.iter(|| {
@@ -60,3 +94,33 @@ fn aes256_new(bh: &mut test::Bencher) {
         test::black_box(&cipher);
     });
 }
+
+#[bench]
+fn aes128_rekey(bh: &mut test::Bencher) {
+    let mut cipher = aes::Aes128::new(&Default::default());
+    bh.iter(|| {
+        let key = test::black_box(Default::default());
+        cipher.rekey(&key);
+        test::black_box(&cipher);
+    });
+}
+
+#[bench]
+fn aes192_rekey(bh: &mut test::Bencher) {
+    let mut cipher = aes::Aes192::new(&Default::default());
+    bh.iter(|| {
+        let key = test::black_box(Default::default());
+        cipher.rekey(&key);
+        test::black_box(&cipher);
+    });
+}
+
+#[bench]
+fn aes256_rekey(bh: &mut test::Bencher) {
+    let mut cipher = aes::Aes256::new(&Default::default());
+    bh.iter(|| {
+        let key = test::black_box(Default::default());
+        cipher.rekey(&key);
+        test::black_box(&cipher);
+    });
+}
@@ -17,6 +17,7 @@ mod expand;
 #[cfg(test)]
 mod test_expand;
 
+use crate::{Backend, BackendUnavailableError};
 use cipher::{
     AlgorithmName, BlockCipherDecClosure, BlockCipherDecrypt, BlockCipherEncClosure,
     BlockCipherEncrypt, BlockSizeUser, Key, KeyInit, KeySizeUser,
@@ -123,6 +124,32 @@ macro_rules! define_aes_impl {
             }
         }
 
+        impl $name {
+            pub(crate) fn new_with_backend(
+                key: &Key<Self>,
+                backend: Backend,
+            ) -> Result<Self, BackendUnavailableError> {
+                match backend {
+                    Backend::Armv8 => Ok(Self::new(key)),
+                    _ => Err(BackendUnavailableError(backend)),
+                }
+            }
+
+            pub(crate) fn backend(&self) -> Backend {
+                Backend::Armv8
+            }
+
+            pub(crate) fn rekey(&mut self, key: &Key<Self>) {
+                self.encrypt.rekey(key);
+                self.decrypt.set_inv_keys(&self.encrypt);
+            }
+
+            #[cfg(feature = "hazmat")]
+            pub(crate) fn from_round_keys(round_keys: &[crate::Block; $rounds]) -> Self {
+                $name_enc::from_round_keys(round_keys).into()
+            }
+        }
+
         impl BlockSizeUser for $name {
             type BlockSize = U16;
         }
@@ -183,6 +210,35 @@ macro_rules! define_aes_impl {
             }
         }
 
+        impl $name_enc {
+            pub(crate) fn new_with_backend(
+                key: &Key<Self>,
+                backend: Backend,
+            ) -> Result<Self, BackendUnavailableError> {
+                match backend {
+                    Backend::Armv8 => Ok(Self::new(key)),
+                    _ => Err(BackendUnavailableError(backend)),
+                }
+            }
+
+            pub(crate) fn backend(&self) -> Backend {
+                Backend::Armv8
+            }
+
+            pub(crate) fn rekey(&mut self, key: &Key<Self>) {
+                self.backend.rekey(key);
+            }
+
+            #[cfg(feature = "hazmat")]
+            pub(crate) fn from_round_keys(round_keys: &[crate::Block; $rounds]) -> Self {
+                // SAFETY: we enforce that this code is called only when
+                // target features required by `load_keys` were properly checked.
+                let keys = unsafe { hazmat::load_keys(round_keys) };
+                let backend = $name_back_enc { keys };
+                Self { backend }
+            }
+        }
+
         impl BlockSizeUser for $name_enc {
             type BlockSize = U16;
         }
@@ -252,6 +308,32 @@ macro_rules! define_aes_impl {
             }
         }
 
+        impl $name_dec {
+            pub(crate) fn new_with_backend(
+                key: &Key<Self>,
+                backend: Backend,
+            ) -> Result<Self, BackendUnavailableError> {
+                match backend {
+                    Backend::Armv8 => Ok(Self::new(key)),
+                    _ => Err(BackendUnavailableError(backend)),
+                }
+            }
+
+            pub(crate) fn backend(&self) -> Backend {
+                Backend::Armv8
+            }
+
+            pub(crate) fn rekey(&mut self, key: &Key<Self>) {
+                let encrypt = $name_back_enc::new(key);
+                self.backend.set_inv_keys(&encrypt);
+            }
+
+            #[cfg(feature = "hazmat")]
+            pub(crate) fn from_round_keys(round_keys: &[crate::Block; $rounds]) -> Self {
+                $name_enc::from_round_keys(round_keys).into()
+            }
+        }
+
         impl BlockSizeUser for $name_dec {
             type BlockSize = U16;
         }
@@ -5,7 +5,9 @@
 //! access to the AES round function gated under the `hazmat` crate feature.
 #![allow(unsafe_op_in_unsafe_fn)]
 
-use crate::hazmat::{Block, Block8};
+use super::expand;
+use crate::hazmat::Block;
+use cipher::array::{Array, ArraySize};
 use core::arch::aarch64::*;
 
 /// AES cipher (encrypt) round function.
@@ -30,9 +32,12 @@ pub(crate) unsafe fn cipher_round(block: &mut Block, round_key: &Block) {
 /// AES cipher (encrypt) round function: parallel version.
 #[allow(clippy::cast_ptr_alignment)]
 #[target_feature(enable = "aes")]
-pub(crate) unsafe fn cipher_round_par(blocks: &mut Block8, round_keys: &Block8) {
-    for i in 0..8 {
-        let mut state = vld1q_u8(blocks[i].as_ptr());
+pub(crate) unsafe fn cipher_round_par<N: ArraySize>(
+    blocks: &mut Array<Block, N>,
+    round_keys: &Array<Block, N>,
+) {
+    for (block, round_key) in blocks.iter_mut().zip(round_keys.iter()) {
+        let mut state = vld1q_u8(block.as_ptr());
 
         // AES single round encryption
         state = vaeseq_u8(state, vdupq_n_u8(0));
@@ -41,9 +46,45 @@ pub(crate) unsafe fn cipher_round_par(blocks: &mut Block8, round_keys: &Block8)
         state = vaesmcq_u8(state);
 
         // AES add round key (bitwise XOR)
-        state = veorq_u8(state, vld1q_u8(round_keys[i].as_ptr()));
+        state = veorq_u8(state, vld1q_u8(round_key.as_ptr()));
 
-        vst1q_u8(blocks[i].as_mut_ptr(), state);
+        vst1q_u8(block.as_mut_ptr(), state);
+    }
+}
+
+/// AES cipher (encrypt) final round function.
+#[allow(clippy::cast_ptr_alignment)]
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn cipher_final_round(block: &mut Block, round_key: &Block) {
+    let b = vld1q_u8(block.as_ptr());
+    let k = vld1q_u8(round_key.as_ptr());
+
+    // AES single round encryption (all-zero round key, deferred until the end)
+    let mut state = vaeseq_u8(b, vdupq_n_u8(0));
+
+    // AES add round key (bitwise XOR)
+    state = veorq_u8(state, k);
+
+    vst1q_u8(block.as_mut_ptr(), state);
+}
+
+/// AES cipher (encrypt) final round function: parallel version.
+#[allow(clippy::cast_ptr_alignment)]
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn cipher_final_round_par<N: ArraySize>(
+    blocks: &mut Array<Block, N>,
+    round_keys: &Array<Block, N>,
+) {
+    for (block, round_key) in blocks.iter_mut().zip(round_keys.iter()) {
+        let mut state = vld1q_u8(block.as_ptr());
+
+        // AES single round encryption
+        state = vaeseq_u8(state, vdupq_n_u8(0));
+
+        // AES add round key (bitwise XOR)
+        state = veorq_u8(state, vld1q_u8(round_key.as_ptr()));
+
+        vst1q_u8(block.as_mut_ptr(), state);
     }
 }
 
@@ -69,9 +110,12 @@ pub(crate) unsafe fn equiv_inv_cipher_round(block: &mut Block, round_key: &Block
 /// AES equivalent inverse cipher (decrypt) round function: parallel version.
 #[allow(clippy::cast_ptr_alignment)]
 #[target_feature(enable = "aes")]
-pub(crate) unsafe fn equiv_inv_cipher_round_par(blocks: &mut Block8, round_keys: &Block8) {
-    for i in 0..8 {
-        let mut state = vld1q_u8(blocks[i].as_ptr());
+pub(crate) unsafe fn equiv_inv_cipher_round_par<N: ArraySize>(
+    blocks: &mut Array<Block, N>,
+    round_keys: &Array<Block, N>,
+) {
+    for (block, round_key) in blocks.iter_mut().zip(round_keys.iter()) {
+        let mut state = vld1q_u8(block.as_ptr());
 
         // AES single round decryption (all-zero round key, deferred until the end)
         state = vaesdq_u8(state, vdupq_n_u8(0));
@@ -80,9 +124,87 @@ pub(crate) unsafe fn equiv_inv_cipher_round_par(blocks: &mut Block8, round_keys:
         state = vaesimcq_u8(state);
 
         // AES add round key (bitwise XOR)
-        state = veorq_u8(state, vld1q_u8(round_keys[i].as_ptr()));
+        state = veorq_u8(state, vld1q_u8(round_key.as_ptr()));
+
+        vst1q_u8(block.as_mut_ptr(), state);
+    }
+}
+
+/// AES inverse cipher (decrypt) round function.
+#[allow(clippy::cast_ptr_alignment)]
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn inv_cipher_round(block: &mut Block, round_key: &Block) {
+    let b = vld1q_u8(block.as_ptr());
+    let k = vld1q_u8(round_key.as_ptr());
+
+    // AES single round decryption (all-zero round key, deferred until the end)
+    let mut state = vaesdq_u8(b, vdupq_n_u8(0));
+
+    // AES add round key (bitwise XOR)
+    state = veorq_u8(state, k);
+
+    // AES inverse mix columns (applied after adding the round key)
+    state = vaesimcq_u8(state);
+
+    vst1q_u8(block.as_mut_ptr(), state);
+}
+
+/// AES inverse cipher (decrypt) round function: parallel version.
+#[allow(clippy::cast_ptr_alignment)]
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn inv_cipher_round_par<N: ArraySize>(
+    blocks: &mut Array<Block, N>,
+    round_keys: &Array<Block, N>,
+) {
+    for (block, round_key) in blocks.iter_mut().zip(round_keys.iter()) {
+        let mut state = vld1q_u8(block.as_ptr());
+
+        // AES single round decryption (all-zero round key, deferred until the end)
+        state = vaesdq_u8(state, vdupq_n_u8(0));
+
+        // AES add round key (bitwise XOR)
+        state = veorq_u8(state, vld1q_u8(round_key.as_ptr()));
+
+        // AES inverse mix columns (applied after adding the round key)
+        state = vaesimcq_u8(state);
+
+        vst1q_u8(block.as_mut_ptr(), state);
+    }
+}
+
+/// AES inverse cipher (decrypt) final round function.
+#[allow(clippy::cast_ptr_alignment)]
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn inv_cipher_final_round(block: &mut Block, round_key: &Block) {
+    let b = vld1q_u8(block.as_ptr());
+    let k = vld1q_u8(round_key.as_ptr());
+
+    // AES single round decryption (all-zero round key, deferred until the end)
+    let mut state = vaesdq_u8(b, vdupq_n_u8(0));
+
+    // AES add round key (bitwise XOR)
+    state = veorq_u8(state, k);
+
+    vst1q_u8(block.as_mut_ptr(), state);
+}
+
+/// AES inverse cipher (decrypt) final round function: parallel version.
+#[allow(clippy::cast_ptr_alignment)]
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn inv_cipher_final_round_par<N: ArraySize>(
+    blocks: &mut Array<Block, N>,
+    round_keys: &Array<Block, N>,
+) {
+    for (block, round_key) in blocks.iter_mut().zip(round_keys.iter()) {
+        let mut state = vld1q_u8(block.as_ptr());
+
+        // AES single round decryption (all-zero round key, deferred until the end)
+        state = vaesdq_u8(state, vdupq_n_u8(0));
+
+        // AES add round key (bitwise XOR)
+        state = veorq_u8(state, vld1q_u8(round_key.as_ptr()));
 
-        vst1q_u8(blocks[i].as_mut_ptr(), state);
+        vst1q_u8(block.as_mut_ptr(), state);
     }
 }
 
@@ -103,3 +225,54 @@ pub(crate) unsafe fn inv_mix_columns(block: &mut Block) {
     let out = vaesimcq_u8(b);
     vst1q_u8(block.as_mut_ptr(), out);
 }
+
+/// Load round keys into NEON registers.
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn load_keys<const N: usize>(round_keys: &[Block; N]) -> [uint8x16_t; N] {
+    let mut keys: [uint8x16_t; N] = core::mem::zeroed();
+    for (key, round_key) in keys.iter_mut().zip(round_keys) {
+        *key = vld1q_u8(round_key.as_ptr());
+    }
+    keys
+}
+
+/// Store round keys from NEON registers.
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn store_keys<const N: usize>(keys: &[uint8x16_t; N]) -> [Block; N] {
+    let mut round_keys = [Block::default(); N];
+    for (round_key, key) in round_keys.iter_mut().zip(keys) {
+        vst1q_u8(round_key.as_mut_ptr(), *key);
+    }
+    round_keys
+}
+
+/// AES-128 key expansion.
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn aes128_expand_key(key: &[u8; 16]) -> [Block; 11] {
+    store_keys(&expand::expand_key(key))
+}
+
+/// AES-192 key expansion.
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn aes192_expand_key(key: &[u8; 24]) -> [Block; 13] {
+    store_keys(&expand::expand_key(key))
+}
+
+/// AES-256 key expansion.
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn aes256_expand_key(key: &[u8; 32]) -> [Block; 15] {
+    store_keys(&expand::expand_key(key))
+}
+
+/// Compute inverse expanded keys (for decryption).
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn inv_expanded_keys<const N: usize>(round_keys: &[Block; N]) -> [Block; N] {
+    let keys = load_keys(round_keys);
+    let mut inv_keys = keys;
+    inv_keys[0] = keys[N - 1];
+    for i in 1..N - 1 {
+        inv_keys[i] = vaesimcq_u8(keys[N - 1 - i]);
+    }
+    inv_keys[N - 1] = keys[0];
+    store_keys(&inv_keys)
+}
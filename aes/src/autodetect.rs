@@ -1,7 +1,7 @@
 //! Autodetection support for hardware accelerated AES backends with fallback
-//! to the fixsliced "soft" implementation.
+//! to the vector permute and fixsliced "soft" implementations.
 
-use crate::soft;
+use crate::{Backend, BackendUnavailableError, soft, vperm};
 use cipher::{
     AlgorithmName, BlockCipherDecClosure, BlockCipherDecrypt, BlockCipherEncClosure,
     BlockCipherEncrypt, BlockSizeUser, Key, KeyInit, KeySizeUser,
@@ -16,6 +16,65 @@ use crate::armv8 as arch;
 #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
 use crate::x86 as arch;
 
+/// Implementation used by a cipher instance, i.e. the active field of its
+/// inner union.
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum Imp {
+    Arch,
+    Vperm,
+    Soft,
+}
+
+impl Imp {
+    /// Select the fastest implementation supported by the CPU.
+    #[inline]
+    fn detect() -> Self {
+        if arch::features::aes::get() {
+            Imp::Arch
+        } else if vperm::available() {
+            Imp::Vperm
+        } else {
+            Imp::Soft
+        }
+    }
+}
+
+/// Evaluate `$body` with `$inner` bound to the active field of `$self.inner`.
+macro_rules! with_inner {
+    ($self:expr, |$inner:ident| $body:expr) => {
+        match $self.imp {
+            Imp::Arch => {
+                let $inner = unsafe { &*$self.inner.arch };
+                $body
+            }
+            Imp::Vperm => {
+                let $inner = unsafe { &*$self.inner.vperm };
+                $body
+            }
+            Imp::Soft => {
+                let $inner = unsafe { &*$self.inner.soft };
+                $body
+            }
+        }
+    };
+    (mut $self:expr, |$inner:ident| $body:expr) => {
+        match $self.imp {
+            Imp::Arch => {
+                let $inner = unsafe { &mut *$self.inner.arch };
+                $body
+            }
+            Imp::Vperm => {
+                let $inner = unsafe { &mut *$self.inner.vperm };
+                $body
+            }
+            Imp::Soft => {
+                let $inner = unsafe { &mut *$self.inner.soft };
+                $body
+            }
+        }
+    };
+}
+
 macro_rules! define_aes_impl {
     (
         name = $name:ident,
@@ -23,24 +82,28 @@ macro_rules! define_aes_impl {
         name_dec = $name_dec:ident,
         module = $module:tt,
         key_size = $key_size:ty,
+        round_keys = $round_keys:ty,
         doc = $doc:expr,
     ) => {
         mod $module {
-            use super::{arch, soft};
+            use super::{arch, soft, vperm};
             use core::mem::ManuallyDrop;
 
             pub(super) union Inner {
                 pub(super) arch: ManuallyDrop<arch::$name>,
+                pub(super) vperm: ManuallyDrop<vperm::$name>,
                 pub(super) soft: ManuallyDrop<soft::$name>,
             }
 
             pub(super) union InnerEnc {
                 pub(super) arch: ManuallyDrop<arch::$name_enc>,
+                pub(super) vperm: ManuallyDrop<vperm::$name_enc>,
                 pub(super) soft: ManuallyDrop<soft::$name_enc>,
             }
 
             pub(super) union InnerDec {
                 pub(super) arch: ManuallyDrop<arch::$name_dec>,
+                pub(super) vperm: ManuallyDrop<vperm::$name_dec>,
                 pub(super) soft: ManuallyDrop<soft::$name_dec>,
             }
         }
@@ -49,12 +112,86 @@ macro_rules! define_aes_impl {
         #[doc = "block cipher"]
         pub struct $name {
             inner: $module::Inner,
-            token: arch::features::aes::InitToken,
+            imp: Imp,
+        }
+
+        impl $name {
+            /// Initialize cipher using the specified [`Backend`].
+            ///
+            /// Returns [`BackendUnavailableError`] if the backend is not
+            /// supported by the current CPU or was disabled at compile time.
+            #[inline]
+            pub fn new_with_backend(
+                key: &Key<Self>,
+                backend: Backend,
+            ) -> Result<Self, BackendUnavailableError> {
+                let (inner, imp) = match backend {
+                    Backend::Soft => (
+                        $module::Inner {
+                            soft: ManuallyDrop::new(soft::$name::new_with_backend(key, backend)?),
+                        },
+                        Imp::Soft,
+                    ),
+                    Backend::Vperm if vperm::available() => (
+                        $module::Inner {
+                            vperm: ManuallyDrop::new(vperm::$name::new_with_backend(key, backend)?),
+                        },
+                        Imp::Vperm,
+                    ),
+                    _ if arch::features::aes::get() => (
+                        $module::Inner {
+                            arch: ManuallyDrop::new(arch::$name::new_with_backend(key, backend)?),
+                        },
+                        Imp::Arch,
+                    ),
+                    _ => return Err(BackendUnavailableError(backend)),
+                };
+
+                Ok(Self { inner, imp })
+            }
+
+            /// Initialize cipher from the expanded encryption round keys, e.g.
+            /// as returned by the `hazmat` key expansion functions.
+            #[cfg(feature = "hazmat")]
+            #[inline]
+            pub fn from_round_keys(round_keys: &$round_keys) -> Self {
+                let imp = Imp::detect();
+                let inner = match imp {
+                    Imp::Arch => $module::Inner {
+                        arch: ManuallyDrop::new(arch::$name::from_round_keys(round_keys)),
+                    },
+                    Imp::Vperm => $module::Inner {
+                        vperm: ManuallyDrop::new(vperm::$name::from_round_keys(round_keys)),
+                    },
+                    Imp::Soft => $module::Inner {
+                        soft: ManuallyDrop::new(soft::$name::from_round_keys(round_keys)),
+                    },
+                };
+
+                Self { inner, imp }
+            }
+
+            /// Get the backend used by this cipher instance.
+            #[inline]
+            pub fn backend(&self) -> Backend {
+                with_inner!(self, |inner| inner.backend())
+            }
+
+            /// Re-initialize cipher with a new key, reusing the existing storage
+            /// and backend selection.
+            ///
+            /// When the `zeroize` feature is enabled, the previous key schedule
+            /// is zeroized before being overwritten.
+            #[inline]
+            pub fn rekey(&mut self, key: &Key<Self>) {
+                with_inner!(mut self, |inner| inner.rekey(key))
+            }
         }
 
         impl KeySizeUser for $name {
             type KeySize = $key_size;
         }
+
         impl From<$name_enc> for $name {
             #[inline]
             fn from(enc: $name_enc) -> $name {
@@ -65,19 +202,21 @@ macro_rules! define_aes_impl {
         impl From<&$name_enc> for $name {
             fn from(enc: &$name_enc) -> $name {
                 use core::ops::Deref;
-                let inner = if enc.token.get() {
-                    $module::Inner {
+                let inner = match enc.imp {
+                    Imp::Arch => $module::Inner {
                         arch: ManuallyDrop::new(unsafe { enc.inner.arch.deref().into() }),
-                    }
-                } else {
-                    $module::Inner {
+                    },
+                    Imp::Vperm => $module::Inner {
+                        vperm: ManuallyDrop::new(unsafe { enc.inner.vperm.deref().into() }),
+                    },
+                    Imp::Soft => $module::Inner {
                         soft: ManuallyDrop::new(unsafe { enc.inner.soft.deref().into() }),
-                    }
+                    },
                 };
 
                 Self {
                     inner,
-                    token: enc.token,
+                    imp: enc.imp,
                 }
             }
         }
@@ -85,37 +224,40 @@ macro_rules! define_aes_impl {
         impl KeyInit for $name {
             #[inline]
             fn new(key: &Key<Self>) -> Self {
-                let (token, aes_features) = arch::features::aes::init_get();
-
-                let inner = if aes_features {
-                    $module::Inner {
+                let imp = Imp::detect();
+                let inner = match imp {
+                    Imp::Arch => $module::Inner {
                         arch: ManuallyDrop::new(arch::$name::new(key)),
-                    }
-                } else {
-                    $module::Inner {
+                    },
+                    Imp::Vperm => $module::Inner {
+                        vperm: ManuallyDrop::new(vperm::$name::new(key)),
+                    },
+                    Imp::Soft => $module::Inner {
                         soft: ManuallyDrop::new(soft::$name::new(key)),
-                    }
+                    },
                 };
 
-                Self { inner, token }
+                Self { inner, imp }
             }
         }
 
         impl Clone for $name {
             fn clone(&self) -> Self {
-                let inner = if self.token.get() {
-                    $module::Inner {
+                let inner = match self.imp {
+                    Imp::Arch => $module::Inner {
                         arch: unsafe { self.inner.arch.clone() },
-                    }
-                } else {
-                    $module::Inner {
+                    },
+                    Imp::Vperm => $module::Inner {
+                        vperm: unsafe { self.inner.vperm.clone() },
+                    },
+                    Imp::Soft => $module::Inner {
                         soft: unsafe { self.inner.soft.clone() },
-                    }
+                    },
                 };
 
                 Self {
                     inner,
-                    token: self.token,
+                    imp: self.imp,
                 }
             }
         }
@@ -126,21 +268,13 @@ macro_rules! define_aes_impl {
 
         impl BlockCipherEncrypt for $name {
             fn encrypt_with_backend(&self, f: impl BlockCipherEncClosure<BlockSize = U16>) {
-                if self.token.get() {
-                    unsafe { &self.inner.arch }.encrypt_with_backend(f)
-                } else {
-                    unsafe { &self.inner.soft }.encrypt_with_backend(f)
-                }
+                with_inner!(self, |inner| inner.encrypt_with_backend(f))
             }
         }
 
         impl BlockCipherDecrypt for $name {
             fn decrypt_with_backend(&self, f: impl BlockCipherDecClosure<BlockSize = U16>) {
-                if self.token.get() {
-                    unsafe { &self.inner.arch }.decrypt_with_backend(f)
-                } else {
-                    unsafe { &self.inner.soft }.decrypt_with_backend(f)
-                }
+                with_inner!(self, |inner| inner.decrypt_with_backend(f))
             }
         }
 
@@ -159,11 +293,11 @@ macro_rules! define_aes_impl {
         impl Drop for $name {
             #[inline]
             fn drop(&mut self) {
-                if self.token.get() {
-                    unsafe { ManuallyDrop::drop(&mut self.inner.arch) };
-                } else {
-                    unsafe { ManuallyDrop::drop(&mut self.inner.soft) };
-                };
+                match self.imp {
+                    Imp::Arch => unsafe { ManuallyDrop::drop(&mut self.inner.arch) },
+                    Imp::Vperm => unsafe { ManuallyDrop::drop(&mut self.inner.vperm) },
+                    Imp::Soft => unsafe { ManuallyDrop::drop(&mut self.inner.soft) },
+                }
             }
         }
 
@@ -174,7 +308,80 @@ macro_rules! define_aes_impl {
         #[doc = "block cipher (encrypt-only)"]
         pub struct $name_enc {
             inner: $module::InnerEnc,
-            token: arch::features::aes::InitToken,
+            imp: Imp,
+        }
+
+        impl $name_enc {
+            /// Initialize cipher using the specified [`Backend`].
+            ///
+            /// Returns [`BackendUnavailableError`] if the backend is not
+            /// supported by the current CPU or was disabled at compile time.
+            #[inline]
+            pub fn new_with_backend(
+                key: &Key<Self>,
+                backend: Backend,
+            ) -> Result<Self, BackendUnavailableError> {
+                let (inner, imp) = match backend {
+                    Backend::Soft => (
+                        $module::InnerEnc {
+                            soft: ManuallyDrop::new(soft::$name_enc::new_with_backend(key, backend)?),
+                        },
+                        Imp::Soft,
+                    ),
+                    Backend::Vperm if vperm::available() => (
+                        $module::InnerEnc {
+                            vperm: ManuallyDrop::new(vperm::$name_enc::new_with_backend(key, backend)?),
+                        },
+                        Imp::Vperm,
+                    ),
+                    _ if arch::features::aes::get() => (
+                        $module::InnerEnc {
+                            arch: ManuallyDrop::new(arch::$name_enc::new_with_backend(key, backend)?),
+                        },
+                        Imp::Arch,
+                    ),
+                    _ => return Err(BackendUnavailableError(backend)),
+                };
+
+                Ok(Self { inner, imp })
+            }
+
+            /// Initialize cipher from the expanded encryption round keys, e.g.
+            /// as returned by the `hazmat` key expansion functions.
+            #[cfg(feature = "hazmat")]
+            #[inline]
+            pub fn from_round_keys(round_keys: &$round_keys) -> Self {
+                let imp = Imp::detect();
+                let inner = match imp {
+                    Imp::Arch => $module::InnerEnc {
+                        arch: ManuallyDrop::new(arch::$name_enc::from_round_keys(round_keys)),
+                    },
+                    Imp::Vperm => $module::InnerEnc {
+                        vperm: ManuallyDrop::new(vperm::$name_enc::from_round_keys(round_keys)),
+                    },
+                    Imp::Soft => $module::InnerEnc {
+                        soft: ManuallyDrop::new(soft::$name_enc::from_round_keys(round_keys)),
+                    },
+                };
+
+                Self { inner, imp }
+            }
+
+            /// Get the backend used by this cipher instance.
+            #[inline]
+            pub fn backend(&self) -> Backend {
+                with_inner!(self, |inner| inner.backend())
+            }
+
+            /// Re-initialize cipher with a new key, reusing the existing storage
+            /// and backend selection.
+            ///
+            /// When the `zeroize` feature is enabled, the previous key schedule
+            /// is zeroized before being overwritten.
+            #[inline]
+            pub fn rekey(&mut self, key: &Key<Self>) {
+                with_inner!(mut self, |inner| inner.rekey(key))
+            }
         }
 
         impl KeySizeUser for $name_enc {
@@ -184,37 +391,40 @@ macro_rules! define_aes_impl {
         impl KeyInit for $name_enc {
             #[inline]
             fn new(key: &Key<Self>) -> Self {
-                let (token, aes_features) = arch::features::aes::init_get();
-
-                let inner = if aes_features {
-                    $module::InnerEnc {
+                let imp = Imp::detect();
+                let inner = match imp {
+                    Imp::Arch => $module::InnerEnc {
                         arch: ManuallyDrop::new(arch::$name_enc::new(key)),
-                    }
-                } else {
-                    $module::InnerEnc {
+                    },
+                    Imp::Vperm => $module::InnerEnc {
+                        vperm: ManuallyDrop::new(vperm::$name_enc::new(key)),
+                    },
+                    Imp::Soft => $module::InnerEnc {
                         soft: ManuallyDrop::new(soft::$name_enc::new(key)),
-                    }
+                    },
                 };
 
-                Self { inner, token }
+                Self { inner, imp }
             }
         }
 
         impl Clone for $name_enc {
             fn clone(&self) -> Self {
-                let inner = if self.token.get() {
-                    $module::InnerEnc {
+                let inner = match self.imp {
+                    Imp::Arch => $module::InnerEnc {
                         arch: unsafe { self.inner.arch.clone() },
-                    }
-                } else {
-                    $module::InnerEnc {
+                    },
+                    Imp::Vperm => $module::InnerEnc {
+                        vperm: unsafe { self.inner.vperm.clone() },
+                    },
+                    Imp::Soft => $module::InnerEnc {
                         soft: unsafe { self.inner.soft.clone() },
-                    }
+                    },
                 };
 
                 Self {
                     inner,
-                    token: self.token,
+                    imp: self.imp,
                 }
             }
         }
@@ -225,11 +435,7 @@ macro_rules! define_aes_impl {
 
         impl BlockCipherEncrypt for $name_enc {
             fn encrypt_with_backend(&self, f: impl BlockCipherEncClosure<BlockSize = U16>) {
-                if self.token.get() {
-                    unsafe { &self.inner.arch }.encrypt_with_backend(f)
-                } else {
-                    unsafe { &self.inner.soft }.encrypt_with_backend(f)
-                }
+                with_inner!(self, |inner| inner.encrypt_with_backend(f))
             }
         }
 
@@ -248,11 +454,11 @@ macro_rules! define_aes_impl {
         impl Drop for $name_enc {
             #[inline]
             fn drop(&mut self) {
-                if self.token.get() {
-                    unsafe { ManuallyDrop::drop(&mut self.inner.arch) };
-                } else {
-                    unsafe { ManuallyDrop::drop(&mut self.inner.soft) };
-                };
+                match self.imp {
+                    Imp::Arch => unsafe { ManuallyDrop::drop(&mut self.inner.arch) },
+                    Imp::Vperm => unsafe { ManuallyDrop::drop(&mut self.inner.vperm) },
+                    Imp::Soft => unsafe { ManuallyDrop::drop(&mut self.inner.soft) },
+                }
             }
         }
 
@@ -263,7 +469,80 @@ macro_rules! define_aes_impl {
         #[doc = "block cipher (decrypt-only)"]
         pub struct $name_dec {
             inner: $module::InnerDec,
-            token: arch::features::aes::InitToken,
+            imp: Imp,
+        }
+
+        impl $name_dec {
+            /// Initialize cipher using the specified [`Backend`].
+            ///
+            /// Returns [`BackendUnavailableError`] if the backend is not
+            /// supported by the current CPU or was disabled at compile time.
+            #[inline]
+            pub fn new_with_backend(
+                key: &Key<Self>,
+                backend: Backend,
+            ) -> Result<Self, BackendUnavailableError> {
+                let (inner, imp) = match backend {
+                    Backend::Soft => (
+                        $module::InnerDec {
+                            soft: ManuallyDrop::new(soft::$name_dec::new_with_backend(key, backend)?),
+                        },
+                        Imp::Soft,
+                    ),
+                    Backend::Vperm if vperm::available() => (
+                        $module::InnerDec {
+                            vperm: ManuallyDrop::new(vperm::$name_dec::new_with_backend(key, backend)?),
+                        },
+                        Imp::Vperm,
+                    ),
+                    _ if arch::features::aes::get() => (
+                        $module::InnerDec {
+                            arch: ManuallyDrop::new(arch::$name_dec::new_with_backend(key, backend)?),
+                        },
+                        Imp::Arch,
+                    ),
+                    _ => return Err(BackendUnavailableError(backend)),
+                };
+
+                Ok(Self { inner, imp })
+            }
+
+            /// Initialize cipher from the expanded encryption round keys, e.g.
+            /// as returned by the `hazmat` key expansion functions.
+            #[cfg(feature = "hazmat")]
+            #[inline]
+            pub fn from_round_keys(round_keys: &$round_keys) -> Self {
+                let imp = Imp::detect();
+                let inner = match imp {
+                    Imp::Arch => $module::InnerDec {
+                        arch: ManuallyDrop::new(arch::$name_dec::from_round_keys(round_keys)),
+                    },
+                    Imp::Vperm => $module::InnerDec {
+                        vperm: ManuallyDrop::new(vperm::$name_dec::from_round_keys(round_keys)),
+                    },
+                    Imp::Soft => $module::InnerDec {
+                        soft: ManuallyDrop::new(soft::$name_dec::from_round_keys(round_keys)),
+                    },
+                };
+
+                Self { inner, imp }
+            }
+
+            /// Get the backend used by this cipher instance.
+            #[inline]
+            pub fn backend(&self) -> Backend {
+                with_inner!(self, |inner| inner.backend())
+            }
+
+            /// Re-initialize cipher with a new key, reusing the existing storage
+            /// and backend selection.
+            ///
+            /// When the `zeroize` feature is enabled, the previous key schedule
+            /// is zeroized before being overwritten.
+            #[inline]
+            pub fn rekey(&mut self, key: &Key<Self>) {
+                with_inner!(mut self, |inner| inner.rekey(key))
+            }
         }
 
         impl KeySizeUser for $name_dec {
@@ -280,19 +559,21 @@ macro_rules! define_aes_impl {
         impl From<&$name_enc> for $name_dec {
             fn from(enc: &$name_enc) -> $name_dec {
                 use core::ops::Deref;
-                let inner = if enc.token.get() {
-                    $module::InnerDec {
+                let inner = match enc.imp {
+                    Imp::Arch => $module::InnerDec {
                         arch: ManuallyDrop::new(unsafe { enc.inner.arch.deref().into() }),
-                    }
-                } else {
-                    $module::InnerDec {
+                    },
+                    Imp::Vperm => $module::InnerDec {
+                        vperm: ManuallyDrop::new(unsafe { enc.inner.vperm.deref().into() }),
+                    },
+                    Imp::Soft => $module::InnerDec {
                         soft: ManuallyDrop::new(unsafe { enc.inner.soft.deref().into() }),
-                    }
+                    },
                 };
 
                 Self {
                     inner,
-                    token: enc.token,
+                    imp: enc.imp,
                 }
             }
         }
@@ -300,37 +581,40 @@ macro_rules! define_aes_impl {
         impl KeyInit for $name_dec {
             #[inline]
             fn new(key: &Key<Self>) -> Self {
-                let (token, aes_features) = arch::features::aes::init_get();
-
-                let inner = if aes_features {
-                    $module::InnerDec {
+                let imp = Imp::detect();
+                let inner = match imp {
+                    Imp::Arch => $module::InnerDec {
                         arch: ManuallyDrop::new(arch::$name_dec::new(key)),
-                    }
-                } else {
-                    $module::InnerDec {
+                    },
+                    Imp::Vperm => $module::InnerDec {
+                        vperm: ManuallyDrop::new(vperm::$name_dec::new(key)),
+                    },
+                    Imp::Soft => $module::InnerDec {
                         soft: ManuallyDrop::new(soft::$name_dec::new(key)),
-                    }
+                    },
                 };
 
-                Self { inner, token }
+                Self { inner, imp }
             }
         }
 
         impl Clone for $name_dec {
             fn clone(&self) -> Self {
-                let inner = if self.token.get() {
-                    $module::InnerDec {
+                let inner = match self.imp {
+                    Imp::Arch => $module::InnerDec {
                         arch: unsafe { self.inner.arch.clone() },
-                    }
-                } else {
-                    $module::InnerDec {
+                    },
+                    Imp::Vperm => $module::InnerDec {
+                        vperm: unsafe { self.inner.vperm.clone() },
+                    },
+                    Imp::Soft => $module::InnerDec {
                         soft: unsafe { self.inner.soft.clone() },
-                    }
+                    },
                 };
 
                 Self {
                     inner,
-                    token: self.token,
+                    imp: self.imp,
                 }
             }
         }
@@ -341,11 +625,7 @@ macro_rules! define_aes_impl {
 
         impl BlockCipherDecrypt for $name_dec {
             fn decrypt_with_backend(&self, f: impl BlockCipherDecClosure<BlockSize = U16>) {
-                if self.token.get() {
-                    unsafe { &self.inner.arch }.decrypt_with_backend(f)
-                } else {
-                    unsafe { &self.inner.soft }.decrypt_with_backend(f)
-                }
+                with_inner!(self, |inner| inner.decrypt_with_backend(f))
             }
         }
 
@@ -364,11 +644,11 @@ macro_rules! define_aes_impl {
         impl Drop for $name_dec {
             #[inline]
             fn drop(&mut self) {
-                if self.token.get() {
-                    unsafe { ManuallyDrop::drop(&mut self.inner.arch) };
-                } else {
-                    unsafe { ManuallyDrop::drop(&mut self.inner.soft) };
-                };
+                match self.imp {
+                    Imp::Arch => unsafe { ManuallyDrop::drop(&mut self.inner.arch) },
+                    Imp::Vperm => unsafe { ManuallyDrop::drop(&mut self.inner.vperm) },
+                    Imp::Soft => unsafe { ManuallyDrop::drop(&mut self.inner.soft) },
+                }
             }
         }
 
@@ -383,6 +663,7 @@ define_aes_impl!(
     name_dec = Aes128Dec,
     module = aes128,
     key_size = U16,
+    round_keys = crate::hazmat::Aes128RoundKeys,
     doc = "AES-128",
 );
 define_aes_impl!(
@@ -391,6 +672,7 @@ define_aes_impl!(
     name_dec = Aes192Dec,
     module = aes192,
     key_size = U24,
+    round_keys = crate::hazmat::Aes192RoundKeys,
     doc = "AES-192",
 );
 define_aes_impl!(
@@ -399,5 +681,6 @@ define_aes_impl!(
     name_dec = Aes256Dec,
     module = aes256,
     key_size = U32,
+    round_keys = crate::hazmat::Aes256RoundKeys,
     doc = "AES-256",
 );
@@ -0,0 +1,70 @@
+//! Backend introspection and selection.
+
+use core::fmt;
+
+/// AES implementation backend.
+///
+/// Returned by the `backend` method of the AES types and accepted by their
+/// `new_with_backend` constructors, which allows to pin a particular
+/// implementation at runtime (e.g. for benchmarking or differential testing).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum Backend {
+    /// Portable constant-time implementation based on fixslicing.
+    Soft,
+    /// Constant-time implementation based on SSSE3 (`x86`/`x86_64`) or NEON
+    /// (`aarch64`) vector permute instructions, for CPUs without AES
+    /// instructions.
+    Vperm,
+    /// AES-NI intrinsics (`x86`/`x86_64`).
+    Ni,
+    /// VAES intrinsics operating on 256-bit AVX2 registers (`x86_64`).
+    Vaes256,
+    /// VAES intrinsics operating on 512-bit AVX-512 registers (`x86_64`).
+    Vaes512,
+    /// ARMv8 Cryptography Extensions (`aarch64`).
+    Armv8,
+}
+
+impl Backend {
+    /// Human-readable name of the backend.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Backend::Soft => "soft",
+            Backend::Vperm => "vperm",
+            Backend::Ni => "AES-NI",
+            Backend::Vaes256 => "VAES256",
+            Backend::Vaes512 => "VAES512",
+            Backend::Armv8 => "ARMv8",
+        }
+    }
+}
+
+impl fmt::Display for Backend {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The error type returned when the requested [`Backend`] is not supported
+/// by the current CPU or was disabled at compile time.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct BackendUnavailableError(pub(crate) Backend);
+
+impl BackendUnavailableError {
+    /// Backend which was requested.
+    #[inline]
+    pub fn backend(&self) -> Backend {
+        self.0
+    }
+}
+
+impl fmt::Display for BackendUnavailableError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "AES backend is not available: {}", self.0)
+    }
+}
+
+impl core::error::Error for BackendUnavailableError {}
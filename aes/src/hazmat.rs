@@ -12,11 +12,19 @@
 //! received extensive peer review by cryptographers.
 
 use crate::soft::fixslice::hazmat as soft;
+use cipher::array::{Array, ArraySize};
 
 pub use crate::Block;
 /// Eight 128-bit AES blocks
 pub type Block8 = cipher::array::Array<Block, cipher::consts::U8>;
 
+/// AES-128 round keys
+pub type Aes128RoundKeys = [Block; 11];
+/// AES-192 round keys
+pub type Aes192RoundKeys = [Block; 13];
+/// AES-256 round keys
+pub type Aes256RoundKeys = [Block; 15];
+
 #[cfg(all(target_arch = "aarch64", not(aes_backend = "soft")))]
 use crate::armv8::hazmat as intrinsics;
 
@@ -24,7 +32,7 @@ use crate::armv8::hazmat as intrinsics;
     any(target_arch = "x86", target_arch = "x86_64"),
     not(aes_backend = "soft")
 ))]
-use crate::x86::ni::hazmat as intrinsics;
+use crate::x86::hazmat as intrinsics;
 
 #[cfg(all(
     any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"),
@@ -41,8 +49,7 @@ macro_rules! if_intrinsics_available {
             not(aes_backend = "soft")
         ))]
         if aes_intrinsics::get() {
-            unsafe { $body }
-            return;
+            return unsafe { $body };
         }
     }};
 }
@@ -72,14 +79,17 @@ pub fn cipher_round(block: &mut Block, round_key: &Block) {
 
 /// ⚠️ AES cipher (encrypt) round function: parallel version.
 ///
-/// Equivalent to [`cipher_round`], but acts on 8 blocks-at-a-time, applying
-/// the same number of round keys.
+/// Equivalent to [`cipher_round`], but acts on `N` blocks-at-a-time, applying
+/// the same number of round keys, i.e. `round_keys[i]` is used for `blocks[i]`.
+///
+/// The blocks are processed using the widest parallel instructions available,
+/// e.g. VAES on x86_64 CPUs which support it.
 ///
 /// # ☢️️ WARNING: HAZARDOUS API ☢️
 ///
 /// Use this function with great care! See the [module-level documentation][crate::hazmat]
 /// for more information.
-pub fn cipher_round_par(blocks: &mut Block8, round_keys: &Block8) {
+pub fn cipher_round_par<N: ArraySize>(blocks: &mut Array<Block, N>, round_keys: &Array<Block, N>) {
     if_intrinsics_available! {
         intrinsics::cipher_round_par(blocks, round_keys)
     }
@@ -87,6 +97,50 @@ pub fn cipher_round_par(blocks: &mut Block8, round_keys: &Block8) {
     soft::cipher_round_par(blocks, round_keys);
 }
 
+/// ⚠️ AES cipher (encrypt) final round function.
+///
+/// The final round of the cipher omits the `MixColumns()` step. This API
+/// performs the following steps as described in FIPS 197 Appendix C:
+///
+/// - `s_box`: state after `SubBytes()`
+/// - `s_row`: state after `ShiftRows()`
+/// - `k_sch`: key schedule value for `round[r]`
+///
+/// This series of operations is equivalent to the Intel AES-NI `AESENCLAST` instruction.
+///
+/// # ☢️️ WARNING: HAZARDOUS API ☢️
+///
+/// Use this function with great care! See the [module-level documentation][crate::hazmat]
+/// for more information.
+pub fn cipher_final_round(block: &mut Block, round_key: &Block) {
+    if_intrinsics_available! {
+        intrinsics::cipher_final_round(block, round_key)
+    }
+
+    soft::cipher_final_round(block, round_key);
+}
+
+/// ⚠️ AES cipher (encrypt) final round function: parallel version.
+///
+/// Equivalent to [`cipher_final_round`], but acts on `N` blocks-at-a-time,
+/// applying the same number of round keys. See [`cipher_round_par`] for more
+/// information.
+///
+/// # ☢️️ WARNING: HAZARDOUS API ☢️
+///
+/// Use this function with great care! See the [module-level documentation][crate::hazmat]
+/// for more information.
+pub fn cipher_final_round_par<N: ArraySize>(
+    blocks: &mut Array<Block, N>,
+    round_keys: &Array<Block, N>,
+) {
+    if_intrinsics_available! {
+        intrinsics::cipher_final_round_par(blocks, round_keys)
+    }
+
+    soft::cipher_final_round_par(blocks, round_keys);
+}
+
 /// ⚠️ AES equivalent inverse cipher (decrypt) round function.
 ///
 /// This API performs the following steps as described in FIPS 197 Appendix C:
@@ -112,14 +166,18 @@ pub fn equiv_inv_cipher_round(block: &mut Block, round_key: &Block) {
 
 /// ⚠️ AES equivalent inverse cipher (decrypt) round function: parallel version.
 ///
-/// Equivalent to [`equiv_inv_cipher_round`], but acts on 8 blocks-at-a-time,
-/// applying the same number of round keys.
+/// Equivalent to [`equiv_inv_cipher_round`], but acts on `N` blocks-at-a-time,
+/// applying the same number of round keys. See [`cipher_round_par`] for more
+/// information.
 ///
 /// # ☢️️ WARNING: HAZARDOUS API ☢️
 ///
 /// Use this function with great care! See the [module-level documentation][crate::hazmat]
 /// for more information.
-pub fn equiv_inv_cipher_round_par(blocks: &mut Block8, round_keys: &Block8) {
+pub fn equiv_inv_cipher_round_par<N: ArraySize>(
+    blocks: &mut Array<Block, N>,
+    round_keys: &Array<Block, N>,
+) {
     if_intrinsics_available! {
         intrinsics::equiv_inv_cipher_round_par(blocks, round_keys)
     }
@@ -127,6 +185,98 @@ pub fn equiv_inv_cipher_round_par(blocks: &mut Block8, round_keys: &Block8) {
     soft::equiv_inv_cipher_round_par(blocks, round_keys);
 }
 
+/// ⚠️ AES inverse cipher (decrypt) round function.
+///
+/// This API performs the following steps as described in FIPS 197 Appendix C:
+///
+/// - `is_row`: state after `InvShiftRows()`
+/// - `is_box`: state after `InvSubBytes()`
+/// - `ik_sch`: key schedule value for `round[r]`
+/// - `ik_add`: state after `AddRoundKey()`
+///
+/// The `InvMixColumns()` step is then applied to produce the state at the
+/// start of `round[r+1]`. Unlike [`equiv_inv_cipher_round`], this function
+/// uses the encryption round keys as returned by [`aes128_expand_key`] and
+/// friends, in reverse order.
+///
+/// # ☢️️ WARNING: HAZARDOUS API ☢️
+///
+/// Use this function with great care! See the [module-level documentation][crate::hazmat]
+/// for more information.
+pub fn inv_cipher_round(block: &mut Block, round_key: &Block) {
+    if_intrinsics_available! {
+        intrinsics::inv_cipher_round(block, round_key)
+    }
+
+    soft::inv_cipher_round(block, round_key);
+}
+
+/// ⚠️ AES inverse cipher (decrypt) round function: parallel version.
+///
+/// Equivalent to [`inv_cipher_round`], but acts on `N` blocks-at-a-time,
+/// applying the same number of round keys. See [`cipher_round_par`] for more
+/// information.
+///
+/// # ☢️️ WARNING: HAZARDOUS API ☢️
+///
+/// Use this function with great care! See the [module-level documentation][crate::hazmat]
+/// for more information.
+pub fn inv_cipher_round_par<N: ArraySize>(
+    blocks: &mut Array<Block, N>,
+    round_keys: &Array<Block, N>,
+) {
+    if_intrinsics_available! {
+        intrinsics::inv_cipher_round_par(blocks, round_keys)
+    }
+
+    soft::inv_cipher_round_par(blocks, round_keys);
+}
+
+/// ⚠️ AES inverse cipher (decrypt) final round function.
+///
+/// The final round of the inverse cipher (and equivalent inverse cipher)
+/// omits the `InvMixColumns()` step. This API performs the following steps as
+/// described in FIPS 197 Appendix C:
+///
+/// - `is_row`: state after `InvShiftRows()`
+/// - `is_box`: state after `InvSubBytes()`
+/// - `ik_sch`: key schedule value for `round[r]`
+///
+/// This series of operations is equivalent to the Intel AES-NI `AESDECLAST` instruction.
+///
+/// # ☢️️ WARNING: HAZARDOUS API ☢️
+///
+/// Use this function with great care! See the [module-level documentation][crate::hazmat]
+/// for more information.
+pub fn inv_cipher_final_round(block: &mut Block, round_key: &Block) {
+    if_intrinsics_available! {
+        intrinsics::inv_cipher_final_round(block, round_key)
+    }
+
+    soft::inv_cipher_final_round(block, round_key);
+}
+
+/// ⚠️ AES inverse cipher (decrypt) final round function: parallel version.
+///
+/// Equivalent to [`inv_cipher_final_round`], but acts on `N` blocks-at-a-time,
+/// applying the same number of round keys. See [`cipher_round_par`] for more
+/// information.
+///
+/// # ☢️️ WARNING: HAZARDOUS API ☢️
+///
+/// Use this function with great care! See the [module-level documentation][crate::hazmat]
+/// for more information.
+pub fn inv_cipher_final_round_par<N: ArraySize>(
+    blocks: &mut Array<Block, N>,
+    round_keys: &Array<Block, N>,
+) {
+    if_intrinsics_available! {
+        intrinsics::inv_cipher_final_round_par(blocks, round_keys)
+    }
+
+    soft::inv_cipher_final_round_par(blocks, round_keys);
+}
+
 /// ⚠️ AES mix columns function.
 ///
 /// # ☢️️ WARNING: HAZARDOUS API ☢️
@@ -156,3 +306,79 @@ pub fn inv_mix_columns(block: &mut Block) {
 
     soft::inv_mix_columns(block);
 }
+
+/// ⚠️ AES-128 key expansion.
+///
+/// Expands `key` into the key schedule described in FIPS 197 Section 5.2,
+/// i.e. `round_keys[r]` is the `k_sch` value for `round[r]` in FIPS 197
+/// Appendix C, with `round_keys[0]` being equal to `key`.
+///
+/// # ☢️️ WARNING: HAZARDOUS API ☢️
+///
+/// Use this function with great care! See the [module-level documentation][crate::hazmat]
+/// for more information.
+pub fn aes128_expand_key(key: &[u8; 16]) -> Aes128RoundKeys {
+    if_intrinsics_available! {
+        intrinsics::aes128_expand_key(key)
+    }
+
+    soft::expand_key(key)
+}
+
+/// ⚠️ AES-192 key expansion.
+///
+/// See [`aes128_expand_key`] for more information.
+///
+/// # ☢️️ WARNING: HAZARDOUS API ☢️
+///
+/// Use this function with great care! See the [module-level documentation][crate::hazmat]
+/// for more information.
+pub fn aes192_expand_key(key: &[u8; 24]) -> Aes192RoundKeys {
+    if_intrinsics_available! {
+        intrinsics::aes192_expand_key(key)
+    }
+
+    soft::expand_key(key)
+}
+
+/// ⚠️ AES-256 key expansion.
+///
+/// See [`aes128_expand_key`] for more information.
+///
+/// # ☢️️ WARNING: HAZARDOUS API ☢️
+///
+/// Use this function with great care! See the [module-level documentation][crate::hazmat]
+/// for more information.
+pub fn aes256_expand_key(key: &[u8; 32]) -> Aes256RoundKeys {
+    if_intrinsics_available! {
+        intrinsics::aes256_expand_key(key)
+    }
+
+    soft::expand_key(key)
+}
+
+/// ⚠️ AES equivalent inverse cipher key schedule.
+///
+/// Computes the decryption round keys for the equivalent inverse cipher
+/// described in FIPS 197 Section 5.3.5 from the encryption round keys
+/// returned by [`aes128_expand_key`], [`aes192_expand_key`] or
+/// [`aes256_expand_key`].
+///
+/// The keys are returned in the order of use, i.e. `inv_round_keys[r]` is the
+/// `ik_sch` value for `round[r]` in FIPS 197 Appendix C, which can be passed
+/// to [`equiv_inv_cipher_round`]. This is the same layout as expected by the
+/// Intel AES-NI `AESDEC` instruction.
+///
+/// # ☢️️ WARNING: HAZARDOUS API ☢️
+///
+/// Use this function with great care! See the [module-level documentation][crate::hazmat]
+/// for more information.
+pub fn inv_expanded_keys<const N: usize>(round_keys: &[Block; N]) -> [Block; N] {
+    const { assert!(N >= 2, "at least two round keys are required") };
+
+    if_intrinsics_available! {
+        intrinsics::inv_expanded_keys(round_keys)
+    }
+
+    soft::inv_expanded_keys(round_keys)
+}
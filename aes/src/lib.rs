@@ -26,6 +26,20 @@
 //! backend at the cost of decreased performance (using a modified form of
 //! the fixslicing technique called "semi-fixslicing").
 //!
+//! When this backend is used exclusively (i.e. on targets without support
+//! for AES intrinsics or with `aes_backend="soft"`), the AES types also
+//! provide `new_const` constructors which allow to expand fixed keys at
+//! compile time.
+//!
+//! ## Vector permute backend
+//! On CPUs which lack AES instructions but support SSSE3 (`x86`/`x86_64`) or
+//! NEON (`aarch64`), a constant-time implementation based on [vector permute]
+//! instructions is used instead of the "soft" backend. It computes the S-box
+//! using 16-entry table lookups performed by the `pshufb`/`tbl` instructions,
+//! which do not leak the looked-up indices via timing. Four blocks are
+//! processed in an interleaved fashion, which makes it faster than the "soft"
+//! backend for both single blocks and bulk encryption.
+//!
 //! ## ARMv8 intrinsics (Rust 1.61+)
 //! On `aarch64` targets including `aarch64-apple-darwin` (Apple M1) and Linux
 //! targets such as `aarch64-unknown-linux-gnu` and `aarch64-unknown-linux-musl`,
@@ -38,22 +52,46 @@
 //! ## `x86`/`x86_64` intrinsics (AES-NI and VAES)
 //! By default this crate uses runtime detection on `i686`/`x86_64` targets
 //! in order to determine if AES-NI and VAES are available, and if they are
-//! not, it will fallback to using a constant-time software implementation.
+//! not, it will fallback to using the vector permute backend on CPUs with
+//! SSSE3 and to the constant-time software implementation otherwise.
+//!
+//! On `x86_64` targets the widest available backend is used: VAES512 (requires
+//! `avx512f` and `vaes`) processes 64 blocks in parallel, VAES256 (requires
+//! `avx2` and `vaes`) processes 30 blocks in parallel, and AES-NI processes
+//! 8 blocks in parallel.
 //!
 //! Passing `RUSTFLAGS=-Ctarget-feature=+aes,+ssse3` explicitly at
 //! compile-time will override runtime detection and ensure that AES-NI is
 //! used or passing `RUSTFLAGS=-Ctarget-feature=+aes,+avx512f,+ssse3,+vaes`
 //! will ensure that AESNI and VAES are always used.
 //!
-//! Note: Enabling VAES256 or VAES512 still requires specifying `--cfg
-//! aes_backend = "avx256"` or `--cfg aes_backend = "avx512"` explicitly.
-//!
 //! Programs built in this manner will crash with an illegal instruction on
 //! CPUs which do not have AES-NI and VAES enabled.
 //!
 //! Note: runtime detection is not possible on SGX targets. Please use the
 //! aforementioned `RUSTFLAGS` to leverage AES-NI and VAES on these targets.
 //!
+//! ## Runtime backend introspection
+//! The backend used by a cipher instance can be queried using the `backend`
+//! method, while the `new_with_backend` constructors allow to pin a specific
+//! [`Backend`] at runtime, returning [`BackendUnavailableError`] if the CPU
+//! does not support it:
+//! ```
+//! use aes::{Aes128, Backend};
+//! use aes::cipher::{Array, BlockCipherEncrypt, KeyInit};
+//!
+//! let key = Array::from([0u8; 16]);
+//! let default = Aes128::new(&key);
+//! let soft = Aes128::new_with_backend(&key, Backend::Soft).unwrap();
+//! assert_eq!(soft.backend(), Backend::Soft);
+//!
+//! let mut block1 = Array::from([42u8; 16]);
+//! let mut block2 = block1;
+//! default.encrypt_block(&mut block1);
+//! soft.encrypt_block(&mut block2);
+//! assert_eq!(block1, block2);
+//! ```
+//!
 //! # Examples
 //! ```
 //! use aes::Aes128;
@@ -98,14 +136,22 @@
 //! For implementation of block cipher modes of operation see
 //! [`block-modes`] repository.
 //!
+//! # Rijndael-256
+//! The [`Rijndael256_128`], [`Rijndael256_192`] and [`Rijndael256_256`] types
+//! implement Rijndael with a 256-bit block size, which is not part of the AES
+//! standard. They are built on the same round primitives as AES and use AES-NI
+//! (with SSSE3) or ARMv8 intrinsics if available, falling back to the "soft"
+//! backend otherwise.
+//!
 //! # Configuration Flags
 //!
 //! You can modify crate using the following configuration flags:
 //!
 //! - `aes_backend`: explicitly select one of the following backends:
 //!   - `soft`: force software backend
-//!   - `avx256`: force AVX2 backend
-//!   - `avx512`: force AVX-512 backend
+//!   - `ni`: use AES-NI, but disable the VAES backends
+//!   - `avx256`: use AES-NI and VAES256, but disable the VAES512 backend
+//!   - `avx512`: use all available backends (same as the default)
 //! - `aes_backend_soft`: modify software backend:
 //!   - `compact`: use compact implementation (less performant, but results in a smaller binary)
 //!
@@ -114,6 +160,7 @@
 //!
 //! [AES]: https://en.wikipedia.org/wiki/Advanced_Encryption_Standard
 //! [fixslicing]: https://eprint.iacr.org/2020/1123.pdf
+//! [vector permute]: https://shiftleft.org/papers/vector_aes/vector_aes.pdf
 //! [AES-NI]: https://en.wikipedia.org/wiki/AES_instruction_set
 //! [`block-modes`]: https://github.com/RustCrypto/block-modes/
 
@@ -130,12 +177,15 @@ pub mod hazmat;
 
 #[macro_use]
 mod macros;
+mod backend;
+mod rijndael256;
 mod soft;
 
 cpubits::cfg_if! {
     if #[cfg(all(target_arch = "aarch64", not(aes_backend = "soft")))] {
         mod armv8;
         mod autodetect;
+        mod vperm;
         pub use autodetect::*;
     } else if #[cfg(all(
         any(target_arch = "x86", target_arch = "x86_64"),
@@ -143,14 +193,17 @@ cpubits::cfg_if! {
     ))] {
         mod x86;
         mod autodetect;
+        mod vperm;
         pub use autodetect::*;
     } else {
         pub use soft::*;
     }
 }
 
+pub use backend::{Backend, BackendUnavailableError};
 pub use cipher;
 use cipher::{array::Array, consts::U16};
+pub use rijndael256::{Rijndael256_128, Rijndael256_192, Rijndael256_256};
 
 /// 128-bit AES block
 pub type Block = Array<u8, U16>;
@@ -40,6 +40,17 @@ macro_rules! impl_backends {
             }
         }
 
+        impl $enc_name {
+            #[inline]
+            pub(crate) fn rekey(&mut self, key: &cipher::Key<Self>) {
+                #[cfg(feature = "zeroize")]
+                unsafe {
+                    zeroize::zeroize_flat_type(&mut self.keys);
+                }
+                self.keys = unsafe { $expand_keys(key.as_ref()) };
+            }
+        }
+
         impl cipher::BlockCipherEncBackend for $enc_name {
             #[inline(always)]
             fn encrypt_block(&self, block: cipher::inout::InOut<'_, '_, cipher::Block<Self>>) {
@@ -87,6 +98,18 @@ macro_rules! impl_backends {
             }
         }
 
+        impl $dec_name {
+            /// Replace the round keys with the inverse of the `enc` ones.
+            #[inline]
+            pub(crate) fn set_inv_keys(&mut self, enc: &$enc_name) {
+                #[cfg(feature = "zeroize")]
+                unsafe {
+                    zeroize::zeroize_flat_type(&mut self.keys);
+                }
+                self.keys = unsafe { $inv_keys(&enc.keys) };
+            }
+        }
+
         impl cipher::BlockCipherDecBackend for $dec_name {
             #[inline(always)]
             fn decrypt_block(&self, block: cipher::inout::InOut<'_, '_, cipher::Block<Self>>) {
@@ -0,0 +1,333 @@
+//! Rijndael with 256-bit block size.
+//!
+//! Rijndael-256 uses the same round function as AES, but operates on a state
+//! of eight columns and rotates the rows of the state by 1, 3 and 4 columns
+//! in its `ShiftRows` step. This is emulated by permuting the bytes of the
+//! state before each round and then applying the AES round function to both
+//! 128-bit halves of the state, which allows to reuse the constant-time
+//! software backend as well as the AES-NI and ARMv8 intrinsics.
+
+#[cfg(all(target_arch = "aarch64", not(aes_backend = "soft")))]
+mod armv8;
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    not(aes_backend = "soft")
+))]
+mod x86;
+
+#[cfg(all(target_arch = "aarch64", not(aes_backend = "soft")))]
+use armv8 as intrinsics;
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    not(aes_backend = "soft")
+))]
+use x86 as intrinsics;
+
+#[cfg(all(target_arch = "aarch64", not(aes_backend = "soft")))]
+cpufeatures::new!(rijndael256_intrinsics, "aes");
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    not(aes_backend = "soft")
+))]
+cpufeatures::new!(rijndael256_intrinsics, "aes", "ssse3");
+
+use crate::soft::fixslice::{
+    self, BatchBlocks, cipher_round_batch, equiv_inv_cipher_round_batch, inv_mix_columns_batch,
+};
+use cipher::{
+    AlgorithmName, BlockCipherDecBackend, BlockCipherDecClosure, BlockCipherDecrypt,
+    BlockCipherEncBackend, BlockCipherEncClosure, BlockCipherEncrypt, BlockSizeUser, Key, KeyInit,
+    KeySizeUser, ParBlocksSizeUser,
+    consts::{U1, U16, U24, U32},
+    inout::InOut,
+};
+use core::fmt;
+
+/// Number of rounds, i.e. `max(Nb, Nk) + 6` with `Nb = 8`.
+const ROUNDS: usize = 14;
+
+/// Rijndael-256 block.
+type Block = [u8; 32];
+
+/// Expanded round keys.
+type RoundKeys = [Block; ROUNDS + 1];
+
+/// Byte permutation applied to the state before each encryption round, so
+/// that the AES `ShiftRows` of each half results in the Rijndael-256 one.
+const ENC_SHUFFLE: [u8; 32] = [
+    0, 17, 22, 23, 4, 5, 26, 27, 8, 9, 14, 31, 12, 13, 18, 19, 16, 1, 6, 7, 20, 21, 10, 11, 24, 25,
+    30, 15, 28, 29, 2, 3,
+];
+
+/// Byte permutation applied to the state before each decryption round, so
+/// that the AES `InvShiftRows` of each half results in the Rijndael-256 one.
+const DEC_SHUFFLE: [u8; 32] = [
+    0, 1, 30, 31, 4, 5, 2, 19, 8, 9, 22, 23, 12, 29, 26, 27, 16, 17, 14, 15, 20, 21, 18, 3, 24, 25,
+    6, 7, 28, 13, 10, 11,
+];
+
+/// Rijndael key expansion for 256-bit blocks.
+fn expand_key<const L: usize>(key: &[u8; L]) -> RoundKeys {
+    let nk = L / 4;
+
+    let mut columns = [0u32; 8 * (ROUNDS + 1)];
+    for (column, chunk) in columns.iter_mut().zip(key.chunks_exact(4)) {
+        *column = u32::from_be_bytes(chunk.try_into().unwrap());
+    }
+
+    let mut rcon = 1u32;
+    for i in nk..columns.len() {
+        let mut word = columns[i - 1];
+
+        if i % nk == 0 {
+            word = fixslice::sub_word(word.rotate_left(8)) ^ (rcon << 24);
+            rcon = (rcon << 1) ^ ((rcon >> 7) * 0x11b);
+        } else if nk > 6 && i % nk == 4 {
+            word = fixslice::sub_word(word);
+        }
+
+        columns[i] = columns[i - nk] ^ word;
+    }
+
+    let mut keys = [[0u8; 32]; ROUNDS + 1];
+    for (key, words) in keys.iter_mut().zip(columns.chunks_exact(8)) {
+        for (dst, word) in key.chunks_exact_mut(4).zip(words) {
+            dst.copy_from_slice(&word.to_be_bytes());
+        }
+    }
+    keys
+}
+
+/// Compute the equivalent inverse cipher round keys (for decryption).
+///
+/// This is the reverse of the encryption keys, with the Inverse Mix Columns
+/// operation applied to all but the first and last expanded key.
+fn inv_expanded_keys(keys: &RoundKeys) -> RoundKeys {
+    let mut inv_keys = *keys;
+    inv_keys.reverse();
+    for key in &mut inv_keys[1..ROUNDS] {
+        let mut halves = split(key);
+        inv_mix_columns_batch(&mut halves);
+        *key = join(&halves);
+    }
+    inv_keys
+}
+
+/// Load the two halves of a Rijndael-256 block into a fixslice batch.
+fn split(block: &Block) -> BatchBlocks {
+    let mut halves = BatchBlocks::default();
+    halves[0].copy_from_slice(&block[..16]);
+    halves[1].copy_from_slice(&block[16..]);
+    halves
+}
+
+/// Extract a Rijndael-256 block from the first two blocks of a fixslice batch.
+fn join(halves: &BatchBlocks) -> Block {
+    let mut block = [0u8; 32];
+    block[..16].copy_from_slice(&halves[0]);
+    block[16..].copy_from_slice(&halves[1]);
+    block
+}
+
+/// Permute the bytes of `block` according to `shuffle`.
+fn shuffle(block: &Block, shuffle: &[u8; 32]) -> Block {
+    shuffle.map(|i| block[usize::from(i)])
+}
+
+fn xor_in_place(block: &mut Block, key: &Block) {
+    for (a, b) in block.iter_mut().zip(key) {
+        *a ^= *b;
+    }
+}
+
+/// Encrypt a block using the fixsliced software backend.
+fn soft_encrypt(keys: &RoundKeys, block: &mut Block) {
+    xor_in_place(block, &keys[0]);
+    for (round, key) in keys.iter().enumerate().skip(1) {
+        let mut halves = split(&shuffle(block, &ENC_SHUFFLE));
+        cipher_round_batch(&mut halves, round == ROUNDS);
+        *block = join(&halves);
+        xor_in_place(block, key);
+    }
+}
+
+/// Decrypt a block using the fixsliced software backend.
+fn soft_decrypt(inv_keys: &RoundKeys, block: &mut Block) {
+    xor_in_place(block, &inv_keys[0]);
+    for (round, key) in inv_keys.iter().enumerate().skip(1) {
+        let mut halves = split(&shuffle(block, &DEC_SHUFFLE));
+        equiv_inv_cipher_round_batch(&mut halves, round == ROUNDS);
+        *block = join(&halves);
+        xor_in_place(block, key);
+    }
+}
+
+macro_rules! define_rijndael256_impl {
+    (
+        $name:ident,
+        $name_back_enc:ident,
+        $name_back_dec:ident,
+        $key_size:ty,
+        $doc:expr $(,)?
+    ) => {
+        #[doc=$doc]
+        #[doc = "block cipher"]
+        #[derive(Clone)]
+        #[allow(non_camel_case_types)]
+        pub struct $name {
+            keys: RoundKeys,
+            inv_keys: RoundKeys,
+            #[cfg(all(
+                any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"),
+                not(aes_backend = "soft")
+            ))]
+            token: rijndael256_intrinsics::InitToken,
+        }
+
+        impl KeySizeUser for $name {
+            type KeySize = $key_size;
+        }
+
+        impl KeyInit for $name {
+            #[inline]
+            fn new(key: &Key<Self>) -> Self {
+                let keys = expand_key(&key.0);
+                Self {
+                    inv_keys: inv_expanded_keys(&keys),
+                    keys,
+                    #[cfg(all(
+                        any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"),
+                        not(aes_backend = "soft")
+                    ))]
+                    token: rijndael256_intrinsics::init(),
+                }
+            }
+        }
+
+        impl BlockSizeUser for $name {
+            type BlockSize = U32;
+        }
+
+        impl BlockCipherEncrypt for $name {
+            fn encrypt_with_backend(&self, f: impl BlockCipherEncClosure<BlockSize = U32>) {
+                f.call(&$name_back_enc(self))
+            }
+        }
+
+        impl BlockCipherDecrypt for $name {
+            fn decrypt_with_backend(&self, f: impl BlockCipherDecClosure<BlockSize = U32>) {
+                f.call(&$name_back_dec(self))
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+                f.write_str(concat!(stringify!($name), " { .. }"))
+            }
+        }
+
+        impl AlgorithmName for $name {
+            fn write_alg_name(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(stringify!($name))
+            }
+        }
+
+        impl Drop for $name {
+            #[inline]
+            fn drop(&mut self) {
+                #[cfg(feature = "zeroize")]
+                {
+                    zeroize::Zeroize::zeroize(&mut self.keys);
+                    zeroize::Zeroize::zeroize(&mut self.inv_keys);
+                }
+            }
+        }
+
+        #[cfg(feature = "zeroize")]
+        impl zeroize::ZeroizeOnDrop for $name {}
+
+        pub(crate) struct $name_back_enc<'a>(&'a $name);
+
+        impl<'a> BlockSizeUser for $name_back_enc<'a> {
+            type BlockSize = U32;
+        }
+
+        impl<'a> ParBlocksSizeUser for $name_back_enc<'a> {
+            type ParBlocksSize = U1;
+        }
+
+        impl<'a> BlockCipherEncBackend for $name_back_enc<'a> {
+            #[inline(always)]
+            fn encrypt_block(&self, mut block: InOut<'_, '_, cipher::Block<Self>>) {
+                let mut b = block.clone_in().0;
+
+                #[cfg(all(
+                    any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"),
+                    not(aes_backend = "soft")
+                ))]
+                if self.0.token.get() {
+                    unsafe { intrinsics::encrypt(&self.0.keys, &mut b) };
+                    *block.get_out() = b.into();
+                    return;
+                }
+
+                soft_encrypt(&self.0.keys, &mut b);
+                *block.get_out() = b.into();
+            }
+        }
+
+        pub(crate) struct $name_back_dec<'a>(&'a $name);
+
+        impl<'a> BlockSizeUser for $name_back_dec<'a> {
+            type BlockSize = U32;
+        }
+
+        impl<'a> ParBlocksSizeUser for $name_back_dec<'a> {
+            type ParBlocksSize = U1;
+        }
+
+        impl<'a> BlockCipherDecBackend for $name_back_dec<'a> {
+            #[inline(always)]
+            fn decrypt_block(&self, mut block: InOut<'_, '_, cipher::Block<Self>>) {
+                let mut b = block.clone_in().0;
+
+                #[cfg(all(
+                    any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"),
+                    not(aes_backend = "soft")
+                ))]
+                if self.0.token.get() {
+                    unsafe { intrinsics::decrypt(&self.0.inv_keys, &mut b) };
+                    *block.get_out() = b.into();
+                    return;
+                }
+
+                soft_decrypt(&self.0.inv_keys, &mut b);
+                *block.get_out() = b.into();
+            }
+        }
+    };
+}
+
+define_rijndael256_impl!(
+    Rijndael256_128,
+    Rijndael256_128BackEnc,
+    Rijndael256_128BackDec,
+    U16,
+    "Rijndael with 256-bit block and 128-bit key",
+);
+
+define_rijndael256_impl!(
+    Rijndael256_192,
+    Rijndael256_192BackEnc,
+    Rijndael256_192BackDec,
+    U24,
+    "Rijndael with 256-bit block and 192-bit key",
+);
+
+define_rijndael256_impl!(
+    Rijndael256_256,
+    Rijndael256_256BackEnc,
+    Rijndael256_256BackDec,
+    U32,
+    "Rijndael with 256-bit block and 256-bit key",
+);
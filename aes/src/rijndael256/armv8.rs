@@ -0,0 +1,71 @@
+//! Rijndael-256 using ARMv8 Cryptography Extensions and `TBL` byte shuffles.
+#![allow(unsafe_op_in_unsafe_fn)]
+
+use super::{Block, DEC_SHUFFLE, ENC_SHUFFLE, ROUNDS, RoundKeys};
+use core::arch::aarch64::*;
+
+#[target_feature(enable = "neon")]
+unsafe fn load(block: &Block) -> uint8x16x2_t {
+    vld1q_u8_x2(block.as_ptr())
+}
+
+#[target_feature(enable = "neon")]
+unsafe fn shuffle(state: uint8x16x2_t, idx: uint8x16x2_t) -> uint8x16x2_t {
+    uint8x16x2_t(vqtbl2q_u8(state, idx.0), vqtbl2q_u8(state, idx.1))
+}
+
+#[target_feature(enable = "aes")]
+pub(super) unsafe fn encrypt(keys: &RoundKeys, block: &mut Block) {
+    let idx = vld1q_u8_x2(ENC_SHUFFLE.as_ptr());
+    let zero = vdupq_n_u8(0);
+
+    let k = load(&keys[0]);
+    let b = load(block);
+    let mut state = uint8x16x2_t(veorq_u8(b.0, k.0), veorq_u8(b.1, k.1));
+
+    for key in &keys[1..ROUNDS] {
+        let k = load(key);
+        let b = shuffle(state, idx);
+        state = uint8x16x2_t(
+            veorq_u8(vaesmcq_u8(vaeseq_u8(b.0, zero)), k.0),
+            veorq_u8(vaesmcq_u8(vaeseq_u8(b.1, zero)), k.1),
+        );
+    }
+
+    let k = load(&keys[ROUNDS]);
+    let b = shuffle(state, idx);
+    state = uint8x16x2_t(
+        veorq_u8(vaeseq_u8(b.0, zero), k.0),
+        veorq_u8(vaeseq_u8(b.1, zero), k.1),
+    );
+
+    vst1q_u8_x2(block.as_mut_ptr(), state);
+}
+
+#[target_feature(enable = "aes")]
+pub(super) unsafe fn decrypt(inv_keys: &RoundKeys, block: &mut Block) {
+    let idx = vld1q_u8_x2(DEC_SHUFFLE.as_ptr());
+    let zero = vdupq_n_u8(0);
+
+    let k = load(&inv_keys[0]);
+    let b = load(block);
+    let mut state = uint8x16x2_t(veorq_u8(b.0, k.0), veorq_u8(b.1, k.1));
+
+    for key in &inv_keys[1..ROUNDS] {
+        let k = load(key);
+        let b = shuffle(state, idx);
+        state = uint8x16x2_t(
+            veorq_u8(vaesimcq_u8(vaesdq_u8(b.0, zero)), k.0),
+            veorq_u8(vaesimcq_u8(vaesdq_u8(b.1, zero)), k.1),
+        );
+    }
+
+    let k = load(&inv_keys[ROUNDS]);
+    let b = shuffle(state, idx);
+    state = uint8x16x2_t(
+        veorq_u8(vaesdq_u8(b.0, zero), k.0),
+        veorq_u8(vaesdq_u8(b.1, zero), k.1),
+    );
+
+    vst1q_u8_x2(block.as_mut_ptr(), state);
+}
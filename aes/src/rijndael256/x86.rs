@@ -0,0 +1,98 @@
+//! Rijndael-256 using AES-NI and SSSE3 byte shuffles.
+#![allow(unsafe_op_in_unsafe_fn)]
+
+use super::{Block, DEC_SHUFFLE, ENC_SHUFFLE, ROUNDS, RoundKeys};
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::*;
+
+/// `PSHUFB` masks selecting the bytes of each half of the permuted state
+/// from the low and high halves of the input state, with `0x80` marking
+/// bytes which are taken from the other half.
+type ShuffleMasks = [[[u8; 16]; 2]; 2];
+
+const ENC_MASKS: ShuffleMasks = shuffle_masks(&ENC_SHUFFLE);
+const DEC_MASKS: ShuffleMasks = shuffle_masks(&DEC_SHUFFLE);
+
+const fn shuffle_masks(shuffle: &[u8; 32]) -> ShuffleMasks {
+    let mut masks = [[[0x80; 16]; 2]; 2];
+    let mut i = 0;
+    while i < 32 {
+        let src = shuffle[i] as usize;
+        masks[i / 16][src / 16][i % 16] = (src % 16) as u8;
+        i += 1;
+    }
+    masks
+}
+
+#[target_feature(enable = "sse2")]
+unsafe fn load_masks(masks: &ShuffleMasks) -> [[__m128i; 2]; 2] {
+    masks.map(|half| half.map(|mask| _mm_loadu_si128(mask.as_ptr().cast())))
+}
+
+#[target_feature(enable = "sse2")]
+unsafe fn load(block: &Block) -> [__m128i; 2] {
+    let p = block.as_ptr().cast::<__m128i>();
+    [_mm_loadu_si128(p), _mm_loadu_si128(p.add(1))]
+}
+
+#[target_feature(enable = "sse2")]
+unsafe fn store(block: &mut Block, state: [__m128i; 2]) {
+    let p = block.as_mut_ptr().cast::<__m128i>();
+    _mm_storeu_si128(p, state[0]);
+    _mm_storeu_si128(p.add(1), state[1]);
+}
+
+#[target_feature(enable = "ssse3")]
+unsafe fn shuffle(state: [__m128i; 2], masks: &[[__m128i; 2]; 2]) -> [__m128i; 2] {
+    masks.map(|[lo, hi]| {
+        _mm_or_si128(
+            _mm_shuffle_epi8(state[0], lo),
+            _mm_shuffle_epi8(state[1], hi),
+        )
+    })
+}
+
+#[target_feature(enable = "aes,ssse3")]
+pub(super) unsafe fn encrypt(keys: &RoundKeys, block: &mut Block) {
+    let masks = load_masks(&ENC_MASKS);
+
+    let [k0, k1] = load(&keys[0]);
+    let [b0, b1] = load(block);
+    let mut state = [_mm_xor_si128(b0, k0), _mm_xor_si128(b1, k1)];
+
+    for key in &keys[1..ROUNDS] {
+        let [k0, k1] = load(key);
+        let [b0, b1] = shuffle(state, &masks);
+        state = [_mm_aesenc_si128(b0, k0), _mm_aesenc_si128(b1, k1)];
+    }
+
+    let [k0, k1] = load(&keys[ROUNDS]);
+    let [b0, b1] = shuffle(state, &masks);
+    state = [_mm_aesenclast_si128(b0, k0), _mm_aesenclast_si128(b1, k1)];
+
+    store(block, state);
+}
+
+#[target_feature(enable = "aes,ssse3")]
+pub(super) unsafe fn decrypt(inv_keys: &RoundKeys, block: &mut Block) {
+    let masks = load_masks(&DEC_MASKS);
+
+    let [k0, k1] = load(&inv_keys[0]);
+    let [b0, b1] = load(block);
+    let mut state = [_mm_xor_si128(b0, k0), _mm_xor_si128(b1, k1)];
+
+    for key in &inv_keys[1..ROUNDS] {
+        let [k0, k1] = load(key);
+        let [b0, b1] = shuffle(state, &masks);
+        state = [_mm_aesdec_si128(b0, k0), _mm_aesdec_si128(b1, k1)];
+    }
+
+    let [k0, k1] = load(&inv_keys[ROUNDS]);
+    let [b0, b1] = shuffle(state, &masks);
+    state = [_mm_aesdeclast_si128(b0, k0), _mm_aesdeclast_si128(b1, k1)];
+
+    store(block, state);
+}
@@ -19,7 +19,10 @@ cpubits::cpubits! {
     }
 }
 
-use crate::Block;
+#[cfg(test)]
+mod test_const;
+
+use crate::{Backend, BackendUnavailableError, Block};
 use cipher::{
     AlgorithmName, BlockCipherDecBackend, BlockCipherDecClosure, BlockCipherDecrypt,
     BlockCipherEncBackend, BlockCipherEncClosure, BlockCipherEncrypt, BlockSizeUser, Key, KeyInit,
@@ -38,7 +41,9 @@ macro_rules! define_aes_impl {
         $name_back_enc:ident,
         $name_back_dec:ident,
         $key_size:ty,
+        $key_len:literal,
         $fixslice_keys:ty,
+        $round_keys:ty,
         $fixslice_key_schedule:path,
         $fixslice_decrypt:path,
         $fixslice_encrypt:path,
@@ -64,6 +69,64 @@ macro_rules! define_aes_impl {
             }
         }
 
+        impl $name {
+            /// Initialize cipher in a `const` context.
+            ///
+            /// This allows to compute the fixsliced round keys at compile time,
+            /// e.g. for fixed keys embedded into firmware images.
+            // Unused when the soft backend is wrapped by `autodetect`
+            #[allow(dead_code)]
+            #[inline]
+            pub const fn new_const(key: &[u8; $key_len]) -> Self {
+                Self {
+                    keys: $fixslice_key_schedule(key),
+                }
+            }
+
+            /// Initialize cipher using the specified [`Backend`].
+            ///
+            /// Returns [`BackendUnavailableError`] for any backend other than
+            /// [`Backend::Soft`].
+            #[inline]
+            pub fn new_with_backend(
+                key: &Key<Self>,
+                backend: Backend,
+            ) -> Result<Self, BackendUnavailableError> {
+                match backend {
+                    Backend::Soft => Ok(Self::new(key)),
+                    _ => Err(BackendUnavailableError(backend)),
+                }
+            }
+
+            /// Get the backend used by this cipher instance.
+            #[inline]
+            pub fn backend(&self) -> Backend {
+                Backend::Soft
+            }
+
+            /// Re-initialize cipher with a new key, reusing the existing storage.
+            ///
+            /// When the `zeroize` feature is enabled, the previous key schedule
+            /// is zeroized before being overwritten.
+            #[inline]
+            pub fn rekey(&mut self, key: &Key<Self>) {
+                let keys = $fixslice_key_schedule(key.into());
+                #[cfg(feature = "zeroize")]
+                zeroize::Zeroize::zeroize(&mut self.keys);
+                self.keys = keys;
+            }
+
+            /// Initialize cipher from the expanded encryption round keys, e.g.
+            /// as returned by the `hazmat` key expansion functions.
+            #[cfg(feature = "hazmat")]
+            #[inline]
+            pub fn from_round_keys(round_keys: &$round_keys) -> Self {
+                Self {
+                    keys: fixslice::hazmat::fixslice_keys(round_keys),
+                }
+            }
+        }
+
         impl BlockSizeUser for $name {
             type BlockSize = U16;
         }
@@ -136,6 +199,59 @@ macro_rules! define_aes_impl {
             }
         }
 
+        impl $name_enc {
+            /// Initialize cipher in a `const` context.
+            ///
+            /// This allows to compute the fixsliced round keys at compile time,
+            /// e.g. for fixed keys embedded into firmware images.
+            // Unused when the soft backend is wrapped by `autodetect`
+            #[allow(dead_code)]
+            #[inline]
+            pub const fn new_const(key: &[u8; $key_len]) -> Self {
+                let inner = $name::new_const(key);
+                Self { inner }
+            }
+
+            /// Initialize cipher using the specified [`Backend`].
+            ///
+            /// Returns [`BackendUnavailableError`] for any backend other than
+            /// [`Backend::Soft`].
+            #[inline]
+            pub fn new_with_backend(
+                key: &Key<Self>,
+                backend: Backend,
+            ) -> Result<Self, BackendUnavailableError> {
+                match backend {
+                    Backend::Soft => Ok(Self::new(key)),
+                    _ => Err(BackendUnavailableError(backend)),
+                }
+            }
+
+            /// Get the backend used by this cipher instance.
+            #[inline]
+            pub fn backend(&self) -> Backend {
+                Backend::Soft
+            }
+
+            /// Re-initialize cipher with a new key, reusing the existing storage.
+            ///
+            /// When the `zeroize` feature is enabled, the previous key schedule
+            /// is zeroized before being overwritten.
+            #[inline]
+            pub fn rekey(&mut self, key: &Key<Self>) {
+                self.inner.rekey(key);
+            }
+
+            /// Initialize cipher from the expanded encryption round keys, e.g.
+            /// as returned by the `hazmat` key expansion functions.
+            #[cfg(feature = "hazmat")]
+            #[inline]
+            pub fn from_round_keys(round_keys: &$round_keys) -> Self {
+                let inner = $name::from_round_keys(round_keys);
+                Self { inner }
+            }
+        }
+
         impl BlockSizeUser for $name_enc {
             type BlockSize = U16;
         }
@@ -196,6 +312,59 @@ macro_rules! define_aes_impl {
             }
         }
 
+        impl $name_dec {
+            /// Initialize cipher in a `const` context.
+            ///
+            /// This allows to compute the fixsliced round keys at compile time,
+            /// e.g. for fixed keys embedded into firmware images.
+            // Unused when the soft backend is wrapped by `autodetect`
+            #[allow(dead_code)]
+            #[inline]
+            pub const fn new_const(key: &[u8; $key_len]) -> Self {
+                let inner = $name::new_const(key);
+                Self { inner }
+            }
+
+            /// Initialize cipher using the specified [`Backend`].
+            ///
+            /// Returns [`BackendUnavailableError`] for any backend other than
+            /// [`Backend::Soft`].
+            #[inline]
+            pub fn new_with_backend(
+                key: &Key<Self>,
+                backend: Backend,
+            ) -> Result<Self, BackendUnavailableError> {
+                match backend {
+                    Backend::Soft => Ok(Self::new(key)),
+                    _ => Err(BackendUnavailableError(backend)),
+                }
+            }
+
+            /// Get the backend used by this cipher instance.
+            #[inline]
+            pub fn backend(&self) -> Backend {
+                Backend::Soft
+            }
+
+            /// Re-initialize cipher with a new key, reusing the existing storage.
+            ///
+            /// When the `zeroize` feature is enabled, the previous key schedule
+            /// is zeroized before being overwritten.
+            #[inline]
+            pub fn rekey(&mut self, key: &Key<Self>) {
+                self.inner.rekey(key);
+            }
+
+            /// Initialize cipher from the expanded encryption round keys, e.g.
+            /// as returned by the `hazmat` key expansion functions.
+            #[cfg(feature = "hazmat")]
+            #[inline]
+            pub fn from_round_keys(round_keys: &$round_keys) -> Self {
+                let inner = $name::from_round_keys(round_keys);
+                Self { inner }
+            }
+        }
+
         impl BlockSizeUser for $name_dec {
             type BlockSize = U16;
         }
@@ -282,7 +451,9 @@ define_aes_impl!(
     Aes128BackEnc,
     Aes128BackDec,
     U16,
+    16,
     FixsliceKeys128,
+    crate::hazmat::Aes128RoundKeys,
     fixslice::aes128_key_schedule,
     fixslice::aes128_decrypt,
     fixslice::aes128_encrypt,
@@ -296,7 +467,9 @@ define_aes_impl!(
     Aes192BackEnc,
     Aes192BackDec,
     U24,
+    24,
     FixsliceKeys192,
+    crate::hazmat::Aes192RoundKeys,
     fixslice::aes192_key_schedule,
     fixslice::aes192_decrypt,
     fixslice::aes192_encrypt,
@@ -310,7 +483,9 @@ define_aes_impl!(
     Aes256BackEnc,
     Aes256BackDec,
     U32,
+    32,
     FixsliceKeys256,
+    crate::hazmat::Aes256RoundKeys,
     fixslice::aes256_key_schedule,
     fixslice::aes256_decrypt,
     fixslice::aes256_encrypt,
@@ -36,77 +36,83 @@ pub(crate) type FixsliceKeys256 = [u64; 120];
 pub(crate) type State = [u64; 8];
 
 /// Fully bitsliced AES-128 key schedule to match the fully-fixsliced representation.
-pub(crate) fn aes128_key_schedule(key: &[u8; 16]) -> FixsliceKeys128 {
+pub(crate) const fn aes128_key_schedule(key: &[u8; 16]) -> FixsliceKeys128 {
     let mut rkeys = [0u64; 88];
 
-    bitslice(&mut rkeys[..8], key, key, key, key);
+    bitslice(rkey_mut(&mut rkeys, 0), key, key, key, key);
 
     let mut rk_off = 0;
-    for rcon in 0..10 {
+    let mut rcon = 0;
+    while rcon < 10 {
         memshift32(&mut rkeys, rk_off);
         rk_off += 8;
 
-        sub_bytes(&mut rkeys[rk_off..(rk_off + 8)]);
-        sub_bytes_nots(&mut rkeys[rk_off..(rk_off + 8)]);
+        sub_bytes(rkey_mut(&mut rkeys, rk_off));
+        sub_bytes_nots(rkey_mut(&mut rkeys, rk_off));
 
         if rcon < 8 {
-            add_round_constant_bit(&mut rkeys[rk_off..(rk_off + 8)], rcon);
+            add_round_constant_bit(rkey_mut(&mut rkeys, rk_off), rcon);
         } else {
-            add_round_constant_bit(&mut rkeys[rk_off..(rk_off + 8)], rcon - 8);
-            add_round_constant_bit(&mut rkeys[rk_off..(rk_off + 8)], rcon - 7);
-            add_round_constant_bit(&mut rkeys[rk_off..(rk_off + 8)], rcon - 5);
-            add_round_constant_bit(&mut rkeys[rk_off..(rk_off + 8)], rcon - 4);
+            add_round_constant_bit(rkey_mut(&mut rkeys, rk_off), rcon - 8);
+            add_round_constant_bit(rkey_mut(&mut rkeys, rk_off), rcon - 7);
+            add_round_constant_bit(rkey_mut(&mut rkeys, rk_off), rcon - 5);
+            add_round_constant_bit(rkey_mut(&mut rkeys, rk_off), rcon - 4);
         }
 
         xor_columns(&mut rkeys, rk_off, 8, ror_distance(1, 3));
+        rcon += 1;
     }
 
     // Adjust to match fixslicing format
     #[cfg(aes_backend_soft = "compact")]
     {
-        for i in (8..88).step_by(16) {
-            inv_shift_rows_1(&mut rkeys[i..(i + 8)]);
+        let mut i = 8;
+        while i < 88 {
+            inv_shift_rows_1(rkey_mut(&mut rkeys, i));
+            i += 16;
         }
     }
     #[cfg(not(aes_backend_soft = "compact"))]
     {
-        for i in (8..72).step_by(32) {
-            inv_shift_rows_1(&mut rkeys[i..(i + 8)]);
-            inv_shift_rows_2(&mut rkeys[(i + 8)..(i + 16)]);
-            inv_shift_rows_3(&mut rkeys[(i + 16)..(i + 24)]);
+        let mut i = 8;
+        while i < 72 {
+            inv_shift_rows_1(rkey_mut(&mut rkeys, i));
+            inv_shift_rows_2(rkey_mut(&mut rkeys, i + 8));
+            inv_shift_rows_3(rkey_mut(&mut rkeys, i + 16));
+            i += 32;
         }
-        inv_shift_rows_1(&mut rkeys[72..80]);
+        inv_shift_rows_1(rkey_mut(&mut rkeys, 72));
     }
 
     // Account for NOTs removed from sub_bytes
-    for i in 1..11 {
-        sub_bytes_nots(&mut rkeys[(i * 8)..(i * 8 + 8)]);
+    let mut i = 1;
+    while i < 11 {
+        sub_bytes_nots(rkey_mut(&mut rkeys, i * 8));
+        i += 1;
     }
 
     rkeys
 }
 
 /// Fully bitsliced AES-192 key schedule to match the fully-fixsliced representation.
-pub(crate) fn aes192_key_schedule(key: &[u8; 24]) -> FixsliceKeys192 {
+pub(crate) const fn aes192_key_schedule(key: &[u8; 24]) -> FixsliceKeys192 {
     let mut rkeys = [0u64; 104];
     let mut tmp = [0u64; 8];
 
-    bitslice(
-        &mut rkeys[..8],
-        &key[..16],
-        &key[..16],
-        &key[..16],
-        &key[..16],
-    );
-    bitslice(&mut tmp, &key[8..], &key[8..], &key[8..], &key[8..]);
+    let (key_lo, _) = key.split_at(16);
+    let (_, key_hi) = key.split_at(8);
+    bitslice(rkey_mut(&mut rkeys, 0), key_lo, key_lo, key_lo, key_lo);
+    bitslice(&mut tmp, key_hi, key_hi, key_hi, key_hi);
 
     let mut rcon = 0;
     let mut rk_off = 8;
 
     loop {
-        for i in 0..8 {
+        let mut i = 0;
+        while i < 8 {
             rkeys[rk_off + i] = (0x00ff00ff00ff00ff & (tmp[i] >> 8))
                 | (0xff00ff00ff00ff00 & (rkeys[(rk_off - 8) + i] << 8));
+            i += 1;
         }
 
         sub_bytes(&mut tmp);
@@ -115,16 +121,19 @@ pub(crate) fn aes192_key_schedule(key: &[u8; 24]) -> FixsliceKeys192 {
         add_round_constant_bit(&mut tmp, rcon);
         rcon += 1;
 
-        for i in 0..8 {
+        let mut i = 0;
+        while i < 8 {
             let mut ti = rkeys[rk_off + i];
             ti ^= 0x0f000f000f000f00 & ror(tmp[i], ror_distance(1, 1));
             ti ^= 0xf000f000f000f000 & (ti << 4);
             tmp[i] = ti;
+            i += 1;
         }
-        rkeys[rk_off..(rk_off + 8)].copy_from_slice(&tmp);
+        rkey_mut(&mut rkeys, rk_off).copy_from_slice(&tmp);
         rk_off += 8;
 
-        for i in 0..8 {
+        let mut i = 0;
+        while i < 8 {
             let ui = tmp[i];
             let mut ti = (0x00ff00ff00ff00ff & (rkeys[(rk_off - 16) + i] >> 8))
                 | (0xff00ff00ff00ff00 & (ui << 8));
@@ -133,8 +142,9 @@ pub(crate) fn aes192_key_schedule(key: &[u8; 24]) -> FixsliceKeys192 {
                 ^ (0xfff0fff0fff0fff0 & (ti << 4))
                 ^ (0xff00ff00ff00ff00 & (ti << 8))
                 ^ (0xf000f000f000f000 & (ti << 12));
+            i += 1;
         }
-        rkeys[rk_off..(rk_off + 8)].copy_from_slice(&tmp);
+        rkey_mut(&mut rkeys, rk_off).copy_from_slice(&tmp);
         rk_off += 8;
 
         sub_bytes(&mut tmp);
@@ -143,7 +153,8 @@ pub(crate) fn aes192_key_schedule(key: &[u8; 24]) -> FixsliceKeys192 {
         add_round_constant_bit(&mut tmp, rcon);
         rcon += 1;
 
-        for i in 0..8 {
+        let mut i = 0;
+        while i < 8 {
             let mut ti = (0x00ff00ff00ff00ff & (rkeys[(rk_off - 16) + i] >> 8))
                 | (0xff00ff00ff00ff00 & (rkeys[(rk_off - 8) + i] << 8));
             ti ^= 0x000f000f000f000f & ror(tmp[i], ror_distance(1, 3));
@@ -151,6 +162,7 @@ pub(crate) fn aes192_key_schedule(key: &[u8; 24]) -> FixsliceKeys192 {
                 ^ (0xfff0fff0fff0fff0 & (ti << 4))
                 ^ (0xff00ff00ff00ff00 & (ti << 8))
                 ^ (0xf000f000f000f000 & (ti << 12));
+            i += 1;
         }
         rk_off += 8;
 
@@ -158,57 +170,54 @@ pub(crate) fn aes192_key_schedule(key: &[u8; 24]) -> FixsliceKeys192 {
             break;
         }
 
-        for i in 0..8 {
+        let mut i = 0;
+        while i < 8 {
             let ui = rkeys[(rk_off - 8) + i];
             let mut ti = rkeys[(rk_off - 16) + i];
             ti ^= 0x0f000f000f000f00 & (ui >> 4);
             ti ^= 0xf000f000f000f000 & (ti << 4);
             tmp[i] = ti;
+            i += 1;
         }
     }
 
     // Adjust to match fixslicing format
     #[cfg(aes_backend_soft = "compact")]
     {
-        for i in (8..104).step_by(16) {
-            inv_shift_rows_1(&mut rkeys[i..(i + 8)]);
+        let mut i = 8;
+        while i < 104 {
+            inv_shift_rows_1(rkey_mut(&mut rkeys, i));
+            i += 16;
         }
     }
     #[cfg(not(aes_backend_soft = "compact"))]
     {
-        for i in (0..96).step_by(32) {
-            inv_shift_rows_1(&mut rkeys[(i + 8)..(i + 16)]);
-            inv_shift_rows_2(&mut rkeys[(i + 16)..(i + 24)]);
-            inv_shift_rows_3(&mut rkeys[(i + 24)..(i + 32)]);
+        let mut i = 0;
+        while i < 96 {
+            inv_shift_rows_1(rkey_mut(&mut rkeys, i + 8));
+            inv_shift_rows_2(rkey_mut(&mut rkeys, i + 16));
+            inv_shift_rows_3(rkey_mut(&mut rkeys, i + 24));
+            i += 32;
         }
     }
 
     // Account for NOTs removed from sub_bytes
-    for i in 1..13 {
-        sub_bytes_nots(&mut rkeys[(i * 8)..(i * 8 + 8)]);
+    let mut i = 1;
+    while i < 13 {
+        sub_bytes_nots(rkey_mut(&mut rkeys, i * 8));
+        i += 1;
     }
 
     rkeys
 }
 
 /// Fully bitsliced AES-256 key schedule to match the fully-fixsliced representation.
-pub(crate) fn aes256_key_schedule(key: &[u8; 32]) -> FixsliceKeys256 {
+pub(crate) const fn aes256_key_schedule(key: &[u8; 32]) -> FixsliceKeys256 {
     let mut rkeys = [0u64; 120];
 
-    bitslice(
-        &mut rkeys[..8],
-        &key[..16],
-        &key[..16],
-        &key[..16],
-        &key[..16],
-    );
-    bitslice(
-        &mut rkeys[8..16],
-        &key[16..],
-        &key[16..],
-        &key[16..],
-        &key[16..],
-    );
+    let (key_lo, key_hi) = key.split_at(16);
+    bitslice(rkey_mut(&mut rkeys, 0), key_lo, key_lo, key_lo, key_lo);
+    bitslice(rkey_mut(&mut rkeys, 8), key_hi, key_hi, key_hi, key_hi);
 
     let mut rk_off = 8;
 
@@ -217,10 +226,10 @@ pub(crate) fn aes256_key_schedule(key: &[u8; 32]) -> FixsliceKeys256 {
         memshift32(&mut rkeys, rk_off);
         rk_off += 8;
 
-        sub_bytes(&mut rkeys[rk_off..(rk_off + 8)]);
-        sub_bytes_nots(&mut rkeys[rk_off..(rk_off + 8)]);
+        sub_bytes(rkey_mut(&mut rkeys, rk_off));
+        sub_bytes_nots(rkey_mut(&mut rkeys, rk_off));
 
-        add_round_constant_bit(&mut rkeys[rk_off..(rk_off + 8)], rcon);
+        add_round_constant_bit(rkey_mut(&mut rkeys, rk_off), rcon);
         xor_columns(&mut rkeys, rk_off, 16, ror_distance(1, 3));
         rcon += 1;
 
@@ -231,8 +240,8 @@ pub(crate) fn aes256_key_schedule(key: &[u8; 32]) -> FixsliceKeys256 {
         memshift32(&mut rkeys, rk_off);
         rk_off += 8;
 
-        sub_bytes(&mut rkeys[rk_off..(rk_off + 8)]);
-        sub_bytes_nots(&mut rkeys[rk_off..(rk_off + 8)]);
+        sub_bytes(rkey_mut(&mut rkeys, rk_off));
+        sub_bytes_nots(rkey_mut(&mut rkeys, rk_off));
 
         xor_columns(&mut rkeys, rk_off, 16, ror_distance(0, 3));
     }
@@ -240,23 +249,29 @@ pub(crate) fn aes256_key_schedule(key: &[u8; 32]) -> FixsliceKeys256 {
     // Adjust to match fixslicing format
     #[cfg(aes_backend_soft = "compact")]
     {
-        for i in (8..120).step_by(16) {
-            inv_shift_rows_1(&mut rkeys[i..(i + 8)]);
+        let mut i = 8;
+        while i < 120 {
+            inv_shift_rows_1(rkey_mut(&mut rkeys, i));
+            i += 16;
         }
     }
     #[cfg(not(aes_backend_soft = "compact"))]
     {
-        for i in (8..104).step_by(32) {
-            inv_shift_rows_1(&mut rkeys[i..(i + 8)]);
-            inv_shift_rows_2(&mut rkeys[(i + 8)..(i + 16)]);
-            inv_shift_rows_3(&mut rkeys[(i + 16)..(i + 24)]);
+        let mut i = 8;
+        while i < 104 {
+            inv_shift_rows_1(rkey_mut(&mut rkeys, i));
+            inv_shift_rows_2(rkey_mut(&mut rkeys, i + 8));
+            inv_shift_rows_3(rkey_mut(&mut rkeys, i + 16));
+            i += 32;
         }
-        inv_shift_rows_1(&mut rkeys[104..112]);
+        inv_shift_rows_1(rkey_mut(&mut rkeys, 104));
     }
 
     // Account for NOTs removed from sub_bytes
-    for i in 1..15 {
-        sub_bytes_nots(&mut rkeys[(i * 8)..(i * 8 + 8)]);
+    let mut i = 1;
+    while i < 15 {
+        sub_bytes_nots(rkey_mut(&mut rkeys, i * 8));
+        i += 1;
     }
 
     rkeys
@@ -797,8 +812,8 @@ fn inv_sub_bytes(state: &mut [u64]) {
 /// See: <http://www.cs.yale.edu/homes/peralta/CircuitStuff/SLP_AES_113.txt>
 ///
 /// Note that the 4 bitwise NOT (^= 0xffffffffffffffff) are moved to the key schedule.
-fn sub_bytes(state: &mut [u64]) {
-    debug_assert_eq!(state.len(), 8);
+const fn sub_bytes(state: &mut [u64]) {
+    debug_assert!(state.len() == 8);
 
     // Scheduled using https://github.com/Ko-/aes-armcortexm/tree/public/scheduler
     // Inline "stack" comments reflect suggested stores and loads (ARM Cortex-M3 and M4)
@@ -970,8 +985,8 @@ fn sub_bytes(state: &mut [u64]) {
 
 /// NOT operations that are omitted in S-box
 #[inline]
-fn sub_bytes_nots(state: &mut [u64]) {
-    debug_assert_eq!(state.len(), 8);
+const fn sub_bytes_nots(state: &mut [u64]) {
+    debug_assert!(state.len() == 8);
     state[0] ^= 0xffffffffffffffff;
     state[1] ^= 0xffffffffffffffff;
     state[5] ^= 0xffffffffffffffff;
@@ -1112,61 +1127,66 @@ define_mix_columns!(
 );
 
 #[inline]
-fn delta_swap_1(a: &mut u64, shift: u32, mask: u64) {
+const fn delta_swap_1(a: &mut u64, shift: u32, mask: u64) {
     let t = (*a ^ ((*a) >> shift)) & mask;
     *a ^= t ^ (t << shift);
 }
 
 #[inline]
-fn delta_swap_2(a: &mut u64, b: &mut u64, shift: u32, mask: u64) {
+const fn delta_swap_2(a: &mut u64, b: &mut u64, shift: u32, mask: u64) {
     let t = (*a ^ ((*b) >> shift)) & mask;
     *a ^= t;
     *b ^= t << shift;
 }
 
 /// Applies ShiftRows once on an AES state (or key).
-#[cfg(any(not(aes_backend_soft = "compact"), feature = "hazmat"))]
 #[inline]
-fn shift_rows_1(state: &mut [u64]) {
-    debug_assert_eq!(state.len(), 8);
-    for x in state.iter_mut() {
-        delta_swap_1(x, 8, 0x00f000ff000f0000);
-        delta_swap_1(x, 4, 0x0f0f00000f0f0000);
+const fn shift_rows_1(state: &mut [u64]) {
+    debug_assert!(state.len() == 8);
+    let mut i = 0;
+    while i < state.len() {
+        delta_swap_1(&mut state[i], 8, 0x00f000ff000f0000);
+        delta_swap_1(&mut state[i], 4, 0x0f0f00000f0f0000);
+        i += 1;
     }
 }
 
 /// Applies ShiftRows twice on an AES state (or key).
 #[inline]
-fn shift_rows_2(state: &mut [u64]) {
-    debug_assert_eq!(state.len(), 8);
-    for x in state.iter_mut() {
-        delta_swap_1(x, 8, 0x00ff000000ff0000);
+const fn shift_rows_2(state: &mut [u64]) {
+    debug_assert!(state.len() == 8);
+    let mut i = 0;
+    while i < state.len() {
+        delta_swap_1(&mut state[i], 8, 0x00ff000000ff0000);
+        i += 1;
     }
 }
 
 /// Applies ShiftRows three times on an AES state (or key).
 #[inline]
-fn shift_rows_3(state: &mut [u64]) {
-    debug_assert_eq!(state.len(), 8);
-    for x in state.iter_mut() {
-        delta_swap_1(x, 8, 0x000f00ff00f00000);
-        delta_swap_1(x, 4, 0x0f0f00000f0f0000);
+const fn shift_rows_3(state: &mut [u64]) {
+    debug_assert!(state.len() == 8);
+    let mut i = 0;
+    while i < state.len() {
+        delta_swap_1(&mut state[i], 8, 0x000f00ff00f00000);
+        delta_swap_1(&mut state[i], 4, 0x0f0f00000f0f0000);
+        i += 1;
     }
 }
 
 #[inline(always)]
-fn inv_shift_rows_1(state: &mut [u64]) {
+const fn inv_shift_rows_1(state: &mut [u64]) {
     shift_rows_3(state);
 }
 
 #[inline(always)]
-fn inv_shift_rows_2(state: &mut [u64]) {
+const fn inv_shift_rows_2(state: &mut [u64]) {
     shift_rows_2(state);
 }
 
 #[cfg(not(aes_backend_soft = "compact"))]
 #[inline(always)]
-fn inv_shift_rows_3(state: &mut [u64]) {
+const fn inv_shift_rows_3(state: &mut [u64]) {
     shift_rows_1(state);
 }
 
@@ -1178,24 +1198,26 @@ fn inv_shift_rows_3(state: &mut [u64]) {
 ///
 /// The `idx_ror` parameter refers to the rotation value, which varies between the
 /// different key schedules.
-fn xor_columns(rkeys: &mut [u64], offset: usize, idx_xor: usize, idx_ror: u32) {
-    for i in 0..8 {
+const fn xor_columns(rkeys: &mut [u64], offset: usize, idx_xor: usize, idx_ror: u32) {
+    let mut i = 0;
+    while i < 8 {
         let off_i = offset + i;
         let rk = rkeys[off_i - idx_xor] ^ (0x000f000f000f000f & ror(rkeys[off_i], idx_ror));
         rkeys[off_i] = rk
             ^ (0xfff0fff0fff0fff0 & (rk << 4))
             ^ (0xff00ff00ff00ff00 & (rk << 8))
             ^ (0xf000f000f000f000 & (rk << 12));
+        i += 1;
     }
 }
 
 /// Bitslice four 128-bit input blocks input0, input1, input2, input3 into a 512-bit internal state.
-fn bitslice(output: &mut [u64], input0: &[u8], input1: &[u8], input2: &[u8], input3: &[u8]) {
-    debug_assert_eq!(output.len(), 8);
-    debug_assert_eq!(input0.len(), 16);
-    debug_assert_eq!(input1.len(), 16);
-    debug_assert_eq!(input2.len(), 16);
-    debug_assert_eq!(input3.len(), 16);
+const fn bitslice(output: &mut [u64], input0: &[u8], input1: &[u8], input2: &[u8], input3: &[u8]) {
+    debug_assert!(output.len() == 8);
+    debug_assert!(input0.len() == 16);
+    debug_assert!(input1.len() == 16);
+    debug_assert!(input2.len() == 16);
+    debug_assert!(input3.len() == 16);
 
     // Bitslicing is a bit index manipulation. 512 bits of data means each bit is positioned at a
     // 9-bit index. AES data is 4 blocks, each one a 4x4 column-major matrix of bytes, so the
@@ -1206,29 +1228,29 @@ fn bitslice(output: &mut [u64], input0: &[u8], input1: &[u8], input2: &[u8], inp
     //     p2 p1 p0 r1 r0 c1 c0 b1 b0
 
     #[rustfmt::skip]
-    fn read_reordered(input: &[u8]) -> u64 {
-        (u64::from(input[0x0])        ) |
-        (u64::from(input[0x1]) << 0x10) |
-        (u64::from(input[0x2]) << 0x20) |
-        (u64::from(input[0x3]) << 0x30) |
-        (u64::from(input[0x8]) << 0x08) |
-        (u64::from(input[0x9]) << 0x18) |
-        (u64::from(input[0xa]) << 0x28) |
-        (u64::from(input[0xb]) << 0x38)
+    const fn read_reordered(input: &[u8]) -> u64 {
+        (input[0x0] as u64)           |
+        ((input[0x1] as u64) << 0x10) |
+        ((input[0x2] as u64) << 0x20) |
+        ((input[0x3] as u64) << 0x30) |
+        ((input[0x8] as u64) << 0x08) |
+        ((input[0x9] as u64) << 0x18) |
+        ((input[0xa] as u64) << 0x28) |
+        ((input[0xb] as u64) << 0x38)
     }
 
     // Reorder each block's bytes on input
     //     __ __ c1 c0 r1 r0 __ __ __ => __ __ c0 r1 r0 c1 __ __ __
     // Reorder by relabeling (note the order of input)
     //     b1 b0 c0 __ __ __ __ __ __ => c0 b1 b0 __ __ __ __ __ __
-    let mut t0 = read_reordered(&input0[0x00..0x0c]);
-    let mut t4 = read_reordered(&input0[0x04..0x10]);
-    let mut t1 = read_reordered(&input1[0x00..0x0c]);
-    let mut t5 = read_reordered(&input1[0x04..0x10]);
-    let mut t2 = read_reordered(&input2[0x00..0x0c]);
-    let mut t6 = read_reordered(&input2[0x04..0x10]);
-    let mut t3 = read_reordered(&input3[0x00..0x0c]);
-    let mut t7 = read_reordered(&input3[0x04..0x10]);
+    let mut t0 = read_reordered(input0);
+    let mut t4 = read_reordered(input0.split_at(0x04).1);
+    let mut t1 = read_reordered(input1);
+    let mut t5 = read_reordered(input1.split_at(0x04).1);
+    let mut t2 = read_reordered(input2);
+    let mut t6 = read_reordered(input2.split_at(0x04).1);
+    let mut t3 = read_reordered(input3);
+    let mut t7 = read_reordered(input3.split_at(0x04).1);
 
     // Bit Index Swap 6 <-> 0:
     //     __ __ b0 __ __ __ __ __ p0 => __ __ p0 __ __ __ __ __ b0
@@ -1344,14 +1366,23 @@ fn inv_bitslice(input: &[u64]) -> BatchBlocks {
     output
 }
 
+/// Get the 8 words of the round key at `offset`, i.e. `&mut rkeys[offset..(offset + 8)]`
+/// in a way which can be used in const contexts.
+#[inline(always)]
+const fn rkey_mut(rkeys: &mut [u64], offset: usize) -> &mut [u64] {
+    rkeys.split_at_mut(offset).1.split_at_mut(8).0
+}
+
 /// Copy 32-bytes within the provided slice to an 8-byte offset
-fn memshift32(buffer: &mut [u64], src_offset: usize) {
-    debug_assert_eq!(src_offset % 8, 0);
+const fn memshift32(buffer: &mut [u64], src_offset: usize) {
+    debug_assert!(src_offset.is_multiple_of(8));
 
     let dst_offset = src_offset + 8;
     debug_assert!(dst_offset + 8 <= buffer.len());
 
-    for i in (0..8).rev() {
+    let mut i = 8;
+    while i > 0 {
+        i -= 1;
         buffer[dst_offset + i] = buffer[src_offset + i];
     }
 }
@@ -1367,17 +1398,17 @@ fn add_round_key(state: &mut State, rkey: &[u64]) {
 }
 
 #[inline(always)]
-fn add_round_constant_bit(state: &mut [u64], bit: usize) {
+const fn add_round_constant_bit(state: &mut [u64], bit: usize) {
     state[bit] ^= 0x00000000f0000000;
 }
 
 #[inline(always)]
-fn ror(x: u64, y: u32) -> u64 {
+const fn ror(x: u64, y: u32) -> u64 {
     x.rotate_right(y)
 }
 
 #[inline(always)]
-fn ror_distance(rows: u32, cols: u32) -> u32 {
+const fn ror_distance(rows: u32, cols: u32) -> u32 {
     (rows << 4) + (cols << 2)
 }
 
@@ -1421,6 +1452,59 @@ fn rotate_rows_and_columns_2_2(x: u64) -> u64 {
     (ror(x, ror_distance(1, 2)) & 0xff00ff00ff00ff00)
 }
 
+/// Apply the AES cipher round function, excluding `AddRoundKey`, to a batch
+/// of blocks. `MixColumns` is skipped for the final round.
+///
+/// Used by Rijndael-256, which permutes the bytes of its state before each
+/// round to emulate its wider `ShiftRows`.
+pub(crate) fn cipher_round_batch(blocks: &mut BatchBlocks, final_round: bool) {
+    let mut state = State::default();
+    bitslice(&mut state, &blocks[0], &blocks[1], &blocks[2], &blocks[3]);
+    sub_bytes(&mut state);
+    sub_bytes_nots(&mut state);
+    shift_rows_1(&mut state);
+    if !final_round {
+        mix_columns_0(&mut state);
+    }
+    *blocks = inv_bitslice(&state);
+}
+
+/// Apply the AES equivalent inverse cipher round function, excluding
+/// `AddRoundKey`, to a batch of blocks. `InvMixColumns` is skipped for the
+/// final round.
+pub(crate) fn equiv_inv_cipher_round_batch(blocks: &mut BatchBlocks, final_round: bool) {
+    let mut state = State::default();
+    bitslice(&mut state, &blocks[0], &blocks[1], &blocks[2], &blocks[3]);
+    sub_bytes_nots(&mut state);
+    inv_sub_bytes(&mut state);
+    inv_shift_rows_1(&mut state);
+    if !final_round {
+        inv_mix_columns_0(&mut state);
+    }
+    *blocks = inv_bitslice(&state);
+}
+
+/// Apply `InvMixColumns` to a batch of blocks: used to compute the
+/// Rijndael-256 decryption key schedule.
+pub(crate) fn inv_mix_columns_batch(blocks: &mut BatchBlocks) {
+    let mut state = State::default();
+    bitslice(&mut state, &blocks[0], &blocks[1], &blocks[2], &blocks[3]);
+    inv_mix_columns_0(&mut state);
+    *blocks = inv_bitslice(&state);
+}
+
+/// Sub bytes for a single AES word: used for key expansion.
+pub(crate) fn sub_word(word: u32) -> u32 {
+    let mut block = Block::default();
+    block[..4].copy_from_slice(&word.to_be_bytes());
+    let mut state = State::default();
+    bitslice(&mut state, &block, &block, &block, &block);
+    sub_bytes(&mut state);
+    sub_bytes_nots(&mut state);
+    let res = inv_bitslice(&state);
+    u32::from_be_bytes(res[0][..4].try_into().unwrap())
+}
+
 /// Low-level "hazmat" AES functions.
 ///
 /// Note: this isn't actually used in the `Aes128`/`Aes192`/`Aes256`
@@ -1428,11 +1512,17 @@ fn rotate_rows_and_columns_2_2(x: u64) -> u64 {
 /// the AES round function gated under the `hazmat` crate feature.
 #[cfg(feature = "hazmat")]
 pub(crate) mod hazmat {
+    #[cfg(not(aes_backend_soft = "compact"))]
+    use super::{inv_shift_rows_2, inv_shift_rows_3};
     use super::{
-        State, bitslice, inv_bitslice, inv_mix_columns_0, inv_shift_rows_1, inv_sub_bytes,
-        mix_columns_0, shift_rows_1, sub_bytes, sub_bytes_nots,
+        BatchBlocks, State, bitslice, inv_bitslice, inv_mix_columns_0, inv_shift_rows_1, inv_sub_bytes,
+        mix_columns_0, shift_rows_1, sub_bytes, sub_bytes_nots, sub_word,
     };
-    use crate::hazmat::{Block, Block8};
+    use crate::hazmat::Block;
+    use cipher::array::{Array, ArraySize};
+
+    /// AES round constants.
+    const ROUND_CONSTS: [u32; 10] = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1b, 0x36];
 
     /// XOR the `src` block into the `dst` block in-place.
     fn xor_in_place(dst: &mut Block, src: &Block) {
@@ -1453,6 +1543,30 @@ pub(crate) mod hazmat {
         block.copy_from_slice(&inv_bitslice(state)[0]);
     }
 
+    /// Apply a round function to `blocks` in batches of 4, followed by the
+    /// addition of the corresponding round keys.
+    #[inline(always)]
+    fn par_round<N: ArraySize>(
+        blocks: &mut Array<Block, N>,
+        round_keys: &Array<Block, N>,
+        round: impl Fn(&mut State),
+    ) {
+        for (chunk, keys) in blocks.chunks_mut(4).zip(round_keys.chunks(4)) {
+            let mut batch = BatchBlocks::default();
+            batch[..chunk.len()].copy_from_slice(chunk);
+
+            let mut state = State::default();
+            bitslice(&mut state, &batch[0], &batch[1], &batch[2], &batch[3]);
+            round(&mut state);
+            let res = inv_bitslice(&state);
+
+            for ((block, res), key) in chunk.iter_mut().zip(&res).zip(keys) {
+                *block = *res;
+                xor_in_place(block, key);
+            }
+        }
+    }
+
     /// AES cipher (encrypt) round function.
     #[inline]
     pub(crate) fn cipher_round(block: &mut Block, round_key: &Block) {
@@ -1467,21 +1581,40 @@ pub(crate) mod hazmat {
 
     /// AES cipher (encrypt) round function: parallel version.
     #[inline]
-    pub(crate) fn cipher_round_par(blocks: &mut Block8, round_keys: &Block8) {
-        for (chunk, keys) in blocks.chunks_exact_mut(4).zip(round_keys.chunks_exact(4)) {
-            let mut state = State::default();
-            bitslice(&mut state, &chunk[0], &chunk[1], &chunk[2], &chunk[3]);
-            sub_bytes(&mut state);
-            sub_bytes_nots(&mut state);
-            shift_rows_1(&mut state);
-            mix_columns_0(&mut state);
-            let res = inv_bitslice(&state);
+    pub(crate) fn cipher_round_par<N: ArraySize>(
+        blocks: &mut Array<Block, N>,
+        round_keys: &Array<Block, N>,
+    ) {
+        par_round(blocks, round_keys, |state| {
+            sub_bytes(state);
+            sub_bytes_nots(state);
+            shift_rows_1(state);
+            mix_columns_0(state);
+        });
+    }
 
-            for i in 0..4 {
-                chunk[i] = res[i];
-                xor_in_place(&mut chunk[i], &keys[i]);
-            }
-        }
+    /// AES cipher (encrypt) final round function.
+    #[inline]
+    pub(crate) fn cipher_final_round(block: &mut Block, round_key: &Block) {
+        let mut state = bitslice_block(block);
+        sub_bytes(&mut state);
+        sub_bytes_nots(&mut state);
+        shift_rows_1(&mut state);
+        inv_bitslice_block(block, &state);
+        xor_in_place(block, round_key);
+    }
+
+    /// AES cipher (encrypt) final round function: parallel version.
+    #[inline]
+    pub(crate) fn cipher_final_round_par<N: ArraySize>(
+        blocks: &mut Array<Block, N>,
+        round_keys: &Array<Block, N>,
+    ) {
+        par_round(blocks, round_keys, |state| {
+            sub_bytes(state);
+            sub_bytes_nots(state);
+            shift_rows_1(state);
+        });
     }
 
     /// AES cipher (encrypt) round function.
@@ -1497,23 +1630,64 @@ pub(crate) mod hazmat {
         xor_in_place(block, round_key);
     }
 
-    /// AES cipher (encrypt) round function: parallel version.
+    /// AES equivalent inverse cipher (decrypt) round function: parallel version.
     #[inline]
-    pub(crate) fn equiv_inv_cipher_round_par(blocks: &mut Block8, round_keys: &Block8) {
-        for (chunk, keys) in blocks.chunks_exact_mut(4).zip(round_keys.chunks_exact(4)) {
-            let mut state = State::default();
-            bitslice(&mut state, &chunk[0], &chunk[1], &chunk[2], &chunk[3]);
-            sub_bytes_nots(&mut state);
-            inv_sub_bytes(&mut state);
-            inv_shift_rows_1(&mut state);
-            inv_mix_columns_0(&mut state);
-            let res = inv_bitslice(&state);
+    pub(crate) fn equiv_inv_cipher_round_par<N: ArraySize>(
+        blocks: &mut Array<Block, N>,
+        round_keys: &Array<Block, N>,
+    ) {
+        par_round(blocks, round_keys, |state| {
+            sub_bytes_nots(state);
+            inv_sub_bytes(state);
+            inv_shift_rows_1(state);
+            inv_mix_columns_0(state);
+        });
+    }
 
-            for i in 0..4 {
-                chunk[i] = res[i];
-                xor_in_place(&mut chunk[i], &keys[i]);
-            }
-        }
+    /// AES inverse cipher (decrypt) round function.
+    ///
+    /// `InvMixColumns` is linear, so this is computed as the equivalent inverse
+    /// cipher round with `InvMixColumns` applied to the round key.
+    #[inline]
+    pub(crate) fn inv_cipher_round(block: &mut Block, round_key: &Block) {
+        let mut round_key = *round_key;
+        inv_mix_columns(&mut round_key);
+        equiv_inv_cipher_round(block, &round_key);
+    }
+
+    /// AES inverse cipher (decrypt) round function: parallel version.
+    #[inline]
+    pub(crate) fn inv_cipher_round_par<N: ArraySize>(
+        blocks: &mut Array<Block, N>,
+        round_keys: &Array<Block, N>,
+    ) {
+        let mut round_keys = round_keys.clone();
+        round_keys.iter_mut().for_each(inv_mix_columns);
+        equiv_inv_cipher_round_par(blocks, &round_keys);
+    }
+
+    /// AES inverse cipher (decrypt) final round function.
+    #[inline]
+    pub(crate) fn inv_cipher_final_round(block: &mut Block, round_key: &Block) {
+        let mut state = bitslice_block(block);
+        sub_bytes_nots(&mut state);
+        inv_sub_bytes(&mut state);
+        inv_shift_rows_1(&mut state);
+        inv_bitslice_block(block, &state);
+        xor_in_place(block, round_key);
+    }
+
+    /// AES inverse cipher (decrypt) final round function: parallel version.
+    #[inline]
+    pub(crate) fn inv_cipher_final_round_par<N: ArraySize>(
+        blocks: &mut Array<Block, N>,
+        round_keys: &Array<Block, N>,
+    ) {
+        par_round(blocks, round_keys, |state| {
+            sub_bytes_nots(state);
+            inv_sub_bytes(state);
+            inv_shift_rows_1(state);
+        });
     }
 
     /// AES mix columns function.
@@ -1531,4 +1705,82 @@ pub(crate) mod hazmat {
         inv_mix_columns_0(&mut state);
         inv_bitslice_block(block, &state);
     }
+
+    /// AES key expansion.
+    pub(crate) fn expand_key<const L: usize, const N: usize>(key: &[u8; L]) -> [Block; N] {
+        assert!((L == 16 && N == 11) || (L == 24 && N == 13) || (L == 32 && N == 15));
+
+        let mut columns = [0u32; 60];
+        for (column, chunk) in columns.iter_mut().zip(key.chunks_exact(4)) {
+            *column = u32::from_be_bytes(chunk.try_into().unwrap());
+        }
+
+        let nk = L / 4;
+        for i in nk..(N * 4) {
+            let mut word = columns[i - 1];
+
+            if i % nk == 0 {
+                word = sub_word(word.rotate_left(8)) ^ (ROUND_CONSTS[i / nk - 1] << 24);
+            } else if nk > 6 && i % nk == 4 {
+                word = sub_word(word);
+            }
+
+            columns[i] = columns[i - nk] ^ word;
+        }
+
+        let mut round_keys = [Block::default(); N];
+        for (round_key, words) in round_keys.iter_mut().zip(columns.chunks_exact(4)) {
+            for (dst, word) in round_key.chunks_exact_mut(4).zip(words) {
+                dst.copy_from_slice(&word.to_be_bytes());
+            }
+        }
+        round_keys
+    }
+
+    /// Compute inverse expanded keys (for decryption).
+    ///
+    /// This is the reverse of the encryption keys, with the Inverse Mix Columns
+    /// operation applied to all but the first and last expanded key.
+    pub(crate) fn inv_expanded_keys<const N: usize>(round_keys: &[Block; N]) -> [Block; N] {
+        let mut inv_keys = *round_keys;
+        inv_keys.reverse();
+        for key in &mut inv_keys[1..N - 1] {
+            inv_mix_columns(key);
+        }
+        inv_keys
+    }
+
+    /// Convert expanded round keys into the fixsliced representation.
+    pub(crate) fn fixslice_keys<const N: usize, const M: usize>(
+        round_keys: &[Block; N],
+    ) -> [u64; M] {
+        assert_eq!(N * 8, M);
+
+        let mut rkeys = [0; M];
+        for (rkey, round_key) in rkeys.chunks_exact_mut(8).zip(round_keys) {
+            bitslice(rkey, round_key, round_key, round_key, round_key);
+        }
+
+        // Adjust to match fixslicing format
+        for (round, rkey) in rkeys.chunks_exact_mut(8).enumerate().take(N - 1).skip(1) {
+            #[cfg(aes_backend_soft = "compact")]
+            if round % 2 == 1 {
+                inv_shift_rows_1(rkey);
+            }
+            #[cfg(not(aes_backend_soft = "compact"))]
+            match round % 4 {
+                1 => inv_shift_rows_1(rkey),
+                2 => inv_shift_rows_2(rkey),
+                3 => inv_shift_rows_3(rkey),
+                _ => {}
+            }
+        }
+
+        // Account for NOTs removed from sub_bytes
+        for rkey in rkeys.chunks_exact_mut(8).skip(1) {
+            sub_bytes_nots(rkey);
+        }
+
+        rkeys
+    }
 }
@@ -0,0 +1,61 @@
+use super::{Aes128, Aes128Dec, Aes128Enc, Aes192, Aes256};
+use cipher::{Array, BlockCipherDecrypt, BlockCipherEncrypt, KeyInit};
+use hex_literal::hex;
+
+const PLAINTEXT: [u8; 16] = hex!("00112233445566778899aabbccddeeff");
+
+const AES128_KEY: [u8; 16] = hex!("000102030405060708090a0b0c0d0e0f");
+const AES192_KEY: [u8; 24] = hex!("000102030405060708090a0b0c0d0e0f1011121314151617");
+const AES256_KEY: [u8; 32] =
+    hex!("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f");
+
+static AES128: Aes128 = Aes128::new_const(&AES128_KEY);
+static AES192: Aes192 = Aes192::new_const(&AES192_KEY);
+static AES256: Aes256 = Aes256::new_const(&AES256_KEY);
+static AES128_ENC: Aes128Enc = Aes128Enc::new_const(&AES128_KEY);
+static AES128_DEC: Aes128Dec = Aes128Dec::new_const(&AES128_KEY);
+
+/// FIPS 197 Appendix C.1
+#[test]
+fn aes128_new_const() {
+    assert_eq!(AES128.keys, Aes128::new(&AES128_KEY.into()).keys);
+
+    let mut block = Array::from(PLAINTEXT);
+    AES128.encrypt_block(&mut block);
+    assert_eq!(block, hex!("69c4e0d86a7b0430d8cdb78070b4c55a"));
+    AES128.decrypt_block(&mut block);
+    assert_eq!(block, PLAINTEXT);
+}
+
+/// FIPS 197 Appendix C.2
+#[test]
+fn aes192_new_const() {
+    assert_eq!(AES192.keys, Aes192::new(&AES192_KEY.into()).keys);
+
+    let mut block = Array::from(PLAINTEXT);
+    AES192.encrypt_block(&mut block);
+    assert_eq!(block, hex!("dda97ca4864cdfe06eaf70a0ec0d7191"));
+    AES192.decrypt_block(&mut block);
+    assert_eq!(block, PLAINTEXT);
+}
+
+/// FIPS 197 Appendix C.3
+#[test]
+fn aes256_new_const() {
+    assert_eq!(AES256.keys, Aes256::new(&AES256_KEY.into()).keys);
+
+    let mut block = Array::from(PLAINTEXT);
+    AES256.encrypt_block(&mut block);
+    assert_eq!(block, hex!("8ea2b7ca516745bfeafc49904b496089"));
+    AES256.decrypt_block(&mut block);
+    assert_eq!(block, PLAINTEXT);
+}
+
+#[test]
+fn aes128_enc_dec_new_const() {
+    let mut block = Array::from(PLAINTEXT);
+    AES128_ENC.encrypt_block(&mut block);
+    assert_eq!(block, hex!("69c4e0d86a7b0430d8cdb78070b4c55a"));
+    AES128_DEC.decrypt_block(&mut block);
+    assert_eq!(block, PLAINTEXT);
+}
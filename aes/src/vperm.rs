@@ -0,0 +1,505 @@
+//! AES block cipher constant-time implementation based on vector permute
+//! instructions, for CPUs which support SSSE3 or NEON but lack dedicated
+//! AES instructions.
+//!
+//! The implementation follows the technique described in [Accelerating AES
+//! with Vector Permute Instructions][1] by Mike Hamburg: bytes are mapped
+//! into a tower field representation GF((2^4)^2) in which inversion can be
+//! computed using a handful of 16-entry table lookups indexed by nibbles.
+//! These lookups are performed with `pshufb` (SSSE3) or `tbl` (NEON), which
+//! execute in constant time regardless of the index values.
+//!
+//! [1]: https://shiftleft.org/papers/vector_aes/vector_aes.pdf
+
+#[cfg(target_arch = "aarch64")]
+mod neon;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod ssse3;
+
+#[cfg(target_arch = "aarch64")]
+use neon as intrinsics;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+use ssse3 as intrinsics;
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+cpufeatures::new!(vperm_features, "ssse3");
+
+use crate::{Backend, BackendUnavailableError, Block};
+use cipher::{
+    AlgorithmName, BlockCipherDecBackend, BlockCipherDecClosure, BlockCipherDecrypt,
+    BlockCipherEncBackend, BlockCipherEncClosure, BlockCipherEncrypt, BlockSizeUser, Key, KeyInit,
+    KeySizeUser, ParBlocks, ParBlocksSizeUser,
+    consts::{U4, U16, U24, U32},
+    inout::InOut,
+};
+use core::fmt;
+
+/// Check whether the vector permute instructions are supported by the CPU.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[inline]
+pub(crate) fn available() -> bool {
+    vperm_features::get()
+}
+
+/// Check whether the vector permute instructions are supported by the CPU.
+///
+/// NEON is mandatory on `aarch64` targets with hardware floating point.
+#[cfg(target_arch = "aarch64")]
+#[inline]
+pub(crate) fn available() -> bool {
+    cfg!(target_feature = "neon")
+}
+
+/// Isomorphism from the AES field to the tower field (low nibble lookup).
+const IPT_LO: [u8; 16] = [
+    0x00, 0x01, 0x1c, 0x1d, 0x2d, 0x2c, 0x31, 0x30, 0x27, 0x26, 0x3b, 0x3a, 0x0a, 0x0b, 0x16, 0x17,
+];
+/// Isomorphism from the AES field to the tower field (high nibble lookup).
+const IPT_HI: [u8; 16] = [
+    0x00, 0x86, 0xfd, 0x7b, 0x8e, 0x08, 0x73, 0xf5, 0x77, 0xf1, 0x8a, 0x0c, 0xf9, 0x7f, 0x04, 0x82,
+];
+/// Inverse isomorphism composed with the inverse affine transformation
+/// (low nibble lookup, includes the `0x63` constant).
+const DIPT_LO: [u8; 16] = [
+    0x2c, 0x99, 0xf0, 0x45, 0xf7, 0x42, 0x2b, 0x9e, 0x38, 0x8d, 0xe4, 0x51, 0xe3, 0x56, 0x3f, 0x8a,
+];
+/// Inverse isomorphism composed with the inverse affine transformation
+/// (high nibble lookup).
+const DIPT_HI: [u8; 16] = [
+    0x00, 0xa7, 0xa8, 0x0f, 0xed, 0x4a, 0x45, 0xe2, 0xd1, 0x76, 0x79, 0xde, 0x3c, 0x9b, 0x94, 0x33,
+];
+/// Inversion in GF(2^4), mapping zero to "infinity" (`0x80`).
+const INV: [u8; 16] = [
+    0x80, 0x01, 0x09, 0x0e, 0x0d, 0x0b, 0x07, 0x06, 0x0f, 0x02, 0x0c, 0x05, 0x0a, 0x04, 0x03, 0x08,
+];
+/// Division of the tower field constant by an element of GF(2^4), mapping
+/// zero to "infinity" (`0x80`).
+const INV_A: [u8; 16] = [
+    0x80, 0x02, 0x01, 0x0f, 0x09, 0x05, 0x0e, 0x0c, 0x0d, 0x04, 0x0b, 0x0a, 0x07, 0x08, 0x06, 0x03,
+];
+/// S-box output, first half: inverse isomorphism and affine transformation.
+const SB_U: [u8; 16] = [
+    0x00, 0xcb, 0xd7, 0xb0, 0x21, 0x8d, 0x67, 0xac, 0x7b, 0x5a, 0xea, 0x3d, 0x46, 0xf6, 0x91, 0x1c,
+];
+/// S-box output, second half: inverse isomorphism and affine transformation.
+const SB_T: [u8; 16] = [
+    0x00, 0x9f, 0x61, 0x16, 0xc2, 0x2a, 0x77, 0xe8, 0x89, 0x4b, 0x5d, 0x3c, 0xb5, 0xa3, 0xd4, 0xfe,
+];
+/// Inverse S-box output, first half: inverse isomorphism.
+const DSB_U: [u8; 16] = [
+    0x00, 0x3b, 0xe4, 0xc8, 0x03, 0x14, 0x2c, 0x17, 0xf3, 0xf0, 0x38, 0xdc, 0x2f, 0xe7, 0xcb, 0xdf,
+];
+/// Inverse S-box output, second half: inverse isomorphism.
+const DSB_T: [u8; 16] = [
+    0x00, 0x24, 0x91, 0x19, 0x23, 0x8f, 0x88, 0xac, 0x3d, 0x1e, 0x07, 0x96, 0xab, 0xb2, 0x3a, 0xb5,
+];
+
+/// `ShiftRows` byte permutation.
+const SHIFT_ROWS: [u8; 16] = [0, 5, 10, 15, 4, 9, 14, 3, 8, 13, 2, 7, 12, 1, 6, 11];
+/// `InvShiftRows` byte permutation.
+const INV_SHIFT_ROWS: [u8; 16] = [0, 13, 10, 7, 4, 1, 14, 11, 8, 5, 2, 15, 12, 9, 6, 3];
+/// Rotation of every column of the state by one row.
+const ROT1: [u8; 16] = [1, 2, 3, 0, 5, 6, 7, 4, 9, 10, 11, 8, 13, 14, 15, 12];
+/// Rotation of every column of the state by two rows.
+const ROT2: [u8; 16] = [2, 3, 0, 1, 6, 7, 4, 5, 10, 11, 8, 9, 14, 15, 12, 13];
+/// Rotation of every column of the state by three rows.
+const ROT3: [u8; 16] = [3, 0, 1, 2, 7, 4, 5, 6, 11, 8, 9, 10, 15, 12, 13, 14];
+
+/// AES key expansion using the vector permute S-box.
+fn expand_key<const L: usize, const N: usize>(key: &[u8; L]) -> [[u8; 16]; N] {
+    let nk = L / 4;
+
+    let mut columns = [[0u8; 4]; 60];
+    for (column, chunk) in columns.iter_mut().zip(key.chunks_exact(4)) {
+        column.copy_from_slice(chunk);
+    }
+
+    let mut rcon = 1u8;
+    for i in nk..4 * N {
+        let mut word = columns[i - 1];
+
+        if i % nk == 0 {
+            word.rotate_left(1);
+            // SAFETY: vperm types are constructed only after the required
+            // target features were checked by `autodetect`.
+            word = unsafe { intrinsics::sub_word(word) };
+            word[0] ^= rcon;
+            rcon = (rcon << 1) ^ ((rcon >> 7) * 0x1b);
+        } else if nk > 6 && i % nk == 4 {
+            word = unsafe { intrinsics::sub_word(word) };
+        }
+
+        for (j, b) in word.iter().enumerate() {
+            columns[i][j] = columns[i - nk][j] ^ b;
+        }
+    }
+
+    let mut keys = [[0u8; 16]; N];
+    for (key, words) in keys.iter_mut().zip(columns.chunks_exact(4)) {
+        for (dst, word) in key.chunks_exact_mut(4).zip(words) {
+            dst.copy_from_slice(word);
+        }
+    }
+    keys
+}
+
+macro_rules! define_aes_impl {
+    (
+        $name:tt,
+        $name_enc:ident,
+        $name_dec:ident,
+        $name_back_enc:ident,
+        $name_back_dec:ident,
+        $key_size:ty,
+        $rounds:tt,
+        $doc:expr $(,)?
+    ) => {
+        #[doc=$doc]
+        #[doc = "block cipher"]
+        #[derive(Clone)]
+        pub struct $name {
+            keys: [[u8; 16]; $rounds],
+        }
+
+        impl KeySizeUser for $name {
+            type KeySize = $key_size;
+        }
+
+        impl KeyInit for $name {
+            #[inline]
+            fn new(key: &Key<Self>) -> Self {
+                Self {
+                    keys: expand_key(&key.0),
+                }
+            }
+        }
+
+        impl $name {
+            pub(crate) fn new_with_backend(
+                key: &Key<Self>,
+                backend: Backend,
+            ) -> Result<Self, BackendUnavailableError> {
+                match backend {
+                    Backend::Vperm => Ok(Self::new(key)),
+                    _ => Err(BackendUnavailableError(backend)),
+                }
+            }
+
+            pub(crate) fn backend(&self) -> Backend {
+                Backend::Vperm
+            }
+
+            pub(crate) fn rekey(&mut self, key: &Key<Self>) {
+                let keys = expand_key(&key.0);
+                #[cfg(feature = "zeroize")]
+                zeroize::Zeroize::zeroize(&mut self.keys);
+                self.keys = keys;
+            }
+
+            #[cfg(feature = "hazmat")]
+            pub(crate) fn from_round_keys(round_keys: &[Block; $rounds]) -> Self {
+                Self {
+                    keys: round_keys.map(|key| key.0),
+                }
+            }
+        }
+
+        impl BlockSizeUser for $name {
+            type BlockSize = U16;
+        }
+
+        impl BlockCipherEncrypt for $name {
+            fn encrypt_with_backend(&self, f: impl BlockCipherEncClosure<BlockSize = U16>) {
+                f.call(&$name_back_enc(self))
+            }
+        }
+
+        impl BlockCipherDecrypt for $name {
+            fn decrypt_with_backend(&self, f: impl BlockCipherDecClosure<BlockSize = U16>) {
+                f.call(&$name_back_dec(self))
+            }
+        }
+
+        impl From<$name_enc> for $name {
+            #[inline]
+            fn from(enc: $name_enc) -> $name {
+                enc.inner
+            }
+        }
+
+        impl From<&$name_enc> for $name {
+            #[inline]
+            fn from(enc: &$name_enc) -> $name {
+                enc.inner.clone()
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+                f.write_str(concat!(stringify!($name), " { .. }"))
+            }
+        }
+
+        impl AlgorithmName for $name {
+            fn write_alg_name(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(stringify!($name))
+            }
+        }
+
+        impl Drop for $name {
+            #[inline]
+            fn drop(&mut self) {
+                #[cfg(feature = "zeroize")]
+                zeroize::Zeroize::zeroize(&mut self.keys);
+            }
+        }
+
+        #[cfg(feature = "zeroize")]
+        impl zeroize::ZeroizeOnDrop for $name {}
+
+        #[doc=$doc]
+        #[doc = "block cipher (encrypt-only)"]
+        #[derive(Clone)]
+        pub struct $name_enc {
+            inner: $name,
+        }
+
+        impl KeySizeUser for $name_enc {
+            type KeySize = $key_size;
+        }
+
+        impl KeyInit for $name_enc {
+            #[inline(always)]
+            fn new(key: &Key<Self>) -> Self {
+                let inner = $name::new(key);
+                Self { inner }
+            }
+        }
+
+        impl $name_enc {
+            pub(crate) fn new_with_backend(
+                key: &Key<Self>,
+                backend: Backend,
+            ) -> Result<Self, BackendUnavailableError> {
+                let inner = $name::new_with_backend(key, backend)?;
+                Ok(Self { inner })
+            }
+
+            pub(crate) fn backend(&self) -> Backend {
+                Backend::Vperm
+            }
+
+            pub(crate) fn rekey(&mut self, key: &Key<Self>) {
+                self.inner.rekey(key);
+            }
+
+            #[cfg(feature = "hazmat")]
+            pub(crate) fn from_round_keys(round_keys: &[Block; $rounds]) -> Self {
+                let inner = $name::from_round_keys(round_keys);
+                Self { inner }
+            }
+        }
+
+        impl BlockSizeUser for $name_enc {
+            type BlockSize = U16;
+        }
+
+        impl BlockCipherEncrypt for $name_enc {
+            fn encrypt_with_backend(&self, f: impl BlockCipherEncClosure<BlockSize = U16>) {
+                f.call(&$name_back_enc(&self.inner))
+            }
+        }
+
+        impl fmt::Debug for $name_enc {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+                f.write_str(concat!(stringify!($name_enc), " { .. }"))
+            }
+        }
+
+        impl AlgorithmName for $name_enc {
+            fn write_alg_name(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(stringify!($name_enc))
+            }
+        }
+
+        #[cfg(feature = "zeroize")]
+        impl zeroize::ZeroizeOnDrop for $name_enc {}
+
+        #[doc=$doc]
+        #[doc = "block cipher (decrypt-only)"]
+        #[derive(Clone)]
+        pub struct $name_dec {
+            inner: $name,
+        }
+
+        impl KeySizeUser for $name_dec {
+            type KeySize = $key_size;
+        }
+
+        impl KeyInit for $name_dec {
+            #[inline(always)]
+            fn new(key: &Key<Self>) -> Self {
+                let inner = $name::new(key);
+                Self { inner }
+            }
+        }
+
+        impl From<$name_enc> for $name_dec {
+            #[inline]
+            fn from(enc: $name_enc) -> $name_dec {
+                Self { inner: enc.inner }
+            }
+        }
+
+        impl From<&$name_enc> for $name_dec {
+            #[inline]
+            fn from(enc: &$name_enc) -> $name_dec {
+                Self {
+                    inner: enc.inner.clone(),
+                }
+            }
+        }
+
+        impl $name_dec {
+            pub(crate) fn new_with_backend(
+                key: &Key<Self>,
+                backend: Backend,
+            ) -> Result<Self, BackendUnavailableError> {
+                let inner = $name::new_with_backend(key, backend)?;
+                Ok(Self { inner })
+            }
+
+            pub(crate) fn backend(&self) -> Backend {
+                Backend::Vperm
+            }
+
+            pub(crate) fn rekey(&mut self, key: &Key<Self>) {
+                self.inner.rekey(key);
+            }
+
+            #[cfg(feature = "hazmat")]
+            pub(crate) fn from_round_keys(round_keys: &[Block; $rounds]) -> Self {
+                let inner = $name::from_round_keys(round_keys);
+                Self { inner }
+            }
+        }
+
+        impl BlockSizeUser for $name_dec {
+            type BlockSize = U16;
+        }
+
+        impl BlockCipherDecrypt for $name_dec {
+            fn decrypt_with_backend(&self, f: impl BlockCipherDecClosure<BlockSize = U16>) {
+                f.call(&$name_back_dec(&self.inner));
+            }
+        }
+
+        impl fmt::Debug for $name_dec {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+                f.write_str(concat!(stringify!($name_dec), " { .. }"))
+            }
+        }
+
+        impl AlgorithmName for $name_dec {
+            fn write_alg_name(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(stringify!($name_dec))
+            }
+        }
+
+        #[cfg(feature = "zeroize")]
+        impl zeroize::ZeroizeOnDrop for $name_dec {}
+
+        pub(crate) struct $name_back_enc<'a>(&'a $name);
+
+        impl<'a> BlockSizeUser for $name_back_enc<'a> {
+            type BlockSize = U16;
+        }
+
+        impl<'a> ParBlocksSizeUser for $name_back_enc<'a> {
+            type ParBlocksSize = U4;
+        }
+
+        impl<'a> BlockCipherEncBackend for $name_back_enc<'a> {
+            #[inline(always)]
+            fn encrypt_block(&self, mut block: InOut<'_, '_, Block>) {
+                let mut b = [block.clone_in().0];
+                // SAFETY: vperm types are constructed only after the required
+                // target features were checked by `autodetect`.
+                unsafe { intrinsics::encrypt(&self.0.keys, &mut b) };
+                *block.get_out() = b[0].into();
+            }
+
+            #[inline(always)]
+            fn encrypt_par_blocks(&self, mut blocks: InOut<'_, '_, ParBlocks<Self>>) {
+                let mut b = blocks.clone_in().0.map(|block| block.0);
+                // SAFETY: vperm types are constructed only after the required
+                // target features were checked by `autodetect`.
+                unsafe { intrinsics::encrypt(&self.0.keys, &mut b) };
+                *blocks.get_out() = b.map(Block::from).into();
+            }
+        }
+
+        pub(crate) struct $name_back_dec<'a>(&'a $name);
+
+        impl<'a> BlockSizeUser for $name_back_dec<'a> {
+            type BlockSize = U16;
+        }
+
+        impl<'a> ParBlocksSizeUser for $name_back_dec<'a> {
+            type ParBlocksSize = U4;
+        }
+
+        impl<'a> BlockCipherDecBackend for $name_back_dec<'a> {
+            #[inline(always)]
+            fn decrypt_block(&self, mut block: InOut<'_, '_, Block>) {
+                let mut b = [block.clone_in().0];
+                // SAFETY: vperm types are constructed only after the required
+                // target features were checked by `autodetect`.
+                unsafe { intrinsics::decrypt(&self.0.keys, &mut b) };
+                *block.get_out() = b[0].into();
+            }
+
+            #[inline(always)]
+            fn decrypt_par_blocks(&self, mut blocks: InOut<'_, '_, ParBlocks<Self>>) {
+                let mut b = blocks.clone_in().0.map(|block| block.0);
+                // SAFETY: vperm types are constructed only after the required
+                // target features were checked by `autodetect`.
+                unsafe { intrinsics::decrypt(&self.0.keys, &mut b) };
+                *blocks.get_out() = b.map(Block::from).into();
+            }
+        }
+    };
+}
+
+define_aes_impl!(
+    Aes128,
+    Aes128Enc,
+    Aes128Dec,
+    Aes128BackEnc,
+    Aes128BackDec,
+    U16,
+    11,
+    "AES-128",
+);
+
+define_aes_impl!(
+    Aes192,
+    Aes192Enc,
+    Aes192Dec,
+    Aes192BackEnc,
+    Aes192BackDec,
+    U24,
+    13,
+    "AES-192",
+);
+
+define_aes_impl!(
+    Aes256,
+    Aes256Enc,
+    Aes256Dec,
+    Aes256BackEnc,
+    Aes256BackDec,
+    U32,
+    15,
+    "AES-256",
+);
@@ -0,0 +1,146 @@
+//! Vector permute AES using NEON `TBL` table lookups.
+#![allow(unsafe_op_in_unsafe_fn)]
+
+use super::{
+    DIPT_HI, DIPT_LO, DSB_T, DSB_U, INV, INV_A, INV_SHIFT_ROWS, IPT_HI, IPT_LO, ROT1, ROT2, ROT3,
+    SB_T, SB_U, SHIFT_ROWS,
+};
+use core::arch::aarch64::*;
+
+#[inline(always)]
+unsafe fn load(bytes: &[u8; 16]) -> uint8x16_t {
+    vld1q_u8(bytes.as_ptr())
+}
+
+#[inline(always)]
+unsafe fn lookup(table: &[u8; 16], idx: uint8x16_t) -> uint8x16_t {
+    vqtbl1q_u8(load(table), idx)
+}
+
+/// Permute the bytes of `x` according to `mask`.
+#[inline(always)]
+unsafe fn shuffle(mask: &[u8; 16], x: uint8x16_t) -> uint8x16_t {
+    vqtbl1q_u8(x, load(mask))
+}
+
+/// Apply the S-box to every byte of `x`, where `ipt` maps bytes into the
+/// tower field and `sb` maps the inverse back (see module docs).
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn sub_bytes(
+    x: uint8x16_t,
+    ipt: (&[u8; 16], &[u8; 16]),
+    sb: (&[u8; 16], &[u8; 16]),
+) -> uint8x16_t {
+    let mask = vdupq_n_u8(0x0f);
+
+    let lo = vandq_u8(x, mask);
+    let hi = vshrq_n_u8(x, 4);
+    let t = veorq_u8(lookup(ipt.0, lo), lookup(ipt.1, hi));
+
+    let k = vandq_u8(t, mask);
+    let i = vshrq_n_u8(t, 4);
+    let j = veorq_u8(i, k);
+
+    let ak = lookup(&INV_A, k);
+    let iak = veorq_u8(lookup(&INV, i), ak);
+    let jak = veorq_u8(lookup(&INV, j), ak);
+    let io = veorq_u8(lookup(&INV, iak), j);
+    let jo = veorq_u8(lookup(&INV, jak), i);
+
+    veorq_u8(lookup(sb.0, io), lookup(sb.1, jo))
+}
+
+/// Multiply every byte of `x` by 2 in GF(2^8).
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn xtime(x: uint8x16_t) -> uint8x16_t {
+    let carry = vreinterpretq_u8_s8(vshrq_n_s8(vreinterpretq_s8_u8(x), 7));
+    veorq_u8(vshlq_n_u8(x, 1), vandq_u8(carry, vdupq_n_u8(0x1b)))
+}
+
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn mix_columns(x: uint8x16_t) -> uint8x16_t {
+    let x2 = xtime(x);
+    let mut out = veorq_u8(x2, shuffle(&ROT1, veorq_u8(x, x2)));
+    out = veorq_u8(out, shuffle(&ROT2, x));
+    veorq_u8(out, shuffle(&ROT3, x))
+}
+
+/// `InvMixColumns` computed as `MixColumns` of `x` multiplied by the
+/// circulant `(05, 00, 04, 00)`.
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn inv_mix_columns(x: uint8x16_t) -> uint8x16_t {
+    let t = xtime(xtime(veorq_u8(x, shuffle(&ROT2, x))));
+    mix_columns(veorq_u8(x, t))
+}
+
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn enc_sub_bytes(x: uint8x16_t) -> uint8x16_t {
+    let x = sub_bytes(x, (&IPT_LO, &IPT_HI), (&SB_U, &SB_T));
+    veorq_u8(x, vdupq_n_u8(0x63))
+}
+
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn dec_sub_bytes(x: uint8x16_t) -> uint8x16_t {
+    sub_bytes(x, (&DIPT_LO, &DIPT_HI), (&DSB_U, &DSB_T))
+}
+
+/// Encrypt `P` blocks, interleaving their rounds to hide instruction latency.
+#[target_feature(enable = "neon")]
+pub(super) unsafe fn encrypt<const N: usize, const P: usize>(
+    keys: &[[u8; 16]; N],
+    blocks: &mut [[u8; 16]; P],
+) {
+    let mut state = [vdupq_n_u8(0); P];
+    for (s, block) in state.iter_mut().zip(blocks.iter()) {
+        *s = veorq_u8(load(block), load(&keys[0]));
+    }
+    for key in &keys[1..N - 1] {
+        let key = load(key);
+        for s in state.iter_mut() {
+            *s = shuffle(&SHIFT_ROWS, enc_sub_bytes(*s));
+            *s = veorq_u8(mix_columns(*s), key);
+        }
+    }
+    let key = load(&keys[N - 1]);
+    for (s, block) in state.iter_mut().zip(blocks.iter_mut()) {
+        *s = veorq_u8(shuffle(&SHIFT_ROWS, enc_sub_bytes(*s)), key);
+        vst1q_u8(block.as_mut_ptr(), *s);
+    }
+}
+
+/// Decrypt `P` blocks, interleaving their rounds to hide instruction latency.
+#[target_feature(enable = "neon")]
+pub(super) unsafe fn decrypt<const N: usize, const P: usize>(
+    keys: &[[u8; 16]; N],
+    blocks: &mut [[u8; 16]; P],
+) {
+    let mut state = [vdupq_n_u8(0); P];
+    for (s, block) in state.iter_mut().zip(blocks.iter()) {
+        *s = veorq_u8(load(block), load(&keys[N - 1]));
+    }
+    for key in keys[1..N - 1].iter().rev() {
+        let key = load(key);
+        for s in state.iter_mut() {
+            *s = dec_sub_bytes(shuffle(&INV_SHIFT_ROWS, *s));
+            *s = inv_mix_columns(veorq_u8(*s, key));
+        }
+    }
+    let key = load(&keys[0]);
+    for (s, block) in state.iter_mut().zip(blocks.iter_mut()) {
+        *s = veorq_u8(dec_sub_bytes(shuffle(&INV_SHIFT_ROWS, *s)), key);
+        vst1q_u8(block.as_mut_ptr(), *s);
+    }
+}
+
+#[target_feature(enable = "neon")]
+pub(super) unsafe fn sub_word(word: [u8; 4]) -> [u8; 4] {
+    let x = vreinterpretq_u8_u32(vdupq_n_u32(u32::from_ne_bytes(word)));
+    let x = vreinterpretq_u32_u8(enc_sub_bytes(x));
+    vgetq_lane_u32(x, 0).to_ne_bytes()
+}
@@ -0,0 +1,153 @@
+//! Vector permute AES using SSSE3 `PSHUFB` table lookups.
+#![allow(unsafe_op_in_unsafe_fn)]
+
+use super::{
+    DIPT_HI, DIPT_LO, DSB_T, DSB_U, INV, INV_A, INV_SHIFT_ROWS, IPT_HI, IPT_LO, ROT1, ROT2, ROT3,
+    SB_T, SB_U, SHIFT_ROWS,
+};
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::*;
+
+#[inline(always)]
+unsafe fn load(bytes: &[u8; 16]) -> __m128i {
+    _mm_loadu_si128(bytes.as_ptr().cast())
+}
+
+#[inline(always)]
+unsafe fn lookup(table: &[u8; 16], idx: __m128i) -> __m128i {
+    _mm_shuffle_epi8(load(table), idx)
+}
+
+/// Permute the bytes of `x` according to `mask`.
+#[inline(always)]
+unsafe fn shuffle(mask: &[u8; 16], x: __m128i) -> __m128i {
+    _mm_shuffle_epi8(x, load(mask))
+}
+
+/// Apply the S-box to every byte of `x`, where `ipt` maps bytes into the
+/// tower field and `sb` maps the inverse back (see module docs).
+#[inline]
+#[target_feature(enable = "ssse3")]
+unsafe fn sub_bytes(
+    x: __m128i,
+    ipt: (&[u8; 16], &[u8; 16]),
+    sb: (&[u8; 16], &[u8; 16]),
+) -> __m128i {
+    let mask = _mm_set1_epi8(0x0f);
+
+    let lo = _mm_and_si128(x, mask);
+    let hi = _mm_and_si128(_mm_srli_epi16(x, 4), mask);
+    let t = _mm_xor_si128(lookup(ipt.0, lo), lookup(ipt.1, hi));
+
+    let k = _mm_and_si128(t, mask);
+    let i = _mm_and_si128(_mm_srli_epi16(t, 4), mask);
+    let j = _mm_xor_si128(i, k);
+
+    let ak = lookup(&INV_A, k);
+    let iak = _mm_xor_si128(lookup(&INV, i), ak);
+    let jak = _mm_xor_si128(lookup(&INV, j), ak);
+    let io = _mm_xor_si128(lookup(&INV, iak), j);
+    let jo = _mm_xor_si128(lookup(&INV, jak), i);
+
+    _mm_xor_si128(lookup(sb.0, io), lookup(sb.1, jo))
+}
+
+/// Multiply every byte of `x` by 2 in GF(2^8).
+#[inline]
+#[target_feature(enable = "ssse3")]
+unsafe fn xtime(x: __m128i) -> __m128i {
+    let carry = _mm_cmplt_epi8(x, _mm_setzero_si128());
+    _mm_xor_si128(
+        _mm_add_epi8(x, x),
+        _mm_and_si128(carry, _mm_set1_epi8(0x1b)),
+    )
+}
+
+#[inline]
+#[target_feature(enable = "ssse3")]
+unsafe fn mix_columns(x: __m128i) -> __m128i {
+    let x2 = xtime(x);
+    let mut out = _mm_xor_si128(x2, shuffle(&ROT1, _mm_xor_si128(x, x2)));
+    out = _mm_xor_si128(out, shuffle(&ROT2, x));
+    _mm_xor_si128(out, shuffle(&ROT3, x))
+}
+
+/// `InvMixColumns` computed as `MixColumns` of `x` multiplied by the
+/// circulant `(05, 00, 04, 00)`.
+#[inline]
+#[target_feature(enable = "ssse3")]
+unsafe fn inv_mix_columns(x: __m128i) -> __m128i {
+    let t = xtime(xtime(_mm_xor_si128(x, shuffle(&ROT2, x))));
+    mix_columns(_mm_xor_si128(x, t))
+}
+
+#[inline]
+#[target_feature(enable = "ssse3")]
+unsafe fn enc_sub_bytes(x: __m128i) -> __m128i {
+    let x = sub_bytes(x, (&IPT_LO, &IPT_HI), (&SB_U, &SB_T));
+    _mm_xor_si128(x, _mm_set1_epi8(0x63))
+}
+
+#[inline]
+#[target_feature(enable = "ssse3")]
+unsafe fn dec_sub_bytes(x: __m128i) -> __m128i {
+    sub_bytes(x, (&DIPT_LO, &DIPT_HI), (&DSB_U, &DSB_T))
+}
+
+/// Encrypt `P` blocks, interleaving their rounds to hide instruction latency.
+#[target_feature(enable = "ssse3")]
+pub(super) unsafe fn encrypt<const N: usize, const P: usize>(
+    keys: &[[u8; 16]; N],
+    blocks: &mut [[u8; 16]; P],
+) {
+    let mut state = [_mm_setzero_si128(); P];
+    for (s, block) in state.iter_mut().zip(blocks.iter()) {
+        *s = _mm_xor_si128(load(block), load(&keys[0]));
+    }
+    for key in &keys[1..N - 1] {
+        let key = load(key);
+        for s in state.iter_mut() {
+            *s = shuffle(&SHIFT_ROWS, enc_sub_bytes(*s));
+            *s = _mm_xor_si128(mix_columns(*s), key);
+        }
+    }
+    let key = load(&keys[N - 1]);
+    for (s, block) in state.iter_mut().zip(blocks.iter_mut()) {
+        *s = _mm_xor_si128(shuffle(&SHIFT_ROWS, enc_sub_bytes(*s)), key);
+        _mm_storeu_si128(block.as_mut_ptr().cast(), *s);
+    }
+}
+
+/// Decrypt `P` blocks, interleaving their rounds to hide instruction latency.
+#[target_feature(enable = "ssse3")]
+pub(super) unsafe fn decrypt<const N: usize, const P: usize>(
+    keys: &[[u8; 16]; N],
+    blocks: &mut [[u8; 16]; P],
+) {
+    let mut state = [_mm_setzero_si128(); P];
+    for (s, block) in state.iter_mut().zip(blocks.iter()) {
+        *s = _mm_xor_si128(load(block), load(&keys[N - 1]));
+    }
+    for key in keys[1..N - 1].iter().rev() {
+        let key = load(key);
+        for s in state.iter_mut() {
+            *s = dec_sub_bytes(shuffle(&INV_SHIFT_ROWS, *s));
+            *s = inv_mix_columns(_mm_xor_si128(*s, key));
+        }
+    }
+    let key = load(&keys[0]);
+    for (s, block) in state.iter_mut().zip(blocks.iter_mut()) {
+        *s = _mm_xor_si128(dec_sub_bytes(shuffle(&INV_SHIFT_ROWS, *s)), key);
+        _mm_storeu_si128(block.as_mut_ptr().cast(), *s);
+    }
+}
+
+#[target_feature(enable = "ssse3")]
+pub(super) unsafe fn sub_word(word: [u8; 4]) -> [u8; 4] {
+    let x = _mm_cvtsi32_si128(i32::from_ne_bytes(word));
+    let x = enc_sub_bytes(x);
+    _mm_cvtsi128_si32(x).to_ne_bytes()
+}
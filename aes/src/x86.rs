@@ -1,17 +1,20 @@
 pub(crate) mod ni;
-#[cfg(all(target_arch = "x86_64", any(aes_backend = "avx256", aes_backend = "avx512")))]
+#[cfg(all(target_arch = "x86_64", not(aes_backend = "ni")))]
 pub(crate) mod vaes256;
-#[cfg(all(target_arch = "x86_64", aes_backend = "avx512"))]
+#[cfg(all(target_arch = "x86_64", not(any(aes_backend = "ni", aes_backend = "avx256"))))]
 pub(crate) mod vaes512;
 
+#[cfg(feature = "hazmat")]
+pub(crate) mod hazmat;
+
 #[cfg(target_arch = "x86")]
 use core::arch::x86 as arch;
 #[cfg(target_arch = "x86_64")]
 use core::arch::x86_64 as arch;
 
 use self::arch::*;
-use crate::Block;
-#[cfg(all(target_arch = "x86_64", aes_backend = "avx512"))]
+use crate::{BackendUnavailableError, Block};
+#[cfg(all(target_arch = "x86_64", not(any(aes_backend = "ni", aes_backend = "avx256"))))]
 use cipher::consts::U64;
 use cipher::{
     AlgorithmName, BlockCipherDecBackend, BlockCipherDecClosure, BlockCipherDecrypt,
@@ -19,105 +22,121 @@ use cipher::{
     KeyInit, KeySizeUser, ParBlocksSizeUser,
     consts::{U8, U16, U24, U32},
 };
-#[cfg(all(target_arch = "x86_64", any(aes_backend = "avx256", aes_backend = "avx512")))]
+#[cfg(all(target_arch = "x86_64", not(aes_backend = "ni")))]
 use cipher::{Array, InOutBuf, consts::U30, typenum::Unsigned};
-#[cfg(all(target_arch = "x86_64", any(aes_backend = "avx256", aes_backend = "avx512")))]
+#[cfg(all(target_arch = "x86_64", not(aes_backend = "ni")))]
 use core::cell::OnceCell;
 use core::fmt;
 
-#[cfg(all(target_arch = "x86_64", any(aes_backend = "avx256", aes_backend = "avx512")))]
+#[cfg(all(target_arch = "x86_64", not(aes_backend = "ni")))]
 pub(crate) type Block30 = Array<Block, U30>;
-#[cfg(all(target_arch = "x86_64", aes_backend = "avx512"))]
+#[cfg(all(target_arch = "x86_64", not(any(aes_backend = "ni", aes_backend = "avx256"))))]
 pub(crate) type Block64 = Array<Block, U64>;
 
 pub(crate) mod features {
     cpufeatures::new!(features_aes, "aes");
-    cpufeatures::new!(features_avx, "avx");
+    cpufeatures::new!(features_avx2, "avx2");
     cpufeatures::new!(features_avx512f, "avx512f");
     cpufeatures::new!(features_vaes, "vaes");
     pub(crate) mod aes {
         pub use super::features_aes::*;
     }
-    #[cfg(all(target_arch = "x86_64", any(aes_backend = "avx256", aes_backend = "avx512")))]
-    pub(crate) mod avx {
-        pub use super::features_avx::*;
+    #[cfg(all(target_arch = "x86_64", not(aes_backend = "ni")))]
+    pub(crate) mod avx2 {
+        pub use super::features_avx2::*;
     }
-    #[cfg(all(target_arch = "x86_64", aes_backend = "avx512"))]
+    #[cfg(all(target_arch = "x86_64", not(any(aes_backend = "ni", aes_backend = "avx256"))))]
     pub(crate) mod avx512f {
         pub use super::features_avx512f::*;
     }
-    #[cfg(all(target_arch = "x86_64", any(aes_backend = "avx256", aes_backend = "avx512")))]
+    #[cfg(all(target_arch = "x86_64", not(aes_backend = "ni")))]
     pub(crate) mod vaes {
         pub use super::features_vaes::*;
     }
 }
 
 type Simd128RoundKeys<const ROUNDS: usize> = [__m128i; ROUNDS];
-#[cfg(all(target_arch = "x86_64", any(aes_backend = "avx256", aes_backend = "avx512")))]
+#[cfg(all(target_arch = "x86_64", not(aes_backend = "ni")))]
 type Simd256RoundKeys<const ROUNDS: usize> = [__m256i; ROUNDS];
-#[cfg(all(target_arch = "x86_64", aes_backend = "avx512"))]
+#[cfg(all(target_arch = "x86_64", not(any(aes_backend = "ni", aes_backend = "avx256"))))]
 type Simd512RoundKeys<const ROUNDS: usize> = [__m512i; ROUNDS];
 
-#[derive(Clone)]
+#[derive(Clone, Copy)]
 enum Backend {
     Ni,
-    #[cfg(all(target_arch = "x86_64", any(aes_backend = "avx256", aes_backend = "avx512")))]
+    #[cfg(all(target_arch = "x86_64", not(aes_backend = "ni")))]
     Vaes256,
-    #[cfg(all(target_arch = "x86_64", aes_backend = "avx512"))]
+    #[cfg(all(target_arch = "x86_64", not(any(aes_backend = "ni", aes_backend = "avx256"))))]
     Vaes512,
 }
 
 #[derive(Clone, Copy)]
 struct Features {
-    #[cfg(all(target_arch = "x86_64", any(aes_backend = "avx256", aes_backend = "avx512")))]
-    avx: self::features::avx::InitToken,
-    #[cfg(all(target_arch = "x86_64", aes_backend = "avx512"))]
+    #[cfg(all(target_arch = "x86_64", not(aes_backend = "ni")))]
+    avx2: self::features::avx2::InitToken,
+    #[cfg(all(target_arch = "x86_64", not(any(aes_backend = "ni", aes_backend = "avx256"))))]
     avx512f: self::features::avx512f::InitToken,
-    #[cfg(all(target_arch = "x86_64", any(aes_backend = "avx256", aes_backend = "avx512")))]
+    #[cfg(all(target_arch = "x86_64", not(aes_backend = "ni")))]
     vaes: self::features::vaes::InitToken,
 }
 
 impl Features {
     fn new() -> Self {
         Self {
-            #[cfg(all(target_arch = "x86_64", any(aes_backend = "avx256", aes_backend = "avx512")))]
-            avx: self::features::avx::init(),
-            #[cfg(all(target_arch = "x86_64", aes_backend = "avx512"))]
+            #[cfg(all(target_arch = "x86_64", not(aes_backend = "ni")))]
+            avx2: self::features::avx2::init(),
+            #[cfg(all(target_arch = "x86_64", not(any(aes_backend = "ni", aes_backend = "avx256"))))]
             avx512f: self::features::avx512f::init(),
-            #[cfg(all(target_arch = "x86_64", any(aes_backend = "avx256", aes_backend = "avx512")))]
+            #[cfg(all(target_arch = "x86_64", not(aes_backend = "ni")))]
             vaes: self::features::vaes::init(),
         }
     }
 
-    #[cfg(all(target_arch = "x86_64", any(aes_backend = "avx256", aes_backend = "avx512")))]
+    #[cfg(all(target_arch = "x86_64", not(aes_backend = "ni")))]
     fn has_vaes256(&self) -> bool {
-        #[cfg(target_arch = "x86_64")]
-        if cfg!(aes_backend = "avx256") && self.vaes.get() && self.avx.get() {
-            return true;
-        }
-        false
+        self.vaes.get() && self.avx2.get()
     }
 
-    #[cfg(all(target_arch = "x86_64", aes_backend = "avx512"))]
+    #[cfg(all(target_arch = "x86_64", not(any(aes_backend = "ni", aes_backend = "avx256"))))]
     fn has_vaes512(&self) -> bool {
-        #[cfg(target_arch = "x86_64")]
-        if cfg!(aes_backend = "avx512") && self.vaes.get() && self.avx512f.get() {
-            return true;
-        }
-        false
+        self.vaes.get() && self.avx512f.get()
     }
 
+    /// Select the widest backend supported by the CPU.
     fn dispatch(&self) -> Backend {
-        #[cfg(all(target_arch = "x86_64", aes_backend = "avx512"))]
+        #[cfg(all(target_arch = "x86_64", not(any(aes_backend = "ni", aes_backend = "avx256"))))]
         if self.has_vaes512() {
             return self::Backend::Vaes512;
         }
-        #[cfg(all(target_arch = "x86_64", any(aes_backend = "avx256", aes_backend = "avx512")))]
+        #[cfg(all(target_arch = "x86_64", not(aes_backend = "ni")))]
         if self.has_vaes256() {
             return self::Backend::Vaes256;
         }
         Backend::Ni
     }
+
+    fn select(&self, backend: crate::Backend) -> Option<Backend> {
+        match backend {
+            crate::Backend::Ni => Some(Backend::Ni),
+            #[cfg(all(target_arch = "x86_64", not(aes_backend = "ni")))]
+            crate::Backend::Vaes256 if self.has_vaes256() => Some(Backend::Vaes256),
+            #[cfg(all(target_arch = "x86_64", not(any(aes_backend = "ni", aes_backend = "avx256"))))]
+            crate::Backend::Vaes512 if self.has_vaes512() => Some(Backend::Vaes512),
+            _ => None,
+        }
+    }
+}
+
+impl From<Backend> for crate::Backend {
+    fn from(backend: Backend) -> crate::Backend {
+        match backend {
+            Backend::Ni => crate::Backend::Ni,
+            #[cfg(all(target_arch = "x86_64", not(aes_backend = "ni")))]
+            Backend::Vaes256 => crate::Backend::Vaes256,
+            #[cfg(all(target_arch = "x86_64", not(any(aes_backend = "ni", aes_backend = "avx256"))))]
+            Backend::Vaes512 => crate::Backend::Vaes512,
+        }
+    }
 }
 
 macro_rules! define_aes_impl {
@@ -138,35 +157,35 @@ macro_rules! define_aes_impl {
             pub(crate) struct Ni<'a> {
                 pub(crate) keys: &'a Simd128RoundKeys<$rounds>,
             }
-            #[cfg(all(target_arch = "x86_64", any(aes_backend = "avx256", aes_backend = "avx512")))]
+            #[cfg(all(target_arch = "x86_64", not(aes_backend = "ni")))]
             impl<'a> Ni<'a> {
                 pub const fn par_blocks(&self) -> usize {
                     <Self as ParBlocksSizeUser>::ParBlocksSize::USIZE
                 }
             }
-            #[cfg(all(target_arch = "x86_64", any(aes_backend = "avx256", aes_backend = "avx512")))]
+            #[cfg(all(target_arch = "x86_64", not(aes_backend = "ni")))]
             impl<'a> From<&Vaes256<'a>> for Ni<'a> {
                 fn from(backend: &Vaes256<'a>) -> Self {
                     Self { keys: backend.keys }
                 }
             }
 
-            #[cfg(all(target_arch = "x86_64", any(aes_backend = "avx256", aes_backend = "avx512")))]
+            #[cfg(all(target_arch = "x86_64", not(aes_backend = "ni")))]
             #[derive(Clone)]
             pub(crate) struct Vaes256<'a> {
-                #[allow(unused)] // TODO: remove once cfg flags are removed
+                #[allow(unused)] // only used by the VAES512 backend
                 pub(crate) features: Features,
                 pub(crate) keys: &'a Simd128RoundKeys<$rounds>,
                 pub(crate) simd_256_keys: OnceCell<Simd256RoundKeys<$rounds>>,
             }
-            #[cfg(all(target_arch = "x86_64", any(aes_backend = "avx256", aes_backend = "avx512")))]
+            #[cfg(all(target_arch = "x86_64", not(aes_backend = "ni")))]
             impl<'a> Vaes256<'a> {
-                #[allow(unused)] // TODO: remove once cfg flags are removed
+                #[allow(unused)] // only used by the VAES512 backend
                 pub const fn par_blocks(&self) -> usize {
                     <Self as ParBlocksSizeUser>::ParBlocksSize::USIZE
                 }
             }
-            #[cfg(all(target_arch = "x86_64", aes_backend = "avx512"))]
+            #[cfg(all(target_arch = "x86_64", not(any(aes_backend = "ni", aes_backend = "avx256"))))]
             impl<'a> From<&Vaes512<'a>> for Vaes256<'a> {
                 fn from(backend: &Vaes512<'a>) -> Self {
                     Self {
@@ -177,7 +196,7 @@ macro_rules! define_aes_impl {
                 }
             }
 
-            #[cfg(all(target_arch = "x86_64", aes_backend = "avx512"))]
+            #[cfg(all(target_arch = "x86_64", not(any(aes_backend = "ni", aes_backend = "avx256"))))]
             pub(crate) struct Vaes512<'a> {
                 pub(crate) features: Features,
                 pub(crate) keys: &'a Simd128RoundKeys<$rounds>,
@@ -209,6 +228,29 @@ macro_rules! define_aes_impl {
             }
         }
 
+        impl $name {
+            pub(crate) fn new_with_backend(
+                key: &Key<Self>,
+                backend: crate::Backend,
+            ) -> Result<Self, BackendUnavailableError> {
+                $name_enc::new_with_backend(key, backend).map(Into::into)
+            }
+
+            pub(crate) fn backend(&self) -> crate::Backend {
+                self.encrypt.backend()
+            }
+
+            pub(crate) fn rekey(&mut self, key: &Key<Self>) {
+                self.encrypt.rekey(key);
+                self.decrypt.set_inv_keys(&self.encrypt);
+            }
+
+            #[cfg(feature = "hazmat")]
+            pub(crate) fn from_round_keys(round_keys: &[Block; $rounds]) -> Self {
+                $name_enc::from_round_keys(round_keys).into()
+            }
+        }
+
         impl From<$name_enc> for $name {
             #[inline]
             fn from(encrypt: $name_enc) -> $name {
@@ -261,7 +303,9 @@ macro_rules! define_aes_impl {
         #[derive(Clone)]
         pub struct $name_enc {
             keys: Simd128RoundKeys<$rounds>,
+            #[allow(unused)] // unused when VAES backends are not available
             features: Features,
+            backend: Backend,
         }
 
         impl Drop for $name_enc {
@@ -285,9 +329,56 @@ macro_rules! define_aes_impl {
             fn new(key: &Key<Self>) -> Self {
                 // SAFETY: we enforce that this code is called only when
                 // target features required by `expand` were properly checked.
+                let features = Features::new();
                 Self {
                     keys: unsafe { self::ni::expand::$module::expand_key(key.as_ref()) },
-                    features: Features::new(),
+                    features,
+                    backend: features.dispatch(),
+                }
+            }
+        }
+
+        impl $name_enc {
+            pub(crate) fn new_with_backend(
+                key: &Key<Self>,
+                backend: crate::Backend,
+            ) -> Result<Self, BackendUnavailableError> {
+                let features = Features::new();
+                let backend = features
+                    .select(backend)
+                    .ok_or(BackendUnavailableError(backend))?;
+                // SAFETY: we enforce that this code is called only when
+                // target features required by `expand` were properly checked.
+                Ok(Self {
+                    keys: unsafe { self::ni::expand::$module::expand_key(key.as_ref()) },
+                    features,
+                    backend,
+                })
+            }
+
+            pub(crate) fn backend(&self) -> crate::Backend {
+                self.backend.into()
+            }
+
+            pub(crate) fn rekey(&mut self, key: &Key<Self>) {
+                #[cfg(feature = "zeroize")]
+                unsafe {
+                    zeroize::zeroize_flat_type(&mut self.keys)
+                }
+                // SAFETY: we enforce that this code is called only when
+                // target features required by `expand` were properly checked.
+                self.keys = unsafe { self::ni::expand::$module::expand_key(key.as_ref()) };
+            }
+
+            #[cfg(feature = "hazmat")]
+            pub(crate) fn from_round_keys(round_keys: &[Block; $rounds]) -> Self {
+                let features = Features::new();
+                // SAFETY: we enforce that this code is called only when
+                // target features required by `load_keys` were properly checked.
+                Self {
+                    keys: unsafe { self::ni::hazmat::load_keys(round_keys) },
+                    features,
+                    backend: features.dispatch(),
                 }
             }
         }
@@ -299,19 +390,18 @@ macro_rules! define_aes_impl {
         impl BlockCipherEncrypt for $name_enc {
             #[inline]
             fn encrypt_with_backend(&self, f: impl BlockCipherEncClosure<BlockSize = U16>) {
-                let features = self.features;
                 let keys = &self.keys;
-                match features.dispatch() {
+                match self.backend {
                     self::Backend::Ni => f.call(&mut $name_backend::Ni { keys }),
-                    #[cfg(all(target_arch = "x86_64", any(aes_backend = "avx256", aes_backend = "avx512")))]
+                    #[cfg(all(target_arch = "x86_64", not(aes_backend = "ni")))]
                     self::Backend::Vaes256 => f.call(&mut $name_backend::Vaes256 {
-                        features,
+                        features: self.features,
                         keys,
                         simd_256_keys: OnceCell::new(),
                     }),
-                    #[cfg(all(target_arch = "x86_64", aes_backend = "avx512"))]
+                    #[cfg(all(target_arch = "x86_64", not(any(aes_backend = "ni", aes_backend = "avx256"))))]
                     self::Backend::Vaes512 => f.call(&mut $name_backend::Vaes512 {
-                        features,
+                        features: self.features,
                         keys,
                         simd_512_keys: OnceCell::new(),
                     }),
@@ -336,7 +426,9 @@ macro_rules! define_aes_impl {
         #[derive(Clone)]
         pub struct $name_dec {
             keys: Simd128RoundKeys<$rounds>,
+            #[allow(unused)] // unused when VAES backends are not available
             features: Features,
+            backend: Backend,
         }
 
         impl Drop for $name_dec {
@@ -374,8 +466,47 @@ macro_rules! define_aes_impl {
             fn from(enc: &$name_enc) -> $name_dec {
                 Self {
                     keys: unsafe { self::ni::expand::inv_keys(&enc.keys) },
-                    features: enc.features.clone(),
+                    features: enc.features,
+                    backend: enc.backend,
+                }
+            }
+        }
+
+        impl $name_dec {
+            pub(crate) fn new_with_backend(
+                key: &Key<Self>,
+                backend: crate::Backend,
+            ) -> Result<Self, BackendUnavailableError> {
+                $name_enc::new_with_backend(key, backend).map(Into::into)
+            }
+
+            pub(crate) fn backend(&self) -> crate::Backend {
+                self.backend.into()
+            }
+
+            pub(crate) fn rekey(&mut self, key: &Key<Self>) {
+                let encrypt = $name_enc {
+                    // SAFETY: we enforce that this code is called only when
+                    // target features required by `expand` were properly checked.
+                    keys: unsafe { self::ni::expand::$module::expand_key(key.as_ref()) },
+                    features: self.features,
+                    backend: self.backend,
+                };
+                self.set_inv_keys(&encrypt);
+            }
+
+            /// Replace the round keys with the inverse of the `enc` ones.
+            fn set_inv_keys(&mut self, enc: &$name_enc) {
+                #[cfg(feature = "zeroize")]
+                unsafe {
+                    zeroize::zeroize_flat_type(&mut self.keys)
                 }
+                self.keys = unsafe { self::ni::expand::inv_keys(&enc.keys) };
+            }
+
+            #[cfg(feature = "hazmat")]
+            pub(crate) fn from_round_keys(round_keys: &[Block; $rounds]) -> Self {
+                $name_enc::from_round_keys(round_keys).into()
             }
         }
 
@@ -386,19 +517,18 @@ macro_rules! define_aes_impl {
         impl BlockCipherDecrypt for $name_dec {
             #[inline]
             fn decrypt_with_backend(&self, f: impl BlockCipherDecClosure<BlockSize = U16>) {
-                let features = self.features;
                 let keys = &self.keys;
-                match features.dispatch() {
+                match self.backend {
                     self::Backend::Ni => f.call(&mut $name_backend::Ni { keys }),
-                    #[cfg(all(target_arch = "x86_64", any(aes_backend = "avx256", aes_backend = "avx512")))]
+                    #[cfg(all(target_arch = "x86_64", not(aes_backend = "ni")))]
                     self::Backend::Vaes256 => f.call(&mut $name_backend::Vaes256 {
-                        features,
+                        features: self.features,
                         keys,
                         simd_256_keys: OnceCell::new(),
                     }),
-                    #[cfg(all(target_arch = "x86_64", aes_backend = "avx512"))]
+                    #[cfg(all(target_arch = "x86_64", not(any(aes_backend = "ni", aes_backend = "avx256"))))]
                     self::Backend::Vaes512 => f.call(&mut $name_backend::Vaes512 {
-                        features,
+                        features: self.features,
                         keys,
                         simd_512_keys: OnceCell::new(),
                     }),
@@ -421,11 +551,11 @@ macro_rules! define_aes_impl {
         impl<'a> BlockSizeUser for $name_backend::Ni<'a> {
             type BlockSize = U16;
         }
-        #[cfg(all(target_arch = "x86_64", any(aes_backend = "avx256", aes_backend = "avx512")))]
+        #[cfg(all(target_arch = "x86_64", not(aes_backend = "ni")))]
         impl<'a> BlockSizeUser for $name_backend::Vaes256<'a> {
             type BlockSize = U16;
         }
-        #[cfg(all(target_arch = "x86_64", aes_backend = "avx512"))]
+        #[cfg(all(target_arch = "x86_64", not(any(aes_backend = "ni", aes_backend = "avx256"))))]
         impl<'a> BlockSizeUser for $name_backend::Vaes512<'a> {
             type BlockSize = U16;
         }
@@ -433,7 +563,7 @@ macro_rules! define_aes_impl {
         impl<'a> ParBlocksSizeUser for $name_backend::Ni<'a> {
             type ParBlocksSize = U8;
         }
-        #[cfg(all(target_arch = "x86_64", any(aes_backend = "avx256", aes_backend = "avx512")))]
+        #[cfg(all(target_arch = "x86_64", not(aes_backend = "ni")))]
         impl<'a> ParBlocksSizeUser for $name_backend::Vaes256<'a> {
             // Block size of 30 is chosen based on AVX2's 16 YMM registers.
             //
@@ -443,7 +573,7 @@ macro_rules! define_aes_impl {
             // This gives (16 <total> - 1 <round key>) * 2 <data> = 30 <data>.
             type ParBlocksSize = U30;
         }
-        #[cfg(all(target_arch = "x86_64", aes_backend = "avx512"))]
+        #[cfg(all(target_arch = "x86_64", not(any(aes_backend = "ni", aes_backend = "avx256"))))]
         impl<'a> ParBlocksSizeUser for $name_backend::Vaes512<'a> {
             // Block size of 64 is chosen based on AVX512's 32 ZMM registers.
             //
@@ -470,7 +600,7 @@ macro_rules! define_aes_impl {
                 }
             }
         }
-        #[cfg(all(target_arch = "x86_64", any(aes_backend = "avx256", aes_backend = "avx512")))]
+        #[cfg(all(target_arch = "x86_64", not(aes_backend = "ni")))]
         impl<'a> BlockCipherEncBackend for $name_backend::Vaes256<'a> {
             #[inline]
             fn encrypt_block(&self, block: InOut<'_, '_, Block>) {
@@ -512,7 +642,7 @@ macro_rules! define_aes_impl {
                 }
             }
         }
-        #[cfg(all(target_arch = "x86_64", aes_backend = "avx512"))]
+        #[cfg(all(target_arch = "x86_64", not(any(aes_backend = "ni", aes_backend = "avx256"))))]
         impl<'a> BlockCipherEncBackend for $name_backend::Vaes512<'a> {
             #[inline]
             fn encrypt_block(&self, block: InOut<'_, '_, Block>) {
@@ -580,7 +710,7 @@ macro_rules! define_aes_impl {
                 }
             }
         }
-        #[cfg(all(target_arch = "x86_64", any(aes_backend = "avx256", aes_backend = "avx512")))]
+        #[cfg(all(target_arch = "x86_64", not(aes_backend = "ni")))]
         impl<'a> BlockCipherDecBackend for $name_backend::Vaes256<'a> {
             #[inline]
             fn decrypt_block(&self, block: InOut<'_, '_, Block>) {
@@ -622,7 +752,7 @@ macro_rules! define_aes_impl {
                 }
             }
         }
-        #[cfg(all(target_arch = "x86_64", aes_backend = "avx512"))]
+        #[cfg(all(target_arch = "x86_64", not(any(aes_backend = "ni", aes_backend = "avx256"))))]
         impl<'a> BlockCipherDecBackend for $name_backend::Vaes512<'a> {
             #[inline]
             fn decrypt_block(&self, block: InOut<'_, '_, Block>) {
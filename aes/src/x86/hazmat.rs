@@ -0,0 +1,46 @@
+//! Low-level "hazmat" AES functions: x86/x86_64 backend selection.
+//!
+//! Parallel round functions use the widest VAES backend supported by the CPU,
+//! while everything else is provided by the AES-NI backend.
+#![allow(unsafe_op_in_unsafe_fn)]
+
+pub(crate) use super::ni::hazmat::{
+    aes128_expand_key, aes192_expand_key, aes256_expand_key, cipher_final_round, cipher_round,
+    equiv_inv_cipher_round, inv_cipher_final_round, inv_cipher_round, inv_expanded_keys,
+    inv_mix_columns, mix_columns,
+};
+
+// VAES has no `InvMixColumns` instruction
+pub(crate) use super::ni::hazmat::inv_cipher_round_par;
+
+use crate::hazmat::Block;
+use cipher::array::{Array, ArraySize};
+
+macro_rules! define_par_round {
+    ($($name:ident),+) => {
+        $(
+            #[target_feature(enable = "aes")]
+            pub(crate) unsafe fn $name<N: ArraySize>(
+                blocks: &mut Array<Block, N>,
+                round_keys: &Array<Block, N>,
+            ) {
+                #[cfg(all(target_arch = "x86_64", not(any(aes_backend = "ni", aes_backend = "avx256"))))]
+                if super::features::vaes::get() && super::features::avx512f::get() {
+                    return super::vaes512::hazmat::$name(blocks, round_keys);
+                }
+                #[cfg(all(target_arch = "x86_64", not(aes_backend = "ni")))]
+                if super::features::vaes::get() && super::features::avx2::get() {
+                    return super::vaes256::hazmat::$name(blocks, round_keys);
+                }
+                super::ni::hazmat::$name(blocks, round_keys)
+            }
+        )+
+    };
+}
+
+define_par_round!(
+    cipher_round_par,
+    cipher_final_round_par,
+    equiv_inv_cipher_round_par,
+    inv_cipher_final_round_par
+);
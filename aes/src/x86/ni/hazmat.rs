@@ -5,7 +5,8 @@
 //! access to the AES round function gated under the `hazmat` crate feature.
 #![allow(unsafe_op_in_unsafe_fn)]
 
-use crate::hazmat::{Block, Block8};
+use super::expand;
+use crate::hazmat::Block;
 use crate::x86::arch::*;
 use cipher::array::{Array, ArraySize};
 
@@ -39,17 +40,46 @@ pub(crate) unsafe fn cipher_round(block: &mut Block, round_key: &Block) {
 
 /// AES cipher (encrypt) round function: parallel version.
 #[target_feature(enable = "aes")]
-pub(crate) unsafe fn cipher_round_par(blocks: &mut Block8, round_keys: &Block8) {
+pub(crate) unsafe fn cipher_round_par<N: ArraySize>(
+    blocks: &mut Array<Block, N>,
+    round_keys: &Array<Block, N>,
+) {
     let xmm_keys = load(round_keys);
     let mut xmm_blocks = load(blocks);
 
-    for i in 0..8 {
+    for i in 0..N::USIZE {
         xmm_blocks[i] = _mm_aesenc_si128(xmm_blocks[i], xmm_keys[i]);
     }
 
     store(blocks, xmm_blocks);
 }
 
+/// AES cipher (encrypt) final round function.
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn cipher_final_round(block: &mut Block, round_key: &Block) {
+    // Safety: `loadu` and `storeu` support unaligned access
+    let b = _mm_loadu_si128(block.as_ptr() as *const __m128i);
+    let k = _mm_loadu_si128(round_key.as_ptr() as *const __m128i);
+    let out = _mm_aesenclast_si128(b, k);
+    _mm_storeu_si128(block.as_mut_ptr() as *mut __m128i, out);
+}
+
+/// AES cipher (encrypt) final round function: parallel version.
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn cipher_final_round_par<N: ArraySize>(
+    blocks: &mut Array<Block, N>,
+    round_keys: &Array<Block, N>,
+) {
+    let xmm_keys = load(round_keys);
+    let mut xmm_blocks = load(blocks);
+
+    for i in 0..N::USIZE {
+        xmm_blocks[i] = _mm_aesenclast_si128(xmm_blocks[i], xmm_keys[i]);
+    }
+
+    store(blocks, xmm_blocks);
+}
+
 /// AES cipher (encrypt) round function.
 #[target_feature(enable = "aes")]
 pub(crate) unsafe fn equiv_inv_cipher_round(block: &mut Block, round_key: &Block) {
@@ -60,19 +90,74 @@ pub(crate) unsafe fn equiv_inv_cipher_round(block: &mut Block, round_key: &Block
     _mm_storeu_si128(block.as_mut_ptr() as *mut __m128i, out);
 }
 
-/// AES cipher (encrypt) round function: parallel version.
+/// AES equivalent inverse cipher (decrypt) round function: parallel version.
 #[target_feature(enable = "aes")]
-pub(crate) unsafe fn equiv_inv_cipher_round_par(blocks: &mut Block8, round_keys: &Block8) {
+pub(crate) unsafe fn equiv_inv_cipher_round_par<N: ArraySize>(
+    blocks: &mut Array<Block, N>,
+    round_keys: &Array<Block, N>,
+) {
     let xmm_keys = load(round_keys);
     let mut xmm_blocks = load(blocks);
 
-    for i in 0..8 {
+    for i in 0..N::USIZE {
         xmm_blocks[i] = _mm_aesdec_si128(xmm_blocks[i], xmm_keys[i]);
     }
 
     store(blocks, xmm_blocks);
 }
 
+/// AES inverse cipher (decrypt) round function.
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn inv_cipher_round(block: &mut Block, round_key: &Block) {
+    // Safety: `loadu` and `storeu` support unaligned access
+    let b = _mm_loadu_si128(block.as_ptr() as *const __m128i);
+    let k = _mm_loadu_si128(round_key.as_ptr() as *const __m128i);
+    let out = _mm_aesimc_si128(_mm_aesdeclast_si128(b, k));
+    _mm_storeu_si128(block.as_mut_ptr() as *mut __m128i, out);
+}
+
+/// AES inverse cipher (decrypt) round function: parallel version.
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn inv_cipher_round_par<N: ArraySize>(
+    blocks: &mut Array<Block, N>,
+    round_keys: &Array<Block, N>,
+) {
+    let xmm_keys = load(round_keys);
+    let mut xmm_blocks = load(blocks);
+
+    for i in 0..N::USIZE {
+        xmm_blocks[i] = _mm_aesimc_si128(_mm_aesdeclast_si128(xmm_blocks[i], xmm_keys[i]));
+    }
+
+    store(blocks, xmm_blocks);
+}
+
+/// AES inverse cipher (decrypt) final round function.
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn inv_cipher_final_round(block: &mut Block, round_key: &Block) {
+    // Safety: `loadu` and `storeu` support unaligned access
+    let b = _mm_loadu_si128(block.as_ptr() as *const __m128i);
+    let k = _mm_loadu_si128(round_key.as_ptr() as *const __m128i);
+    let out = _mm_aesdeclast_si128(b, k);
+    _mm_storeu_si128(block.as_mut_ptr() as *mut __m128i, out);
+}
+
+/// AES inverse cipher (decrypt) final round function: parallel version.
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn inv_cipher_final_round_par<N: ArraySize>(
+    blocks: &mut Array<Block, N>,
+    round_keys: &Array<Block, N>,
+) {
+    let xmm_keys = load(round_keys);
+    let mut xmm_blocks = load(blocks);
+
+    for i in 0..N::USIZE {
+        xmm_blocks[i] = _mm_aesdeclast_si128(xmm_blocks[i], xmm_keys[i]);
+    }
+
+    store(blocks, xmm_blocks);
+}
+
 /// AES mix columns function.
 #[target_feature(enable = "aes")]
 pub(crate) unsafe fn mix_columns(block: &mut Block) {
@@ -95,3 +180,47 @@ pub(crate) unsafe fn inv_mix_columns(block: &mut Block) {
     let out = _mm_aesimc_si128(b);
     _mm_storeu_si128(block.as_mut_ptr() as *mut __m128i, out);
 }
+
+/// Load round keys into XMM registers.
+#[target_feature(enable = "sse2")]
+pub(crate) unsafe fn load_keys<const N: usize>(round_keys: &[Block; N]) -> [__m128i; N] {
+    let mut keys: [__m128i; N] = core::mem::zeroed();
+    for (key, round_key) in keys.iter_mut().zip(round_keys) {
+        *key = _mm_loadu_si128(round_key.as_ptr().cast());
+    }
+    keys
+}
+
+/// Store round keys from XMM registers.
+#[target_feature(enable = "sse2")]
+pub(crate) unsafe fn store_keys<const N: usize>(keys: &[__m128i; N]) -> [Block; N] {
+    let mut round_keys = [Block::default(); N];
+    for (round_key, key) in round_keys.iter_mut().zip(keys) {
+        _mm_storeu_si128(round_key.as_mut_ptr().cast(), *key);
+    }
+    round_keys
+}
+
+/// AES-128 key expansion.
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn aes128_expand_key(key: &[u8; 16]) -> [Block; 11] {
+    store_keys(&expand::aes128::expand_key(key))
+}
+
+/// AES-192 key expansion.
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn aes192_expand_key(key: &[u8; 24]) -> [Block; 13] {
+    store_keys(&expand::aes192::expand_key(key))
+}
+
+/// AES-256 key expansion.
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn aes256_expand_key(key: &[u8; 32]) -> [Block; 15] {
+    store_keys(&expand::aes256::expand_key(key))
+}
+
+/// Compute inverse expanded keys (for decryption).
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn inv_expanded_keys<const N: usize>(round_keys: &[Block; N]) -> [Block; N] {
+    store_keys(&expand::inv_keys(&load_keys(round_keys)))
+}
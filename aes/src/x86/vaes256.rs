@@ -1 +1,4 @@
 pub(super) mod encdec;
+
+#[cfg(feature = "hazmat")]
+pub(crate) mod hazmat;
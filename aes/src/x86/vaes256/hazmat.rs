@@ -0,0 +1,65 @@
+//! Low-level "hazmat" AES functions: VAES256 support.
+//!
+//! Processes 2 blocks per m256i register, with any remaining blocks
+//! handled using the 128-bit AES-NI instructions.
+#![allow(unsafe_op_in_unsafe_fn)]
+
+use crate::hazmat::Block;
+use crate::x86::arch::*;
+use cipher::array::{Array, ArraySize};
+
+macro_rules! define_par_round {
+    ($(#[$attr:meta])* $name:ident, $op256:ident, $op128:ident) => {
+        $(#[$attr])*
+        #[target_feature(enable = "aes,avx2,vaes")]
+        pub(crate) unsafe fn $name<N: ArraySize>(
+            blocks: &mut Array<Block, N>,
+            round_keys: &Array<Block, N>,
+        ) {
+            let mut block_chunks = blocks.chunks_exact_mut(2);
+            let mut key_chunks = round_keys.chunks_exact(2);
+
+            // Safety: `read_unaligned` and `write_unaligned` support unaligned access
+            for (chunk, keys) in (&mut block_chunks).zip(&mut key_chunks) {
+                let b = chunk.as_ptr().cast::<__m256i>().read_unaligned();
+                let k = keys.as_ptr().cast::<__m256i>().read_unaligned();
+                chunk.as_mut_ptr().cast::<__m256i>().write_unaligned($op256(b, k));
+            }
+
+            let tail = block_chunks.into_remainder();
+            for (block, key) in tail.iter_mut().zip(key_chunks.remainder()) {
+                let b = _mm_loadu_si128(block.as_ptr().cast());
+                let k = _mm_loadu_si128(key.as_ptr().cast());
+                _mm_storeu_si128(block.as_mut_ptr().cast(), $op128(b, k));
+            }
+        }
+    };
+}
+
+define_par_round!(
+    /// AES cipher (encrypt) round function: parallel version.
+    cipher_round_par,
+    _mm256_aesenc_epi128,
+    _mm_aesenc_si128
+);
+
+define_par_round!(
+    /// AES cipher (encrypt) final round function: parallel version.
+    cipher_final_round_par,
+    _mm256_aesenclast_epi128,
+    _mm_aesenclast_si128
+);
+
+define_par_round!(
+    /// AES equivalent inverse cipher (decrypt) round function: parallel version.
+    equiv_inv_cipher_round_par,
+    _mm256_aesdec_epi128,
+    _mm_aesdec_si128
+);
+
+define_par_round!(
+    /// AES inverse cipher (decrypt) final round function: parallel version.
+    inv_cipher_final_round_par,
+    _mm256_aesdeclast_epi128,
+    _mm_aesdeclast_si128
+);
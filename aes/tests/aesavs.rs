@@ -0,0 +1,216 @@
+//! NIST CAVP AES Algorithm Validation Suite (AESAVS) tests for ECB mode:
+//! https://csrc.nist.gov/projects/cryptographic-algorithm-validation-program/block-ciphers
+//!
+//! The known answer (GFSbox, KeySbox, VarKey, VarTxt), multi-block message
+//! (MMT) and Monte Carlo (MCT) tests are executed against every backend
+//! available on the current CPU. The "soft" backend variants are covered by
+//! running this suite with the corresponding `aes_backend_soft` and `cpubits`
+//! configuration flags.
+
+use aes::cipher::{Array, BlockCipherDecrypt, BlockCipherEncrypt, BlockSizeUser, consts::U16};
+use aes::{Aes128, Aes192, Aes256, Backend, Block};
+
+const BACKENDS: &[Backend] = &[
+    Backend::Soft,
+    Backend::Vperm,
+    Backend::Ni,
+    Backend::Vaes256,
+    Backend::Vaes512,
+    Backend::Armv8,
+];
+
+/// Test vector parsed from a CAVP `.rsp` file.
+struct Vector {
+    decrypt: bool,
+    key: Vec<u8>,
+    plaintext: Vec<u8>,
+    ciphertext: Vec<u8>,
+}
+
+impl Vector {
+    /// Returns `(input, expected output)` for the direction of this vector.
+    fn io(&self) -> (&[u8], &[u8]) {
+        if self.decrypt {
+            (&self.ciphertext, &self.plaintext)
+        } else {
+            (&self.plaintext, &self.ciphertext)
+        }
+    }
+}
+
+fn hex(s: &str) -> Vec<u8> {
+    assert!(s.len().is_multiple_of(2), "odd hex length: {s}");
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn parse(data: &str) -> Vec<Vector> {
+    let mut vectors = Vec::new();
+    let mut decrypt = false;
+    let (mut key, mut plaintext, mut ciphertext) = (None, None, None);
+
+    for line in data.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match line {
+            "[ENCRYPT]" => decrypt = false,
+            "[DECRYPT]" => decrypt = true,
+            _ => {
+                let (name, value) = line.split_once(" = ").expect("malformed line");
+                match name {
+                    "COUNT" => {}
+                    "KEY" => key = Some(hex(value)),
+                    "PLAINTEXT" => plaintext = Some(hex(value)),
+                    "CIPHERTEXT" => ciphertext = Some(hex(value)),
+                    _ => panic!("unexpected field: {name}"),
+                }
+            }
+        }
+
+        if key.is_some() && plaintext.is_some() && ciphertext.is_some() {
+            vectors.push(Vector {
+                decrypt,
+                key: key.take().unwrap(),
+                plaintext: plaintext.take().unwrap(),
+                ciphertext: ciphertext.take().unwrap(),
+            });
+        }
+    }
+
+    assert!(!vectors.is_empty());
+    vectors
+}
+
+fn to_blocks(bytes: &[u8]) -> Vec<Block> {
+    bytes
+        .chunks(16)
+        .map(|chunk| Block::try_from(chunk).unwrap())
+        .collect()
+}
+
+/// Run known answer and multi-block message tests.
+fn check_vectors<C>(data: &str, new: fn(&[u8], Backend) -> Option<C>)
+where
+    C: BlockCipherEncrypt + BlockCipherDecrypt + BlockSizeUser<BlockSize = U16>,
+{
+    let vectors = parse(data);
+    for &backend in BACKENDS {
+        if new(&vectors[0].key, backend).is_none() {
+            continue;
+        }
+
+        for (i, v) in vectors.iter().enumerate() {
+            let cipher = new(&v.key, backend).unwrap();
+            let (input, expected) = v.io();
+            let mut blocks = to_blocks(input);
+            if v.decrypt {
+                cipher.decrypt_blocks(&mut blocks);
+            } else {
+                cipher.encrypt_blocks(&mut blocks);
+            }
+            assert_eq!(blocks, to_blocks(expected), "{backend}: vector {i} failed");
+        }
+    }
+}
+
+/// Run Monte Carlo tests as specified in AESAVS section 6.4.1.
+fn monte_carlo<C>(data: &str, new: fn(&[u8], Backend) -> Option<C>)
+where
+    C: BlockCipherEncrypt + BlockCipherDecrypt + BlockSizeUser<BlockSize = U16>,
+{
+    let vectors = parse(data);
+    for &backend in BACKENDS {
+        if new(&vectors[0].key, backend).is_none() {
+            continue;
+        }
+
+        for decrypt in [false, true] {
+            let mut outer = vectors.iter().filter(|v| v.decrypt == decrypt).peekable();
+            let Some(first) = outer.peek() else {
+                continue;
+            };
+            let mut key = first.key.clone();
+            let mut block = Block::try_from(first.io().0).unwrap();
+
+            for (i, v) in outer.enumerate() {
+                assert_eq!(key, v.key, "{backend}: MCT key {i} mismatch");
+                assert_eq!(&block[..], v.io().0, "{backend}: MCT input {i} mismatch");
+
+                let cipher = new(&key, backend).unwrap();
+                let mut prev = block;
+                for _ in 0..1000 {
+                    prev = block;
+                    if decrypt {
+                        cipher.decrypt_block(&mut block);
+                    } else {
+                        cipher.encrypt_block(&mut block);
+                    }
+                }
+                assert_eq!(&block[..], v.io().1, "{backend}: MCT output {i} mismatch");
+
+                // Key is XORed with the trailing bytes of `CT[998] || CT[999]`
+                let tail: Vec<u8> = prev.iter().chain(block.iter()).copied().collect();
+                let tail = &tail[tail.len() - key.len()..];
+                key.iter_mut().zip(tail).for_each(|(k, t)| *k ^= t);
+            }
+        }
+    }
+}
+
+macro_rules! aesavs_tests {
+    ($name:ident, $cipher:ty, $bits:literal $(, $mct:ident)?) => {
+        mod $name {
+            use super::*;
+
+            fn new(key: &[u8], backend: Backend) -> Option<$cipher> {
+                let key = Array::try_from(key).unwrap();
+                <$cipher>::new_with_backend(&key, backend).ok()
+            }
+
+            #[test]
+            fn gfsbox() {
+                let data = include_str!(concat!("data/aesavs/ECBGFSbox", $bits, ".rsp"));
+                check_vectors(data, new);
+            }
+
+            #[test]
+            fn keysbox() {
+                let data = include_str!(concat!("data/aesavs/ECBKeySbox", $bits, ".rsp"));
+                check_vectors(data, new);
+            }
+
+            #[test]
+            fn varkey() {
+                let data = include_str!(concat!("data/aesavs/ECBVarKey", $bits, ".rsp"));
+                check_vectors(data, new);
+            }
+
+            #[test]
+            fn vartxt() {
+                let data = include_str!(concat!("data/aesavs/ECBVarTxt", $bits, ".rsp"));
+                check_vectors(data, new);
+            }
+
+            #[test]
+            fn mmt() {
+                let data = include_str!(concat!("data/aesavs/ECBMMT", $bits, ".rsp"));
+                check_vectors(data, new);
+            }
+
+            $(
+                #[test]
+                fn $mct() {
+                    let data = include_str!(concat!("data/aesavs/ECBMCT", $bits, ".rsp"));
+                    monte_carlo(data, new);
+                }
+            )?
+        }
+    };
+}
+
+aesavs_tests!(aes128, Aes128, "128", mct);
+aesavs_tests!(aes192, Aes192, "192");
+aesavs_tests!(aes256, Aes256, "256");
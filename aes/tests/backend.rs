@@ -0,0 +1,177 @@
+//! Tests for runtime backend introspection and selection.
+
+use aes::cipher::{Array, BlockCipherDecrypt, BlockCipherEncrypt, KeyInit};
+use aes::{Aes128, Aes128Dec, Aes128Enc, Aes192, Aes256, Backend};
+
+const BACKENDS: &[Backend] = &[
+    Backend::Soft,
+    Backend::Vperm,
+    Backend::Ni,
+    Backend::Vaes256,
+    Backend::Vaes512,
+    Backend::Armv8,
+];
+
+macro_rules! backend_test {
+    ($name:ident, $cipher:ty, $key_len:expr) => {
+        #[test]
+        fn $name() {
+            let key = Array::from([0x42; $key_len]);
+            let reference = <$cipher>::new_with_backend(&key, Backend::Soft).unwrap();
+            assert_eq!(reference.backend(), Backend::Soft);
+
+            let mut expected = [Array::from([0u8; 16]); 100];
+            for (i, block) in expected.iter_mut().enumerate() {
+                block[0] = i as u8;
+            }
+            let plaintext = expected;
+            reference.encrypt_blocks(&mut expected);
+
+            for &backend in BACKENDS {
+                let cipher = match <$cipher>::new_with_backend(&key, backend) {
+                    Ok(cipher) => cipher,
+                    Err(err) => {
+                        assert_eq!(err.backend(), backend);
+                        continue;
+                    }
+                };
+                assert_eq!(cipher.backend(), backend);
+
+                let mut blocks = plaintext;
+                cipher.encrypt_blocks(&mut blocks);
+                assert_eq!(blocks, expected, "{backend} encryption mismatch");
+
+                cipher.decrypt_blocks(&mut blocks);
+                assert_eq!(blocks, plaintext, "{backend} decryption mismatch");
+            }
+        }
+    };
+}
+
+backend_test!(aes128_backends, Aes128, 16);
+backend_test!(aes192_backends, Aes192, 24);
+backend_test!(aes256_backends, Aes256, 32);
+
+macro_rules! rekey_test {
+    ($name:ident, $cipher:ty, $key_len:expr) => {
+        #[test]
+        fn $name() {
+            let key1 = Array::from([0x42; $key_len]);
+            let key2 = Array::from([0x24; $key_len]);
+            let reference = <$cipher>::new(&key2);
+
+            for &backend in BACKENDS {
+                let Ok(mut cipher) = <$cipher>::new_with_backend(&key1, backend) else {
+                    continue;
+                };
+                cipher.rekey(&key2);
+                assert_eq!(cipher.backend(), backend);
+
+                let mut block1 = Array::from([0x11; 16]);
+                let mut block2 = block1;
+                cipher.encrypt_block(&mut block1);
+                reference.encrypt_block(&mut block2);
+                assert_eq!(block1, block2, "{backend} rekey encryption mismatch");
+
+                cipher.decrypt_block(&mut block1);
+                assert_eq!(block1, [0x11; 16], "{backend} rekey decryption mismatch");
+            }
+        }
+    };
+}
+
+rekey_test!(aes128_rekey, Aes128, 16);
+rekey_test!(aes192_rekey, Aes192, 24);
+rekey_test!(aes256_rekey, Aes256, 32);
+
+#[test]
+fn enc_dec_rekey() {
+    let key1 = Array::from([0x42; 16]);
+    let key2 = Array::from([0x24; 16]);
+    let reference = Aes128::new(&key2);
+
+    for &backend in BACKENDS {
+        let (Ok(mut enc), Ok(mut dec)) = (
+            Aes128Enc::new_with_backend(&key1, backend),
+            Aes128Dec::new_with_backend(&key1, backend),
+        ) else {
+            continue;
+        };
+        enc.rekey(&key2);
+        dec.rekey(&key2);
+        assert_eq!(enc.backend(), backend);
+        assert_eq!(dec.backend(), backend);
+
+        let mut block1 = Array::from([0x11; 16]);
+        let mut block2 = block1;
+        enc.encrypt_block(&mut block1);
+        reference.encrypt_block(&mut block2);
+        assert_eq!(block1, block2, "{backend} rekey encryption mismatch");
+
+        dec.decrypt_block(&mut block1);
+        assert_eq!(block1, [0x11; 16], "{backend} rekey decryption mismatch");
+    }
+}
+
+#[test]
+fn default_backend_is_available() {
+    let key = Array::from([0u8; 16]);
+    let backend = Aes128::new(&key).backend();
+    let cipher = Aes128::new_with_backend(&key, backend).unwrap();
+    assert_eq!(cipher.backend(), backend);
+}
+
+#[test]
+fn enc_dec_preserve_backend() {
+    let key = Array::from([0u8; 16]);
+    let enc = Aes128Enc::new_with_backend(&key, Backend::Soft).unwrap();
+    assert_eq!(enc.backend(), Backend::Soft);
+    assert_eq!(Aes128Dec::from(&enc).backend(), Backend::Soft);
+    assert_eq!(Aes128::from(&enc).backend(), Backend::Soft);
+    assert_eq!(Aes128::from(enc).clone().backend(), Backend::Soft);
+
+    let dec = Aes128Dec::new_with_backend(&key, Backend::Soft).unwrap();
+    assert_eq!(dec.backend(), Backend::Soft);
+}
+
+#[test]
+fn default_backend_is_widest() {
+    let key = Array::from([0u8; 16]);
+    let default = Aes128::new(&key).backend();
+    let widest = [
+        Backend::Vaes512,
+        Backend::Vaes256,
+        Backend::Ni,
+        Backend::Armv8,
+        Backend::Vperm,
+    ]
+    .into_iter()
+    .find(|&backend| Aes128::new_with_backend(&key, backend).is_ok())
+    .unwrap_or(Backend::Soft);
+    assert_eq!(default, widest);
+}
+
+#[test]
+fn vperm_matches_soft() {
+    let mut key = Array::from([0u8; 32]);
+    let mut block = Array::from([0u8; 16]);
+    for _ in 0..64 {
+        let Ok(vperm) = Aes256::new_with_backend(&key, Backend::Vperm) else {
+            return;
+        };
+        let soft = Aes256::new_with_backend(&key, Backend::Soft).unwrap();
+
+        let mut expected = block;
+        soft.encrypt_block(&mut expected);
+        let mut actual = block;
+        vperm.encrypt_block(&mut actual);
+        assert_eq!(actual, expected);
+
+        vperm.decrypt_block(&mut actual);
+        assert_eq!(actual, block);
+
+        key[..16].copy_from_slice(&expected);
+        key[16..].copy_from_slice(&block);
+        block = expected;
+    }
+}
@@ -3,6 +3,10 @@
 // TODO(tarcieri): support for using the hazmat functions with the `soft` backend
 #![cfg(feature = "hazmat")]
 
+use aes::cipher::{
+    array::{Array, ArraySize},
+    consts::{U1, U2, U3, U4, U6, U7, U16, U19},
+};
 use aes::hazmat::{Block, Block8};
 use hex_literal::hex;
 
@@ -74,6 +78,66 @@ const EQUIV_INV_CIPHER_ROUND_TEST_VECTORS: &[RoundTestVector] = &[
     },
 ];
 
+/// Cipher final round function test vectors from FIPS 197 Appendix C.
+const CIPHER_FINAL_ROUND_TEST_VECTORS: &[RoundTestVector] = &[
+    // C.1 AES-128 round 10
+    RoundTestVector {
+        start: hex!("bd6e7c3df2b5779e0b61216e8b10b689"),
+        k_sch: hex!("13111d7fe3944a17f307a78b4d2b30c5"),
+        output: hex!("69c4e0d86a7b0430d8cdb78070b4c55a"),
+    },
+    // C.2 AES-192 round 12
+    RoundTestVector {
+        start: hex!("afb73eeb1cd1b85162280f27fb20d585"),
+        k_sch: hex!("a4970a331a78dc09c418c271e3a41d5d"),
+        output: hex!("dda97ca4864cdfe06eaf70a0ec0d7191"),
+    },
+    // C.3 AES-256 round 14
+    RoundTestVector {
+        start: hex!("627bceb9999d5aaac945ecf423f56da5"),
+        k_sch: hex!("24fc79ccbf0979e9371ac23c6d68de36"),
+        output: hex!("8ea2b7ca516745bfeafc49904b496089"),
+    },
+];
+
+/// Inverse Cipher round function test vectors from FIPS 197 Appendix C.1.
+const INV_CIPHER_ROUND_TEST_VECTORS: &[RoundTestVector] = &[
+    // round 1
+    RoundTestVector {
+        start: hex!("7ad5fda789ef4e272bca100b3d9ff59f"),
+        k_sch: hex!("549932d1f08557681093ed9cbe2c974e"),
+        output: hex!("54d990a16ba09ab596bbf40ea111702f"),
+    },
+    // round 2
+    RoundTestVector {
+        start: hex!("54d990a16ba09ab596bbf40ea111702f"),
+        k_sch: hex!("47438735a41c65b9e016baf4aebf7ad2"),
+        output: hex!("3e1c22c0b6fcbf768da85067f6170495"),
+    },
+    // round 3
+    RoundTestVector {
+        start: hex!("3e1c22c0b6fcbf768da85067f6170495"),
+        k_sch: hex!("14f9701ae35fe28c440adf4d4ea9c026"),
+        output: hex!("b458124c68b68a014b99f82e5f15554c"),
+    },
+    // round 4
+    RoundTestVector {
+        start: hex!("b458124c68b68a014b99f82e5f15554c"),
+        k_sch: hex!("5e390f7df7a69296a7553dc10aa31f6b"),
+        output: hex!("e8dab6901477d4653ff7f5e2e747dd4f"),
+    },
+];
+
+/// Inverse Cipher final round function test vectors from FIPS 197 Appendix C.1.
+const INV_CIPHER_FINAL_ROUND_TEST_VECTORS: &[RoundTestVector] = &[
+    // round 10
+    RoundTestVector {
+        start: hex!("6353e08c0960e104cd70b751bacad0e7"),
+        k_sch: hex!("000102030405060708090a0b0c0d0e0f"),
+        output: hex!("00112233445566778899aabbccddeeff"),
+    },
+];
+
 #[test]
 fn cipher_round_fips197_vectors() {
     for vector in CIPHER_ROUND_TEST_VECTORS {
@@ -140,6 +204,156 @@ fn equiv_inv_cipher_round_par_fips197_vectors() {
     }
 }
 
+#[test]
+fn cipher_final_round_fips197_vectors() {
+    for vector in CIPHER_FINAL_ROUND_TEST_VECTORS {
+        let mut block = Block::from(vector.start);
+        aes::hazmat::cipher_final_round(&mut block, &vector.k_sch.into());
+        assert_eq!(block.as_slice(), &vector.output);
+    }
+}
+
+#[test]
+fn inv_cipher_round_fips197_vectors() {
+    for vector in INV_CIPHER_ROUND_TEST_VECTORS {
+        let mut block = Block::from(vector.start);
+        aes::hazmat::inv_cipher_round(&mut block, &vector.k_sch.into());
+        assert_eq!(block.as_slice(), &vector.output);
+    }
+}
+
+#[test]
+fn inv_cipher_final_round_fips197_vectors() {
+    for vector in INV_CIPHER_FINAL_ROUND_TEST_VECTORS {
+        let mut block = Block::from(vector.start);
+        aes::hazmat::inv_cipher_final_round(&mut block, &vector.k_sch.into());
+        assert_eq!(block.as_slice(), &vector.output);
+    }
+}
+
+/// Check a parallel round function with `N` blocks against `vectors`.
+fn check_round_par<N: ArraySize>(
+    vectors: &[RoundTestVector],
+    round_par: fn(&mut Array<Block, N>, &Array<Block, N>),
+) {
+    let mut blocks = Array::<Block, N>::default();
+    let mut round_keys = Array::<Block, N>::default();
+
+    for i in 0..N::USIZE {
+        let vector = &vectors[i % vectors.len()];
+        blocks[i] = Block::from(vector.start);
+        round_keys[i] = Block::from(vector.k_sch);
+    }
+
+    round_par(&mut blocks, &round_keys);
+
+    for (i, block) in blocks.iter().enumerate() {
+        let vector = &vectors[i % vectors.len()];
+        assert_eq!(
+            block.as_slice(),
+            &vector.output,
+            "width {}, block {i}",
+            N::USIZE
+        );
+    }
+}
+
+macro_rules! round_par_widths_test {
+    ($name:ident, $vectors:expr, $round_par:ident) => {
+        #[test]
+        fn $name() {
+            use aes::hazmat::$round_par;
+            check_round_par::<U1>($vectors, $round_par);
+            check_round_par::<U2>($vectors, $round_par);
+            check_round_par::<U3>($vectors, $round_par);
+            check_round_par::<U4>($vectors, $round_par);
+            check_round_par::<U6>($vectors, $round_par);
+            check_round_par::<U7>($vectors, $round_par);
+            check_round_par::<U16>($vectors, $round_par);
+            check_round_par::<U19>($vectors, $round_par);
+        }
+    };
+}
+
+round_par_widths_test!(
+    cipher_round_par_widths,
+    CIPHER_ROUND_TEST_VECTORS,
+    cipher_round_par
+);
+round_par_widths_test!(
+    cipher_final_round_par_widths,
+    CIPHER_FINAL_ROUND_TEST_VECTORS,
+    cipher_final_round_par
+);
+round_par_widths_test!(
+    equiv_inv_cipher_round_par_widths,
+    EQUIV_INV_CIPHER_ROUND_TEST_VECTORS,
+    equiv_inv_cipher_round_par
+);
+round_par_widths_test!(
+    inv_cipher_round_par_widths,
+    INV_CIPHER_ROUND_TEST_VECTORS,
+    inv_cipher_round_par
+);
+round_par_widths_test!(
+    inv_cipher_final_round_par_widths,
+    INV_CIPHER_FINAL_ROUND_TEST_VECTORS,
+    inv_cipher_final_round_par
+);
+
+/// Full cipher computations from FIPS 197 Appendix C, composed from the round functions.
+#[test]
+fn round_functions_fips197_cipher() {
+    fn check<const N: usize>(round_keys: &[Block; N], ciphertext: [u8; 16]) {
+        let plaintext = Block::from(hex!("00112233445566778899aabbccddeeff"));
+
+        let mut block = plaintext;
+        xor(&mut block, &round_keys[0]);
+        for round_key in &round_keys[1..N - 1] {
+            aes::hazmat::cipher_round(&mut block, round_key);
+        }
+        aes::hazmat::cipher_final_round(&mut block, &round_keys[N - 1]);
+        assert_eq!(block.as_slice(), &ciphertext);
+
+        xor(&mut block, &round_keys[N - 1]);
+        for round_key in round_keys[1..N - 1].iter().rev() {
+            aes::hazmat::inv_cipher_round(&mut block, round_key);
+        }
+        aes::hazmat::inv_cipher_final_round(&mut block, &round_keys[0]);
+        assert_eq!(block, plaintext);
+
+        let inv_round_keys = aes::hazmat::inv_expanded_keys(round_keys);
+        block = Block::from(ciphertext);
+        xor(&mut block, &inv_round_keys[0]);
+        for round_key in &inv_round_keys[1..N - 1] {
+            aes::hazmat::equiv_inv_cipher_round(&mut block, round_key);
+        }
+        aes::hazmat::inv_cipher_final_round(&mut block, &inv_round_keys[N - 1]);
+        assert_eq!(block, plaintext);
+    }
+
+    fn xor(block: &mut Block, round_key: &Block) {
+        for (a, b) in block.iter_mut().zip(round_key) {
+            *a ^= b;
+        }
+    }
+
+    check(
+        &aes::hazmat::aes128_expand_key(&hex!("000102030405060708090a0b0c0d0e0f")),
+        hex!("69c4e0d86a7b0430d8cdb78070b4c55a"),
+    );
+    check(
+        &aes::hazmat::aes192_expand_key(&hex!("000102030405060708090a0b0c0d0e0f1011121314151617")),
+        hex!("dda97ca4864cdfe06eaf70a0ec0d7191"),
+    );
+    check(
+        &aes::hazmat::aes256_expand_key(&hex!(
+            "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"
+        )),
+        hex!("8ea2b7ca516745bfeafc49904b496089"),
+    );
+}
+
 #[test]
 fn mix_columns_fips197_vector() {
     let mut block = Block::from(hex!("6353e08c0960e104cd70b751bacad0e7"));
@@ -153,3 +367,119 @@ fn inv_mix_columns_fips197_vector() {
     aes::hazmat::inv_mix_columns(&mut block);
     assert_eq!(block.as_slice(), &hex!("4773b91ff72f354361cb018ea1e6cf2c"))
 }
+
+/// Key expansion test vector from FIPS 197 Appendix A.1.
+const AES128_EXPANDED_KEY: [[u8; 16]; 11] = [
+    hex!("2b7e151628aed2a6abf7158809cf4f3c"),
+    hex!("a0fafe1788542cb123a339392a6c7605"),
+    hex!("f2c295f27a96b9435935807a7359f67f"),
+    hex!("3d80477d4716fe3e1e237e446d7a883b"),
+    hex!("ef44a541a8525b7fb671253bdb0bad00"),
+    hex!("d4d1c6f87c839d87caf2b8bc11f915bc"),
+    hex!("6d88a37a110b3efddbf98641ca0093fd"),
+    hex!("4e54f70e5f5fc9f384a64fb24ea6dc4f"),
+    hex!("ead27321b58dbad2312bf5607f8d292f"),
+    hex!("ac7766f319fadc2128d12941575c006e"),
+    hex!("d014f9a8c9ee2589e13f0cc8b6630ca6"),
+];
+
+#[test]
+fn aes128_expand_key_fips197_vector() {
+    let round_keys = aes::hazmat::aes128_expand_key(&hex!("2b7e151628aed2a6abf7158809cf4f3c"));
+    for (round_key, expected) in round_keys.iter().zip(&AES128_EXPANDED_KEY) {
+        assert_eq!(round_key.as_slice(), expected);
+    }
+}
+
+#[test]
+fn aes192_expand_key_fips197_vector() {
+    let key = hex!("8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b");
+    let round_keys = aes::hazmat::aes192_expand_key(&key);
+    assert_eq!(round_keys[0].as_slice(), &key[..16]);
+    assert_eq!(
+        round_keys[12].as_slice(),
+        &hex!("e98ba06f448c773c8ecc720401002202")
+    );
+}
+
+#[test]
+fn aes256_expand_key_fips197_vector() {
+    let key = hex!("603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4");
+    let round_keys = aes::hazmat::aes256_expand_key(&key);
+    assert_eq!(round_keys[0].as_slice(), &key[..16]);
+    assert_eq!(round_keys[1].as_slice(), &key[16..]);
+    assert_eq!(
+        round_keys[14].as_slice(),
+        &hex!("fe4890d1e6188d0b046df344706c631e")
+    );
+}
+
+#[test]
+fn inv_expanded_keys_fips197_vectors() {
+    let round_keys = aes::hazmat::aes128_expand_key(&hex!("000102030405060708090a0b0c0d0e0f"));
+    assert_eq!(
+        round_keys[1].as_slice(),
+        &CIPHER_ROUND_TEST_VECTORS[0].k_sch
+    );
+
+    let inv_round_keys = aes::hazmat::inv_expanded_keys(&round_keys);
+    assert_eq!(inv_round_keys[0], round_keys[10]);
+    assert_eq!(inv_round_keys[10], round_keys[0]);
+    for (inv_round_key, vector) in inv_round_keys[1..]
+        .iter()
+        .zip(EQUIV_INV_CIPHER_ROUND_TEST_VECTORS)
+    {
+        assert_eq!(inv_round_key.as_slice(), &vector.k_sch);
+    }
+}
+
+#[test]
+fn from_round_keys_fips197_vectors() {
+    use aes::cipher::{BlockCipherDecrypt, BlockCipherEncrypt};
+
+    let plaintext = Block::from(hex!("00112233445566778899aabbccddeeff"));
+
+    macro_rules! check {
+        ($cipher:ident, $enc:ident, $dec:ident, $expand:ident, $key:expr, $ciphertext:expr) => {
+            let round_keys = aes::hazmat::$expand(&$key);
+            let ciphertext = Block::from($ciphertext);
+
+            let mut block = plaintext;
+            aes::$enc::from_round_keys(&round_keys).encrypt_block(&mut block);
+            assert_eq!(block, ciphertext);
+            aes::$dec::from_round_keys(&round_keys).decrypt_block(&mut block);
+            assert_eq!(block, plaintext);
+
+            let cipher = aes::$cipher::from_round_keys(&round_keys);
+            cipher.encrypt_block(&mut block);
+            assert_eq!(block, ciphertext);
+            cipher.decrypt_block(&mut block);
+            assert_eq!(block, plaintext);
+        };
+    }
+
+    check!(
+        Aes128,
+        Aes128Enc,
+        Aes128Dec,
+        aes128_expand_key,
+        hex!("000102030405060708090a0b0c0d0e0f"),
+        hex!("69c4e0d86a7b0430d8cdb78070b4c55a")
+    );
+    check!(
+        Aes192,
+        Aes192Enc,
+        Aes192Dec,
+        aes192_expand_key,
+        hex!("000102030405060708090a0b0c0d0e0f1011121314151617"),
+        hex!("dda97ca4864cdfe06eaf70a0ec0d7191")
+    );
+    check!(
+        Aes256,
+        Aes256Enc,
+        Aes256Dec,
+        aes256_expand_key,
+        hex!("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"),
+        hex!("8ea2b7ca516745bfeafc49904b496089")
+    );
+}
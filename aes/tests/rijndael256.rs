@@ -0,0 +1,193 @@
+//! Rijndael-256 tests.
+//!
+//! `GLADMAN_*` vectors are from Brian Gladman's Rijndael test vectors for
+//! all block and key sizes (plaintext and key taken from the digits of pi
+//! and e).
+//!
+//! `ECB_VK_*` and `ECB_VT_*` vectors are a slice of the variable key and
+//! variable text known answer tests in the format of the Rijndael
+//! submission `ecb_vk.txt` and `ecb_vt.txt` files, with a 256-bit block:
+//! entries I=1, I=2 and the last I for every key size.
+
+use aes::cipher::{Array, BlockCipherDecrypt, BlockCipherEncrypt, KeyInit};
+use aes::{Rijndael256_128, Rijndael256_192, Rijndael256_256};
+use hex_literal::hex;
+
+/// Known answer test vector.
+struct TestVector {
+    key: &'static [u8],
+    plaintext: [u8; 32],
+    ciphertext: [u8; 32],
+}
+
+const GLADMAN_128: &[TestVector] = &[TestVector {
+    key: &hex!("2b7e151628aed2a6abf7158809cf4f3c"),
+    plaintext: hex!("3243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c8"),
+    ciphertext: hex!("7d15479076b69a46ffb3b3beae97ad8313f622f67fedb487de9f06b9ed9c8f19"),
+}];
+
+const ECB_VK_128: &[TestVector] = &[
+    TestVector {
+        key: &hex!("80000000000000000000000000000000"),
+        plaintext: hex!("0000000000000000000000000000000000000000000000000000000000000000"),
+        ciphertext: hex!("dd5f0f030eb01470daa8057151b6a5a24d694c94fc2efc0000e6a35bfb661fed"),
+    },
+    TestVector {
+        key: &hex!("40000000000000000000000000000000"),
+        plaintext: hex!("0000000000000000000000000000000000000000000000000000000000000000"),
+        ciphertext: hex!("8978a62a86ef403b2419cf7b3a86d2ccd6c9e850f95c82e4d8fce9c2f24127fc"),
+    },
+    TestVector {
+        key: &hex!("00000000000000000000000000000001"),
+        plaintext: hex!("0000000000000000000000000000000000000000000000000000000000000000"),
+        ciphertext: hex!("88cc57d51a2170390ef956db28009eba8ddce3e0419c5d7f584b537929739309"),
+    },
+];
+
+const ECB_VT_128: &[TestVector] = &[
+    TestVector {
+        key: &hex!("00000000000000000000000000000000"),
+        plaintext: hex!("8000000000000000000000000000000000000000000000000000000000000000"),
+        ciphertext: hex!("105ad7cd0c5d2f7b40df76a3d264248f10493fbb5710f90a0a84e1e09437f529"),
+    },
+    TestVector {
+        key: &hex!("00000000000000000000000000000000"),
+        plaintext: hex!("4000000000000000000000000000000000000000000000000000000000000000"),
+        ciphertext: hex!("b40827d4a748b5caa3116cab3ba8b8819b662811a21be0958bd112abad99aa8a"),
+    },
+    TestVector {
+        key: &hex!("00000000000000000000000000000000"),
+        plaintext: hex!("0000000000000000000000000000000000000000000000000000000000000001"),
+        ciphertext: hex!("937667c4fb56ed574479ed1b27010930bd9651146223019bb827c74e28a024d6"),
+    },
+];
+
+const GLADMAN_192: &[TestVector] = &[TestVector {
+    key: &hex!("2b7e151628aed2a6abf7158809cf4f3c762e7160f38b4da5"),
+    plaintext: hex!("3243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c8"),
+    ciphertext: hex!("5d7101727bb25781bf6715b0e6955282b9610e23a43c2eb062699f0ebf5887b2"),
+}];
+
+const ECB_VK_192: &[TestVector] = &[
+    TestVector {
+        key: &hex!("800000000000000000000000000000000000000000000000"),
+        plaintext: hex!("0000000000000000000000000000000000000000000000000000000000000000"),
+        ciphertext: hex!("af2df17e790c8eb31b3d9d35486357292bb266fa9962a1d3ef533c47759f7548"),
+    },
+    TestVector {
+        key: &hex!("400000000000000000000000000000000000000000000000"),
+        plaintext: hex!("0000000000000000000000000000000000000000000000000000000000000000"),
+        ciphertext: hex!("7a686d8aeac29583a876aea0a6b77337c289d6b61a2c47c364e8cecb77d9a3fc"),
+    },
+    TestVector {
+        key: &hex!("000000000000000000000000000000000000000000000001"),
+        plaintext: hex!("0000000000000000000000000000000000000000000000000000000000000000"),
+        ciphertext: hex!("44ec9f59b8b6e3b6d53c3ce7b37803a5a1dbbeb4b25d8aac68c2ed73798be273"),
+    },
+];
+
+const ECB_VT_192: &[TestVector] = &[
+    TestVector {
+        key: &hex!("000000000000000000000000000000000000000000000000"),
+        plaintext: hex!("8000000000000000000000000000000000000000000000000000000000000000"),
+        ciphertext: hex!("44b1262ffb313cae84f00112746266fc95264718b0533d22ca8c5022abff359c"),
+    },
+    TestVector {
+        key: &hex!("000000000000000000000000000000000000000000000000"),
+        plaintext: hex!("4000000000000000000000000000000000000000000000000000000000000000"),
+        ciphertext: hex!("f9511a5cda9fa6beea177b29a7158cbfdc52b9260e23d1de844eb20e2620da75"),
+    },
+    TestVector {
+        key: &hex!("000000000000000000000000000000000000000000000000"),
+        plaintext: hex!("0000000000000000000000000000000000000000000000000000000000000001"),
+        ciphertext: hex!("084f30731a376a3a75478dc30e080862e353dc29dc381326706e59dc1e03512a"),
+    },
+];
+
+const GLADMAN_256: &[TestVector] = &[TestVector {
+    key: &hex!("2b7e151628aed2a6abf7158809cf4f3c762e7160f38b4da56a784d9045190cfe"),
+    plaintext: hex!("3243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c8"),
+    ciphertext: hex!("a49406115dfb30a40418aafa4869b7c6a886ff31602a7dd19c889dc64f7e4e7a"),
+}];
+
+const ECB_VK_256: &[TestVector] = &[
+    TestVector {
+        key: &hex!("8000000000000000000000000000000000000000000000000000000000000000"),
+        plaintext: hex!("0000000000000000000000000000000000000000000000000000000000000000"),
+        ciphertext: hex!("e62abce069837b65309be4eda2c0e149fe56c07b7082d3287f592c4a4927a277"),
+    },
+    TestVector {
+        key: &hex!("4000000000000000000000000000000000000000000000000000000000000000"),
+        plaintext: hex!("0000000000000000000000000000000000000000000000000000000000000000"),
+        ciphertext: hex!("1f00b4dd622c0b2951f25970b0ed47a65f513112daca242b5292ca314917bf94"),
+    },
+    TestVector {
+        key: &hex!("0000000000000000000000000000000000000000000000000000000000000001"),
+        plaintext: hex!("0000000000000000000000000000000000000000000000000000000000000000"),
+        ciphertext: hex!("7536b4b6490c083597f6596de8c627b1c75d0f4f9ba24de284ff575e25dda7eb"),
+    },
+];
+
+const ECB_VT_256: &[TestVector] = &[
+    TestVector {
+        key: &hex!("0000000000000000000000000000000000000000000000000000000000000000"),
+        plaintext: hex!("8000000000000000000000000000000000000000000000000000000000000000"),
+        ciphertext: hex!("159a08e46e616e6e9978502010daff922eb362e77dcaaf02eaeb7354eb8b8dba"),
+    },
+    TestVector {
+        key: &hex!("0000000000000000000000000000000000000000000000000000000000000000"),
+        plaintext: hex!("4000000000000000000000000000000000000000000000000000000000000000"),
+        ciphertext: hex!("2756ddecd7558b198962f092d7ba3eef45d9e287380aab8e852658092aa9dfa1"),
+    },
+    TestVector {
+        key: &hex!("0000000000000000000000000000000000000000000000000000000000000000"),
+        plaintext: hex!("0000000000000000000000000000000000000000000000000000000000000001"),
+        ciphertext: hex!("4e76ca69967125a9636f3554229556f6e2b2351cb4fd10b4e052afd85bebdfa8"),
+    },
+];
+
+macro_rules! rijndael256_test {
+    ($name:ident, $cipher:ty, $($vectors:expr),+) => {
+        #[test]
+        fn $name() {
+            for vector in [$($vectors),+].into_iter().flatten() {
+                let cipher = <$cipher>::new_from_slice(vector.key).unwrap();
+
+                let mut block = Array::from(vector.plaintext);
+                cipher.encrypt_block(&mut block);
+                assert_eq!(block, Array::from(vector.ciphertext));
+
+                cipher.decrypt_block(&mut block);
+                assert_eq!(block, Array::from(vector.plaintext));
+
+                let mut blocks = [Array::from(vector.plaintext); 5];
+                cipher.encrypt_blocks(&mut blocks);
+                assert!(blocks.iter().all(|b| b == &Array::from(vector.ciphertext)));
+                cipher.decrypt_blocks(&mut blocks);
+                assert!(blocks.iter().all(|b| b == &Array::from(vector.plaintext)));
+            }
+        }
+    };
+}
+
+rijndael256_test!(
+    rijndael256_128,
+    Rijndael256_128,
+    GLADMAN_128,
+    ECB_VK_128,
+    ECB_VT_128
+);
+rijndael256_test!(
+    rijndael256_192,
+    Rijndael256_192,
+    GLADMAN_192,
+    ECB_VK_192,
+    ECB_VT_192
+);
+rijndael256_test!(
+    rijndael256_256,
+    Rijndael256_256,
+    GLADMAN_256,
+    ECB_VK_256,
+    ECB_VT_256
+);
@@ -1,7 +1,7 @@
 #![feature(test)]
 extern crate test;
 
-use blowfish::Blowfish;
+use blowfish::{Blowfish, BlowfishLE};
 use cipher::{block_decryptor_bench, block_encryptor_bench};
 
 block_encryptor_bench!(
@@ -14,3 +14,14 @@ block_decryptor_bench!(
     blowfish_decrypt_block,
     blowfish_decrypt_blocks,
 );
+
+block_encryptor_bench!(
+    Key: BlowfishLE,
+    blowfish_le_encrypt_block,
+    blowfish_le_encrypt_blocks,
+);
+block_decryptor_bench!(
+    Key: BlowfishLE,
+    blowfish_le_decrypt_block,
+    blowfish_le_decrypt_blocks,
+);
@@ -0,0 +1,157 @@
+//! bcrypt expensive key schedule, hash core and `bcrypt_pbkdf` hash function.
+
+use crate::Blowfish;
+use byteorder::{BE, ByteOrder, LE};
+use core::fmt;
+
+#[cfg(feature = "zeroize")]
+use cipher::zeroize::Zeroize;
+
+/// Maximum number of key bytes used by bcrypt.
+const MAX_KEY_LEN: usize = 72;
+
+/// Plaintext encrypted by bcrypt: "OrpheanBeholderScryDoubt".
+const CTEXT: &[u8; 24] = b"OrpheanBeholderScryDoubt";
+
+/// Plaintext encrypted by `bcrypt_hash`: "OxychromaticBlowfishSwatDynamite".
+const HASH_CTEXT: &[u8; 32] = b"OxychromaticBlowfishSwatDynamite";
+
+/// Version of the bcrypt algorithm, which determines how passwords are
+/// converted to keys.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum BcryptVersion {
+    /// `$2a$`: the key is the NUL-terminated password, but its length is
+    /// stored in 8 bits as OpenBSD does for compatibility. Passwords of 255
+    /// bytes or longer thus wrap around, e.g. only the first 4 bytes of a
+    /// 259-byte password are used.
+    V2a,
+    /// `$2b$`: the key is the NUL-terminated password truncated to 72 bytes.
+    V2b,
+}
+
+/// Run the bcrypt expensive key schedule (EksBlowfishSetup) with
+/// `2^cost` iterations.
+///
+/// `cost` must be in the range `4..=31` and `key` must be 1 to 72 bytes long.
+pub fn eks_blowfish_setup(
+    cost: u32,
+    salt: &[u8; 16],
+    key: &[u8],
+) -> Result<Blowfish<BE>, BcryptError> {
+    if !(4..=31).contains(&cost) {
+        return Err(BcryptError::InvalidCost);
+    }
+    if key.is_empty() || key.len() > MAX_KEY_LEN {
+        return Err(BcryptError::InvalidKeyLength);
+    }
+
+    let mut state = Blowfish::init_state();
+    state.salted_expand_key(salt, key);
+    for _ in 0..1u64 << cost {
+        state.expand_key(key);
+        state.expand_key(salt);
+    }
+    Ok(state)
+}
+
+/// Compute the raw 24-byte bcrypt output for `password`.
+///
+/// As with C's `crypt(3)`, `password` ends at its first NUL byte, if any.
+///
+/// Note that the standard bcrypt hash string only encodes the first 23
+/// bytes of the output.
+pub fn bcrypt_raw(
+    cost: u32,
+    salt: &[u8; 16],
+    password: &[u8],
+    version: BcryptVersion,
+) -> Result<[u8; 24], BcryptError> {
+    let password = until_nul(password);
+    let mut key = [0u8; MAX_KEY_LEN];
+    let key_len = match version {
+        BcryptVersion::V2a => match (password.len() + 1) as u8 {
+            // The key stream repeats the first byte of the password
+            0 => 1,
+            len => usize::from(len).min(MAX_KEY_LEN),
+        },
+        BcryptVersion::V2b => (password.len() + 1).min(MAX_KEY_LEN),
+    };
+    for (i, k) in key[..key_len].iter_mut().enumerate() {
+        *k = password.get(i).copied().unwrap_or(0);
+    }
+
+    let state = eks_blowfish_setup(cost, salt, &key[..key_len]);
+    #[cfg(feature = "zeroize")]
+    key.zeroize();
+    let state = state?;
+
+    let mut cdata = [0u32; 6];
+    BE::read_u32_into(CTEXT, &mut cdata);
+    encrypt_64(&state, &mut cdata);
+
+    let mut out = [0u8; 24];
+    BE::write_u32_into(&cdata, &mut out);
+    Ok(out)
+}
+
+fn until_nul(password: &[u8]) -> &[u8] {
+    match password.iter().position(|&b| b == 0) {
+        Some(n) => &password[..n],
+        None => password,
+    }
+}
+
+/// The `bcrypt_hash` function used by `bcrypt_pbkdf`, e.g. for OpenSSH
+/// private keys.
+///
+/// `sha2pass` and `sha2salt` are the SHA-512 digests of the password and
+/// of the salt with the block counter, respectively. Note that unlike
+/// [`bcrypt_raw`] the output words are stored in little-endian byte order.
+pub fn bcrypt_hash(sha2pass: &[u8; 64], sha2salt: &[u8; 64]) -> [u8; 32] {
+    let mut state = Blowfish::init_state();
+    state.salted_expand_key(sha2salt, sha2pass);
+    for _ in 0..64 {
+        state.expand_key(sha2salt);
+        state.expand_key(sha2pass);
+    }
+
+    let mut cdata = [0u32; 8];
+    BE::read_u32_into(HASH_CTEXT, &mut cdata);
+    encrypt_64(&state, &mut cdata);
+
+    let mut out = [0u8; 32];
+    LE::write_u32_into(&cdata, &mut out);
+    out
+}
+
+/// Encrypt `cdata` 64 times in ECB mode.
+fn encrypt_64(state: &Blowfish<BE>, cdata: &mut [u32]) {
+    for _ in 0..64 {
+        for lr in cdata.chunks_exact_mut(2) {
+            let [l, r] = state.encrypt([lr[0], lr[1]]);
+            lr.copy_from_slice(&[l, r]);
+        }
+    }
+}
+
+/// The error type returned by the bcrypt functions.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[non_exhaustive]
+pub enum BcryptError {
+    /// The cost is not in the range `4..=31`.
+    InvalidCost,
+    /// The key is empty or longer than 72 bytes.
+    InvalidKeyLength,
+}
+
+impl fmt::Display for BcryptError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(match self {
+            Self::InvalidCost => "invalid bcrypt cost",
+            Self::InvalidKeyLength => "invalid bcrypt key length",
+        })
+    }
+}
+
+impl core::error::Error for BcryptError {}
@@ -8,6 +8,13 @@
 //!
 //! USE AT YOUR OWN RISK!
 //!
+//! # bcrypt
+//! Enabling the `bcrypt` feature provides the expensive key schedule
+//! (`eks_blowfish_setup`) and the raw hash function (`bcrypt_raw`) of the
+//! bcrypt password hashing algorithm, as well as the `bcrypt_hash` function
+//! used by `bcrypt_pbkdf`. Encoding and parsing of hash strings is
+//! left to higher-level crates.
+//!
 //! [Blowfish]: https://en.wikipedia.org/wiki/Blowfish_(cipher)
 
 #![no_std]
@@ -25,8 +32,8 @@ use byteorder::{BE, ByteOrder, LE};
 use cipher::{
     AlgorithmName, Block, BlockCipherDecBackend, BlockCipherDecClosure, BlockCipherDecrypt,
     BlockCipherEncBackend, BlockCipherEncClosure, BlockCipherEncrypt, BlockSizeUser, InOut,
-    InvalidLength, Key, KeyInit, KeySizeUser, ParBlocksSizeUser,
-    consts::{U1, U8, U56},
+    InvalidLength, Key, KeyInit, KeySizeUser, ParBlocks, ParBlocksSizeUser,
+    consts::{U4, U8, U56},
 };
 use core::fmt;
 use core::marker::PhantomData;
@@ -34,8 +41,13 @@ use core::marker::PhantomData;
 #[cfg(feature = "zeroize")]
 use cipher::zeroize::{Zeroize, ZeroizeOnDrop};
 
+#[cfg(feature = "bcrypt")]
+mod bcrypt;
 mod consts;
 
+#[cfg(feature = "bcrypt")]
+pub use crate::bcrypt::{BcryptError, BcryptVersion, bcrypt_hash, bcrypt_raw, eks_blowfish_setup};
+
 /// Blowfish variant which uses Little Endian byte order read/writes.s.
 pub type BlowfishLE = Blowfish<LE>;
 
@@ -120,6 +132,36 @@ impl<T: ByteOrder> Blowfish<T> {
         r ^= self.p[0];
         [r, l]
     }
+
+    /// Encrypt `N` independent blocks with interleaved rounds.
+    fn encrypt_par<const N: usize>(&self, mut blocks: [[u32; 2]; N]) -> [[u32; 2]; N] {
+        for i in 0..8 {
+            for [l, r] in blocks.iter_mut() {
+                *l ^= self.p[2 * i];
+                *r ^= self.round_function(*l);
+            }
+            for [l, r] in blocks.iter_mut() {
+                *r ^= self.p[2 * i + 1];
+                *l ^= self.round_function(*r);
+            }
+        }
+        blocks.map(|[l, r]| [r ^ self.p[17], l ^ self.p[16]])
+    }
+
+    /// Decrypt `N` independent blocks with interleaved rounds.
+    fn decrypt_par<const N: usize>(&self, mut blocks: [[u32; 2]; N]) -> [[u32; 2]; N] {
+        for i in (1..9).rev() {
+            for [l, r] in blocks.iter_mut() {
+                *l ^= self.p[2 * i + 1];
+                *r ^= self.round_function(*l);
+            }
+            for [l, r] in blocks.iter_mut() {
+                *r ^= self.p[2 * i];
+                *l ^= self.round_function(*r);
+            }
+        }
+        blocks.map(|[l, r]| [r ^ self.p[0], l ^ self.p[1]])
+    }
 }
 
 impl<T: ByteOrder> KeySizeUser for Blowfish<T> {
@@ -146,7 +188,7 @@ impl<T: ByteOrder> BlockSizeUser for Blowfish<T> {
 }
 
 impl<T: ByteOrder> ParBlocksSizeUser for Blowfish<T> {
-    type ParBlocksSize = U1;
+    type ParBlocksSize = U4;
 }
 
 impl<T: ByteOrder> BlockCipherEncrypt for Blowfish<T> {
@@ -164,6 +206,18 @@ impl<T: ByteOrder> BlockCipherEncBackend for Blowfish<T> {
         b = self.encrypt(b);
         T::write_u32_into(&b, block.get_out());
     }
+
+    #[inline]
+    fn encrypt_par_blocks(&self, mut blocks: InOut<'_, '_, ParBlocks<Self>>) {
+        let mut b = [[0u32; 2]; 4];
+        for (b, block) in b.iter_mut().zip(blocks.get_in()) {
+            T::read_u32_into(block, b);
+        }
+        b = self.encrypt_par(b);
+        for (b, block) in b.iter().zip(blocks.get_out()) {
+            T::write_u32_into(b, block);
+        }
+    }
 }
 
 impl<T: ByteOrder> BlockCipherDecrypt for Blowfish<T> {
@@ -181,6 +235,18 @@ impl<T: ByteOrder> BlockCipherDecBackend for Blowfish<T> {
         b = self.decrypt(b);
         T::write_u32_into(&b, block.get_out());
     }
+
+    #[inline]
+    fn decrypt_par_blocks(&self, mut blocks: InOut<'_, '_, ParBlocks<Self>>) {
+        let mut b = [[0u32; 2]; 4];
+        for (b, block) in b.iter_mut().zip(blocks.get_in()) {
+            T::read_u32_into(block, b);
+        }
+        b = self.decrypt_par(b);
+        for (b, block) in b.iter().zip(blocks.get_out()) {
+            T::write_u32_into(b, block);
+        }
+    }
 }
 
 impl fmt::Debug for Blowfish<BE> {
@@ -0,0 +1,243 @@
+//! `$2b$` and short-password `$2a$` test vectors were generated using
+//! libxcrypt's `crypt(3)`. The `$2a$` 8-bit length wraparound follows
+//! OpenBSD's `bcrypt.c`; its vector is the one passlib uses to detect
+//! OpenBSD's wraparound bug.
+//!
+//! `bcrypt_hash` vectors are the first blocks of OpenBSD's `bcrypt_pbkdf`
+//! regression tests, computed with a reference implementation which
+//! reproduces the full `bcrypt_pbkdf` outputs.
+#![cfg(feature = "bcrypt")]
+
+use blowfish::{BcryptError, BcryptVersion, bcrypt_hash, bcrypt_raw, eks_blowfish_setup};
+use hex_literal::hex;
+
+/// Decode bcrypt's base-64 variant, ignoring trailing bits.
+fn decode_b64(s: &str) -> Vec<u8> {
+    const ALPHABET: &[u8] = b"./ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+    let mut out = Vec::new();
+    let (mut acc, mut bits) = (0u32, 0);
+    for c in s.bytes() {
+        let digit = ALPHABET.iter().position(|&a| a == c).unwrap() as u32;
+        acc = (acc << 6) | digit;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+        }
+    }
+    out
+}
+
+/// Check `password` against a hash string of the form `$2x$CC$<salt><hash>`.
+fn check(password: &[u8], hash: &str) {
+    let version = match &hash[..4] {
+        "$2a$" => BcryptVersion::V2a,
+        "$2b$" => BcryptVersion::V2b,
+        _ => panic!("unsupported version: {hash}"),
+    };
+    let cost = hash[4..6].parse().unwrap();
+    let salt = decode_b64(&hash[7..29]).try_into().unwrap();
+    let expected = decode_b64(&hash[29..]);
+
+    let out = bcrypt_raw(cost, &salt, password, version).unwrap();
+    assert_eq!(out[..23], expected[..], "{hash}");
+}
+
+#[test]
+fn vectors() {
+    let long = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+    let vectors: &[(&[u8], &str)] = &[
+        (
+            b"",
+            "$2a$06$DCq7YPn5Rq63x1Lad4cll.TV4S6ytwfsfvkgY8jIucDrjc8deX1s.",
+        ),
+        (
+            b"abc",
+            "$2a$04$R1lJ2gkNaoPGdafE.H.16.RnQirGeK4ikGMfWpa9z7GteDPNpXAYi",
+        ),
+        (
+            b"U*U",
+            "$2b$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW",
+        ),
+        (
+            b"U*U*",
+            "$2b$05$CCCCCCCCCCCCCCCCCCCCC.VGOzA784oUp/Z0DY336zx7pLYAy0lwK",
+        ),
+        (
+            b"\xff\xa3345",
+            "$2b$05$/OK.fbVrR/bpIqNJ5ianF.nRht2l/HRhr6zmCp9vYUvvsqynflf9e",
+        ),
+        (
+            long,
+            "$2b$05$abcdefghijklmnopqrstuu5s2v8.iXieOjg/.AySBTTZIIVFJeBui",
+        ),
+        (
+            &[b'a'; 259],
+            "$2b$04$......................UaUp2CqHXn14N7RprrzoDsNv91ahi36",
+        ),
+    ];
+    for (password, hash) in vectors {
+        check(password, hash);
+    }
+
+    // Everything after the first 72 bytes is ignored
+    let mut longer = long.to_vec();
+    longer.extend_from_slice(b"chars after 72 are ignored");
+    check(
+        &longer,
+        "$2b$05$abcdefghijklmnopqrstuu5s2v8.iXieOjg/.AySBTTZIIVFJeBui",
+    );
+
+    // The password ends at its first NUL byte, as it does for `crypt(3)`
+    check(
+        b"abc\0junk",
+        "$2a$04$R1lJ2gkNaoPGdafE.H.16.RnQirGeK4ikGMfWpa9z7GteDPNpXAYi",
+    );
+    let mut nul_long = long.to_vec();
+    nul_long[3] = 0;
+    check(
+        &nul_long,
+        "$2b$05$abcdefghijklmnopqrstuu6avZLLsXUvbRjCl/feqnTtiaY7ESntq",
+    );
+}
+
+/// A 255-byte password, 256 bytes with its NUL terminator. OpenBSD's
+/// `$2a$` truncated the key length to 8 bits, so it wraps to 0 here.
+#[test]
+fn version_2a_wraparound_vectors() {
+    let password = &b"0123456789".repeat(26)[..255];
+    // OpenBSD `bcrypt.c` before the `$2b$` fix
+    check(
+        password,
+        "$2a$04$R1lJ2gkNaoPGdafE.H.16.nVyh2niHsGJhayOHLMiXlI45o8/DU.6",
+    );
+    // libxcrypt, which uses the first 72 bytes for both versions
+    check(
+        password,
+        "$2b$04$R1lJ2gkNaoPGdafE.H.16.1MKHPvmKwryeulRe225LKProWYwt9Oi",
+    );
+}
+
+#[test]
+fn version_2a_wraparound() {
+    let salt = *b"0123456789abcdef";
+    let hash = |password: &[u8], version| bcrypt_raw(4, &salt, password, version).unwrap();
+    let raw = |key: &[u8]| {
+        let mut out = [0u32; 6];
+        let state = eks_blowfish_setup(4, &salt, key).unwrap();
+        let mut ctext = [0u32; 6];
+        for (c, w) in ctext.iter_mut().zip(b"OrpheanBeholderScryDoubt".chunks(4)) {
+            *c = u32::from_be_bytes(w.try_into().unwrap());
+        }
+        for (o, lr) in out.chunks_mut(2).zip(ctext.chunks(2)) {
+            let mut lr = [lr[0], lr[1]];
+            for _ in 0..64 {
+                lr = state.bc_encrypt(lr);
+            }
+            o.copy_from_slice(&lr);
+        }
+        out.iter()
+            .flat_map(|w| w.to_be_bytes())
+            .collect::<Vec<u8>>()
+    };
+
+    // No NUL bytes, which would end the password
+    let password: Vec<u8> = (1..=255u8).cycle().take(300).collect();
+
+    // Short passwords are identical for both versions
+    for len in [0, 1, 71, 72, 73, 254] {
+        let p = &password[..len];
+        assert_eq!(hash(p, BcryptVersion::V2a), hash(p, BcryptVersion::V2b));
+    }
+
+    // Length 255 wraps to 0: the key stream repeats the first byte
+    assert_eq!(
+        hash(&password[..255], BcryptVersion::V2a)[..],
+        raw(&password[..1])
+    );
+    // Length 259 wraps to 4: the first 4 bytes are used without a NUL
+    assert_eq!(
+        hash(&password[..259], BcryptVersion::V2a)[..],
+        raw(&password[..4])
+    );
+    assert_ne!(
+        hash(&password[..259], BcryptVersion::V2a),
+        hash(&password[..259], BcryptVersion::V2b)
+    );
+    // Length 300 wraps to 45: the first 45 bytes are used without a NUL
+    assert_eq!(
+        hash(&password[..300], BcryptVersion::V2a)[..],
+        raw(&password[..45])
+    );
+
+    // `$2b$` uses the first 72 bytes regardless of the length
+    for len in [72, 255, 259, 300] {
+        assert_eq!(
+            hash(&password[..len], BcryptVersion::V2b)[..],
+            raw(&password[..72])
+        );
+    }
+}
+
+#[test]
+fn invalid_params() {
+    let salt = [0u8; 16];
+    for cost in [0, 3, 32] {
+        let res = bcrypt_raw(cost, &salt, b"password", BcryptVersion::V2b);
+        assert_eq!(res, Err(BcryptError::InvalidCost));
+    }
+    assert_eq!(
+        eks_blowfish_setup(4, &salt, b"").err(),
+        Some(BcryptError::InvalidKeyLength)
+    );
+    assert_eq!(
+        eks_blowfish_setup(4, &salt, &[1; 73]).err(),
+        Some(BcryptError::InvalidKeyLength)
+    );
+}
+
+#[test]
+fn pbkdf_hash() {
+    // `(SHA-512(password), SHA-512(salt || 00000001), bcrypt_hash output)`
+    let vectors: &[([u8; 64], [u8; 64], [u8; 32])] = &[
+        // password = "password", salt = "salt"
+        (
+            hex!(
+                "b109f3bbbc244eb82441917ed06d618b9008dd09b3befd1b5e07394c706a8bb9"
+                "80b1d7785e5976ec049b46df5f1326af5a2ea6d103fd07c95385ffab0cacbc86"
+            ),
+            hex!(
+                "8eaba3ea8781df3b2edcf49d094deece81ba5eb244098729de30cfcb1b3cc556"
+                "014006b5ee4db9b9ca6abd0b97625c17719116f5c423cae8e81dbab075a840bd"
+            ),
+            hex!("7af41469e52860da49debfaa6a4e12a2cb857b907b2bb20d2902a9ccd424adb1"),
+        ),
+        // password = "\0", salt = "salt"
+        (
+            hex!(
+                "b8244d028981d693af7b456af8efa4cad63d282e19ff14942c246e50d9351d22"
+                "704a802a71c3580b6370de4ceb293c324a8423342557d4e5c38438f0e36910ee"
+            ),
+            hex!(
+                "8eaba3ea8781df3b2edcf49d094deece81ba5eb244098729de30cfcb1b3cc556"
+                "014006b5ee4db9b9ca6abd0b97625c17719116f5c423cae8e81dbab075a840bd"
+            ),
+            hex!("5ce225b71d87be6a505c6ff4333b6ca1cde350d4ba511998c07fecf9317026c1"),
+        ),
+        // password = "pass\0word", salt = "sa\0lt"
+        (
+            hex!(
+                "3247e8ab90f9ad5751f6af067d842b096955904591795f5d6f9390bed2689cc4"
+                "4936281572415deed0619f2b8843e51b1b58abc607fda275132861f08cee18fc"
+            ),
+            hex!(
+                "a1b68f544ad6c84b049b9dd861d691ba49779f2e24749f1cc3f61dac4e247d77"
+                "f1edc204dad308b30f9197371fccc16fbd22b41e127407424386505728705dec"
+            ),
+            hex!("91ee202118910b63a5e80b67c501422fc8000bdf0d4f27ea95ef6d84a0528d4d"),
+        ),
+    ];
+    for (sha2pass, sha2salt, expected) in vectors {
+        assert_eq!(&bcrypt_hash(sha2pass, sha2salt), expected);
+    }
+}
@@ -0,0 +1,398 @@
+//! Portable bitsliced constant-time implementation processing 8 blocks at once.
+//!
+//! Each half of the Camellia state is stored as 8 slices, one per bit of a
+//! byte: bit `8 * p + j` of slice `b` holds bit `b` of byte `p` of block `j`.
+//! All S-boxes of a round are evaluated at once using a Boolean circuit: `s1`
+//! is the inversion in GF(2^8) (computed in the tower field GF(((2^2)^2)^2))
+//! surrounded by affine transforms, and `s2`, `s3` and `s4` are obtained by
+//! rotating slices of the respective bytes. The P-function and FL-functions
+//! reduce to rotations of 32-bit halves of the slices.
+
+use cipher::{
+    array::Array,
+    consts::{U8, U16},
+    inout::{InOut, InOutBuf},
+};
+
+/// Number of blocks processed in parallel.
+const LANES: usize = 8;
+
+/// 8 Camellia blocks.
+pub(crate) type Blocks = Array<Array<u8, U16>, U8>;
+
+/// Bitsliced 8-byte half of the state of 8 blocks.
+type Slices = [u64; 8];
+
+/// Bytes using `s2` in the F-function.
+const S2_BYTES: u64 = 0x0000_00ff_0000_ff00;
+/// Bytes using `s3` in the F-function.
+const S3_BYTES: u64 = 0x0000_ff00_00ff_0000;
+/// Bytes using `s4` in the F-function.
+const S4_BYTES: u64 = 0x00ff_0000_ff00_0000;
+
+type Gf4 = [u64; 2];
+type Gf16 = [u64; 4];
+
+/// Multiplication in GF(2^2) with the normal basis `(1, w)`, `w^2 = w + 1`.
+#[inline(always)]
+fn gf4_mul(a: Gf4, b: Gf4) -> Gf4 {
+    let p = a[0] & b[0];
+    let t = a[1] & b[1];
+    [p ^ t, ((a[0] ^ a[1]) & (b[0] ^ b[1])) ^ p]
+}
+
+/// Squaring (and inversion) in GF(2^2).
+#[inline(always)]
+fn gf4_sq(a: Gf4) -> Gf4 {
+    [a[0] ^ a[1], a[1]]
+}
+
+/// Multiplication by `w` in GF(2^2).
+#[inline(always)]
+fn gf4_mul_w(a: Gf4) -> Gf4 {
+    [a[1], a[0] ^ a[1]]
+}
+
+#[inline(always)]
+fn gf4_add(a: Gf4, b: Gf4) -> Gf4 {
+    [a[0] ^ b[0], a[1] ^ b[1]]
+}
+
+/// Multiplication in GF(2^4) = GF(2^2)\[z\] / (z^2 + z + w).
+#[inline(always)]
+fn gf16_mul(a: Gf16, b: Gf16) -> Gf16 {
+    let (a0, a1) = ([a[0], a[1]], [a[2], a[3]]);
+    let (b0, b1) = ([b[0], b[1]], [b[2], b[3]]);
+    let p = gf4_mul(a0, b0);
+    let t = gf4_mul(a1, b1);
+    let c0 = gf4_add(p, gf4_mul_w(t));
+    let c1 = gf4_add(gf4_mul(gf4_add(a0, a1), gf4_add(b0, b1)), p);
+    [c0[0], c0[1], c1[0], c1[1]]
+}
+
+/// Inversion in GF(2^4).
+#[inline(always)]
+fn gf16_inv(a: Gf16) -> Gf16 {
+    let (a0, a1) = ([a[0], a[1]], [a[2], a[3]]);
+    let s = gf4_add(a0, a1);
+    let n = gf4_add(gf4_mul(a0, s), gf4_mul_w(gf4_sq(a1)));
+    let n_inv = gf4_sq(n);
+    let c0 = gf4_mul(s, n_inv);
+    let c1 = gf4_mul(a1, n_inv);
+    [c0[0], c0[1], c1[0], c1[1]]
+}
+
+/// `v * a^2` in GF(2^4), where `v = wz + 1` defines the extension to GF(2^8).
+#[inline(always)]
+fn gf16_sq_mul_v(a: Gf16) -> Gf16 {
+    [a[0] ^ a[1] ^ a[2] ^ a[3], a[1] ^ a[3], a[1], a[0]]
+}
+
+/// Inversion in GF(2^8) = GF(2^4)\[y\] / (y^2 + y + v) (with `0^-1 = 0`).
+#[inline(always)]
+fn gf256_inv(a: Slices) -> Slices {
+    let a0 = [a[0], a[1], a[2], a[3]];
+    let a1 = [a[4], a[5], a[6], a[7]];
+    let s: Gf16 = core::array::from_fn(|i| a0[i] ^ a1[i]);
+    let p = gf16_mul(a0, s);
+    let q = gf16_sq_mul_v(a1);
+    let n_inv = gf16_inv(core::array::from_fn(|i| p[i] ^ q[i]));
+    let c0 = gf16_mul(s, n_inv);
+    let c1 = gf16_mul(a1, n_inv);
+    [c0[0], c0[1], c0[2], c0[3], c1[0], c1[1], c1[2], c1[3]]
+}
+
+/// S-box `s1` applied to every byte.
+///
+/// `s1(x) = A2(inv(A1(x)))`, where `A1` maps the input into the tower field
+/// representation and `A2` maps the inverse back.
+#[inline(always)]
+fn s1(x: Slices) -> Slices {
+    // A1
+    let u = [
+        x[4],
+        !(x[0] ^ x[1] ^ x[4]),
+        !(x[2] ^ x[3] ^ x[5]),
+        x[5],
+        !x[2],
+        x[0] ^ x[3] ^ x[6],
+        x[0] ^ x[3] ^ x[4] ^ x[5] ^ x[7],
+        !(x[1] ^ x[2] ^ x[5]),
+    ];
+    let v = gf256_inv(u);
+    // A2
+    [
+        v[0] ^ v[2] ^ v[5] ^ v[6],
+        !v[2],
+        !(v[0] ^ v[3] ^ v[4] ^ v[5]),
+        !(v[0] ^ v[3]),
+        v[1] ^ v[2] ^ v[6],
+        !(v[2] ^ v[5]),
+        !v[0],
+        v[0] ^ v[3] ^ v[4] ^ v[6] ^ v[7],
+    ]
+}
+
+/// Replace bits of bytes selected by `mask` with bits of `y`.
+#[inline(always)]
+fn select(x: u64, y: u64, mask: u64) -> u64 {
+    x ^ ((x ^ y) & mask)
+}
+
+/// Expand every bit of every byte of `k` (in big endian order) into a full
+/// byte of the respective slice, i.e. bitslice `k` for all blocks.
+#[inline(always)]
+fn splat(k: u64) -> Slices {
+    let k = k.swap_bytes();
+    core::array::from_fn(|b| ((k >> b) & 0x0101_0101_0101_0101) * 0xff)
+}
+
+#[inline(always)]
+fn xor(a: &mut Slices, b: Slices) {
+    for (a, b) in a.iter_mut().zip(b) {
+        *a ^= b;
+    }
+}
+
+#[inline(always)]
+fn halves(x: u64) -> (u32, u32) {
+    (x as u32, (x >> 32) as u32)
+}
+
+#[inline(always)]
+fn join(l: u32, r: u32) -> u64 {
+    u64::from(l) | (u64::from(r) << 32)
+}
+
+/// Rotate bytes of a 4-byte half such that byte `i` receives byte `i + k`.
+#[inline(always)]
+fn rot(x: u32, k: u32) -> u32 {
+    x.rotate_right(8 * k)
+}
+
+/// F-function.
+#[inline(always)]
+fn f(mut x: Slices, k: u64) -> Slices {
+    xor(&mut x, splat(k));
+
+    // s4(x) = s1(x <<< 1)
+    let x = core::array::from_fn(|b| select(x[b], x[(b + 7) % 8], S4_BYTES));
+    let y = s1(x);
+    // s2(x) = s1(x) <<< 1, s3(x) = s1(x) >>> 1
+    let y: Slices = core::array::from_fn(|b| {
+        let t = select(y[b], y[(b + 7) % 8], S2_BYTES);
+        select(t, y[(b + 1) % 8], S3_BYTES)
+    });
+
+    // P-function
+    y.map(|y| {
+        let (mut l, mut r) = halves(y);
+        l ^= rot(r, 1);
+        r ^= rot(l, 2);
+        l ^= rot(r, 3);
+        r ^= rot(l, 3);
+        join(r, l)
+    })
+}
+
+/// Rotate the 32-bit halves in `x` left by 1 bit.
+#[inline(always)]
+fn rotl1(x: [u32; 8]) -> [u32; 8] {
+    core::array::from_fn(|b| if b == 0 { rot(x[7], 1) } else { x[b - 1] })
+}
+
+/// FL-function.
+#[inline(always)]
+fn fl(x: Slices, k: u64) -> Slices {
+    let k = splat(k);
+    let (mut x1, mut x2) = ([0u32; 8], [0u32; 8]);
+    let (mut k1, mut k2) = ([0u32; 8], [0u32; 8]);
+    for b in 0..8 {
+        (x1[b], x2[b]) = halves(x[b]);
+        (k1[b], k2[b]) = halves(k[b]);
+    }
+
+    let t = rotl1(core::array::from_fn(|b| x1[b] & k1[b]));
+    for b in 0..8 {
+        x2[b] ^= t[b];
+        x1[b] ^= x2[b] | k2[b];
+    }
+
+    core::array::from_fn(|b| join(x1[b], x2[b]))
+}
+
+/// FLINV-function.
+#[inline(always)]
+fn flinv(y: Slices, k: u64) -> Slices {
+    let k = splat(k);
+    let (mut y1, mut y2) = ([0u32; 8], [0u32; 8]);
+    let (mut k1, mut k2) = ([0u32; 8], [0u32; 8]);
+    for b in 0..8 {
+        (y1[b], y2[b]) = halves(y[b]);
+        (k1[b], k2[b]) = halves(k[b]);
+    }
+
+    for b in 0..8 {
+        y1[b] ^= y2[b] | k2[b];
+    }
+    let t = rotl1(core::array::from_fn(|b| y1[b] & k1[b]));
+    for b in 0..8 {
+        y2[b] ^= t[b];
+    }
+
+    core::array::from_fn(|b| join(y1[b], y2[b]))
+}
+
+/// Transpose 8x8 bit matrix stored in `x` with one row per byte.
+#[inline(always)]
+fn transpose8(mut x: u64) -> u64 {
+    let t = (x ^ (x >> 7)) & 0x00aa_00aa_00aa_00aa;
+    x ^= t ^ (t << 7);
+    let t = (x ^ (x >> 14)) & 0x0000_cccc_0000_cccc;
+    x ^= t ^ (t << 14);
+    let t = (x ^ (x >> 28)) & 0x0000_0000_f0f0_f0f0;
+    x ^= t ^ (t << 28);
+    x
+}
+
+/// Bitslice one half of the blocks.
+#[inline(always)]
+fn load_half(blocks: &Blocks, offset: usize) -> Slices {
+    let mut res = [0u64; 8];
+    for p in 0..8 {
+        let row = u64::from_le_bytes(core::array::from_fn(|j| blocks[j][offset + p]));
+        let t = transpose8(row).to_le_bytes();
+        for b in 0..8 {
+            res[b] |= u64::from(t[b]) << (8 * p);
+        }
+    }
+    res
+}
+
+/// Inverse of [`load_half`].
+#[inline(always)]
+fn store_half(x: Slices, blocks: &mut Blocks, offset: usize) {
+    for p in 0..8 {
+        let t = u64::from_le_bytes(core::array::from_fn(|b| (x[b] >> (8 * p)) as u8));
+        let row = transpose8(t).to_le_bytes();
+        for j in 0..LANES {
+            blocks[j][offset + p] = row[j];
+        }
+    }
+}
+
+#[inline]
+pub(crate) fn encrypt_par<const RK: usize>(k: &[u64; RK], mut blocks: InOut<'_, '_, Blocks>) {
+    let mut d1 = load_half(blocks.get_in(), 0);
+    let mut d2 = load_half(blocks.get_in(), 8);
+
+    xor(&mut d1, splat(k[0]));
+    xor(&mut d2, splat(k[1]));
+
+    for i in (2..RK - 2).step_by(2) {
+        if i % 8 == 0 {
+            d1 = fl(d1, k[i]);
+            d2 = flinv(d2, k[i + 1]);
+
+            continue;
+        }
+        xor(&mut d2, f(d1, k[i]));
+        xor(&mut d1, f(d2, k[i + 1]));
+    }
+
+    xor(&mut d2, splat(k[RK - 2]));
+    xor(&mut d1, splat(k[RK - 1]));
+
+    let out = blocks.get_out();
+    store_half(d2, out, 0);
+    store_half(d1, out, 8);
+}
+
+#[inline]
+pub(crate) fn decrypt_par<const RK: usize>(k: &[u64; RK], mut blocks: InOut<'_, '_, Blocks>) {
+    let mut d1 = load_half(blocks.get_in(), 0);
+    let mut d2 = load_half(blocks.get_in(), 8);
+
+    xor(&mut d2, splat(k[RK - 1]));
+    xor(&mut d1, splat(k[RK - 2]));
+
+    for i in (2..RK - 2).rev().step_by(2) {
+        if (i - 1) % 8 == 0 {
+            d1 = fl(d1, k[i]);
+            d2 = flinv(d2, k[i - 1]);
+
+            continue;
+        }
+        xor(&mut d2, f(d1, k[i]));
+        xor(&mut d1, f(d2, k[i - 1]));
+    }
+
+    xor(&mut d1, splat(k[1]));
+    xor(&mut d2, splat(k[0]));
+
+    let out = blocks.get_out();
+    store_half(d2, out, 0);
+    store_half(d1, out, 8);
+}
+
+/// Encrypt fewer than 8 blocks. The remaining lanes are filled with zeros.
+#[inline]
+pub(crate) fn encrypt_tail<const RK: usize>(
+    k: &[u64; RK],
+    mut blocks: InOutBuf<'_, '_, Array<u8, U16>>,
+) {
+    let n = blocks.len();
+    let mut buf = Blocks::default();
+    buf[..n].copy_from_slice(blocks.get_in());
+    encrypt_par(k, (&mut buf).into());
+    blocks.get_out().copy_from_slice(&buf[..n]);
+}
+
+/// Decrypt fewer than 8 blocks. The remaining lanes are filled with zeros.
+#[inline]
+pub(crate) fn decrypt_tail<const RK: usize>(
+    k: &[u64; RK],
+    mut blocks: InOutBuf<'_, '_, Array<u8, U16>>,
+) {
+    let n = blocks.len();
+    let mut buf = Blocks::default();
+    buf[..n].copy_from_slice(blocks.get_in());
+    decrypt_par(k, (&mut buf).into());
+    blocks.get_out().copy_from_slice(&buf[..n]);
+}
+
+/// Encrypt a single block.
+#[inline]
+pub(crate) fn encrypt_block<const RK: usize>(
+    k: &[u64; RK],
+    mut block: InOut<'_, '_, Array<u8, U16>>,
+) {
+    let mut buf = [block.clone_in()];
+    encrypt_tail(k, (&mut buf[..]).into());
+    *block.get_out() = buf[0];
+}
+
+/// Decrypt a single block.
+#[inline]
+pub(crate) fn decrypt_block<const RK: usize>(
+    k: &[u64; RK],
+    mut block: InOut<'_, '_, Array<u8, U16>>,
+) {
+    let mut buf = [block.clone_in()];
+    decrypt_tail(k, (&mut buf[..]).into());
+    *block.get_out() = buf[0];
+}
+
+/// F-function applied to a single value, used by the key schedule.
+pub(crate) fn f_u64(x: u64, k: u64) -> u64 {
+    let y = f(splat(x), k);
+    // take the bits of the first block
+    let t = u64::from_le_bytes(core::array::from_fn(|b| {
+        let mut byte = 0;
+        for p in 0..8 {
+            byte |= (((y[b] >> (8 * p)) & 1) as u8) << p;
+        }
+        byte
+    }));
+    u64::from_be_bytes(transpose8(t).to_le_bytes())
+}
@@ -0,0 +1,345 @@
+//! Byte-sliced constant-time implementation processing 16 blocks at once.
+//!
+//! Every vector holds the same byte of 16 different blocks, so the P-function
+//! and FL-functions become plain vector operations. The S-boxes are computed
+//! without lookup tables using the AES S-box: the Camellia S-box `s1` is
+//! affine equivalent to the AES S-box (both are based on the inversion in
+//! GF(2^8)), i.e. `s1(x) = A2(S_aes(A1(x)))` for affine transforms `A1` and
+//! `A2`. The affine transforms are applied using nibble-indexed byte shuffles,
+//! and `s2`, `s3` and `s4` are obtained by folding the respective bit
+//! rotations into these transforms.
+
+use cipher::{
+    array::Array,
+    consts::U16,
+    inout::{InOut, InOutBuf},
+};
+use core::ops::{BitAnd, BitOr, BitXor};
+
+#[cfg(target_arch = "aarch64")]
+mod armv8;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod x86;
+
+#[cfg(target_arch = "aarch64")]
+pub(crate) use armv8::{decrypt_par, decrypt_tail, encrypt_par, encrypt_tail, f};
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub(crate) use x86::{decrypt_par, decrypt_tail, encrypt_par, encrypt_tail, f};
+
+/// Number of blocks processed in parallel.
+const LANES: usize = 16;
+
+/// 16 Camellia blocks.
+pub(crate) type Blocks = Array<Array<u8, U16>, U16>;
+
+/// Affine transform over GF(2) represented as lookup tables for the low and
+/// the high nibble of the input byte. The constant part is stored in the
+/// low nibble table.
+type Tables = [[u8; 16]; 2];
+
+/// `A1`, applied before the AES S-box for `s1`, `s2` and `s3`.
+const PRE_S1: Tables = [
+    [
+        0x08, 0x09, 0x11, 0x10, 0xb9, 0xb8, 0xa0, 0xa1, 0xa3, 0xa2, 0xba, 0xbb, 0x12, 0x13, 0x0b,
+        0x0a,
+    ],
+    [
+        0x00, 0xa7, 0x93, 0x34, 0x61, 0xc6, 0xf2, 0x55, 0xd9, 0x7e, 0x4a, 0xed, 0xb8, 0x1f, 0x2b,
+        0x8c,
+    ],
+];
+/// `A1(x <<< 1)`, applied before the AES S-box for `s4`.
+const PRE_S4: Tables = [
+    [
+        0x08, 0x11, 0xb9, 0xa0, 0xa3, 0xba, 0x12, 0x0b, 0xaf, 0xb6, 0x1e, 0x07, 0x04, 0x1d, 0xb5,
+        0xac,
+    ],
+    [
+        0x00, 0x93, 0x61, 0xf2, 0xd9, 0x4a, 0xb8, 0x2b, 0x01, 0x92, 0x60, 0xf3, 0xd8, 0x4b, 0xb9,
+        0x2a,
+    ],
+];
+/// `A2`, applied after the AES S-box for `s1` and `s4`.
+const POST_S1: Tables = [
+    [
+        0x11, 0x82, 0x84, 0x17, 0x3e, 0xad, 0xab, 0x38, 0x71, 0xe2, 0xe4, 0x77, 0x5e, 0xcd, 0xcb,
+        0x58,
+    ],
+    [
+        0x00, 0xb8, 0xd9, 0x61, 0xa0, 0x18, 0x79, 0xc1, 0xa8, 0x10, 0x71, 0xc9, 0x08, 0xb0, 0xd1,
+        0x69,
+    ],
+];
+/// `A2(x) <<< 1`, applied after the AES S-box for `s2`.
+const POST_S2: Tables = [
+    [
+        0x22, 0x05, 0x09, 0x2e, 0x7c, 0x5b, 0x57, 0x70, 0xe2, 0xc5, 0xc9, 0xee, 0xbc, 0x9b, 0x97,
+        0xb0,
+    ],
+    [
+        0x00, 0x71, 0xb3, 0xc2, 0x41, 0x30, 0xf2, 0x83, 0x51, 0x20, 0xe2, 0x93, 0x10, 0x61, 0xa3,
+        0xd2,
+    ],
+];
+/// `A2(x) >>> 1`, applied after the AES S-box for `s3`.
+const POST_S3: Tables = [
+    [
+        0x88, 0x41, 0x42, 0x8b, 0x1f, 0xd6, 0xd5, 0x1c, 0xb8, 0x71, 0x72, 0xbb, 0x2f, 0xe6, 0xe5,
+        0x2c,
+    ],
+    [
+        0x00, 0x5c, 0xec, 0xb0, 0x50, 0x0c, 0xbc, 0xe0, 0x54, 0x08, 0xb8, 0xe4, 0x04, 0x58, 0xe8,
+        0xb4,
+    ],
+];
+
+/// Byte shuffle undoing the AES ShiftRows step.
+const INV_SHIFT_ROWS: [u8; 16] = [
+    0x00, 0x0d, 0x0a, 0x07, 0x04, 0x01, 0x0e, 0x0b, 0x08, 0x05, 0x02, 0x0f, 0x0c, 0x09, 0x06, 0x03,
+];
+
+/// Vector of `LANES` bytes.
+trait Bytes: Copy + BitAnd<Output = Self> + BitOr<Output = Self> + BitXor<Output = Self> {
+    fn splat(x: u8) -> Self;
+    fn load(bytes: &[u8; LANES]) -> Self;
+    fn store(self, bytes: &mut [u8; LANES]);
+    /// Apply an affine transform to every byte.
+    fn affine(self, tables: &Tables) -> Self;
+    /// Apply the AES S-box to every byte.
+    fn aes_sbox(self) -> Self;
+    /// Shift every byte left by 1 bit.
+    fn shl1(self) -> Self;
+    /// Shift every byte right by 7 bits.
+    fn shr7(self) -> Self;
+}
+
+#[inline(always)]
+fn sbox<V: Bytes>(x: V, pre: &Tables, post: &Tables) -> V {
+    x.affine(pre).aes_sbox().affine(post)
+}
+
+#[inline(always)]
+fn splat_key<V: Bytes>(k: u64) -> [V; 8] {
+    k.to_be_bytes().map(V::splat)
+}
+
+#[inline(always)]
+fn xor<V: Bytes>(a: &mut [V; 8], b: [V; 8]) {
+    for (a, b) in a.iter_mut().zip(b) {
+        *a = *a ^ b;
+    }
+}
+
+/// F-function.
+#[inline(always)]
+fn f_bytes<V: Bytes>(mut x: [V; 8], k: u64) -> [V; 8] {
+    xor(&mut x, splat_key(k));
+
+    let y1 = sbox(x[0], &PRE_S1, &POST_S1);
+    let y2 = sbox(x[1], &PRE_S1, &POST_S2);
+    let y3 = sbox(x[2], &PRE_S1, &POST_S3);
+    let y4 = sbox(x[3], &PRE_S4, &POST_S1);
+    let y5 = sbox(x[4], &PRE_S1, &POST_S2);
+    let y6 = sbox(x[5], &PRE_S1, &POST_S3);
+    let y7 = sbox(x[6], &PRE_S4, &POST_S1);
+    let y8 = sbox(x[7], &PRE_S1, &POST_S1);
+
+    // P-function
+    let t1 = y1 ^ y8;
+    let t2 = y2 ^ y5;
+    let t3 = y3 ^ y6;
+    let t4 = y4 ^ y7;
+    let z5 = t1 ^ y2 ^ y6 ^ y7;
+    let z6 = t2 ^ y3 ^ y7 ^ y8;
+    let z7 = t3 ^ y4 ^ y5 ^ y8;
+    let z8 = t4 ^ y1 ^ y5 ^ y6;
+    [
+        t1 ^ t3 ^ t4,
+        t1 ^ t2 ^ t4,
+        t1 ^ t2 ^ t3,
+        t2 ^ t3 ^ t4,
+        z5,
+        z6,
+        z7,
+        z8,
+    ]
+}
+
+/// Rotate 32-bit big endian words left by 1 bit.
+#[inline(always)]
+fn rotl1<V: Bytes>(x: [V; 4]) -> [V; 4] {
+    core::array::from_fn(|i| x[i].shl1() | x[(i + 1) % 4].shr7())
+}
+
+/// FL-function.
+#[inline(always)]
+fn fl<V: Bytes>(x: [V; 8], k: u64) -> [V; 8] {
+    let k = splat_key::<V>(k);
+    let mut x1: [V; 4] = core::array::from_fn(|i| x[i]);
+    let mut x2: [V; 4] = core::array::from_fn(|i| x[4 + i]);
+
+    let t = rotl1(core::array::from_fn(|i| x1[i] & k[i]));
+    for i in 0..4 {
+        x2[i] = x2[i] ^ t[i];
+        x1[i] = x1[i] ^ (x2[i] | k[4 + i]);
+    }
+
+    core::array::from_fn(|i| if i < 4 { x1[i] } else { x2[i - 4] })
+}
+
+/// FLINV-function.
+#[inline(always)]
+fn flinv<V: Bytes>(y: [V; 8], k: u64) -> [V; 8] {
+    let k = splat_key::<V>(k);
+    let mut y1: [V; 4] = core::array::from_fn(|i| y[i]);
+    let mut y2: [V; 4] = core::array::from_fn(|i| y[4 + i]);
+
+    for i in 0..4 {
+        y1[i] = y1[i] ^ (y2[i] | k[4 + i]);
+    }
+    let t = rotl1(core::array::from_fn(|i| y1[i] & k[i]));
+    for i in 0..4 {
+        y2[i] = y2[i] ^ t[i];
+    }
+
+    core::array::from_fn(|i| if i < 4 { y1[i] } else { y2[i - 4] })
+}
+
+/// Transpose blocks into byte slices: byte `j` of block `i` goes into lane
+/// `i` of vector `j`.
+#[inline(always)]
+fn load<V: Bytes>(blocks: &Blocks) -> ([V; 8], [V; 8]) {
+    let mut t = [[0u8; LANES]; 16];
+    for (i, block) in blocks.iter().enumerate() {
+        for (j, &b) in block.iter().enumerate() {
+            t[j][i] = b;
+        }
+    }
+    (
+        core::array::from_fn(|j| V::load(&t[j])),
+        core::array::from_fn(|j| V::load(&t[8 + j])),
+    )
+}
+
+/// Inverse of [`load`] which also swaps the block halves.
+#[inline(always)]
+fn store<V: Bytes>(d1: [V; 8], d2: [V; 8], blocks: &mut Blocks) {
+    let mut t = [[0u8; LANES]; 16];
+    for j in 0..8 {
+        d2[j].store(&mut t[j]);
+        d1[j].store(&mut t[8 + j]);
+    }
+    for (i, block) in blocks.iter_mut().enumerate() {
+        for (j, b) in block.iter_mut().enumerate() {
+            *b = t[j][i];
+        }
+    }
+}
+
+#[inline(always)]
+fn encrypt<V: Bytes, const RK: usize>(k: &[u64; RK], mut blocks: InOut<'_, '_, Blocks>) {
+    let (mut d1, mut d2) = load::<V>(blocks.get_in());
+
+    xor(&mut d1, splat_key(k[0]));
+    xor(&mut d2, splat_key(k[1]));
+
+    for i in (2..RK - 2).step_by(2) {
+        if i % 8 == 0 {
+            d1 = fl(d1, k[i]);
+            d2 = flinv(d2, k[i + 1]);
+
+            continue;
+        }
+        xor(&mut d2, f_bytes(d1, k[i]));
+        xor(&mut d1, f_bytes(d2, k[i + 1]));
+    }
+
+    xor(&mut d2, splat_key(k[RK - 2]));
+    xor(&mut d1, splat_key(k[RK - 1]));
+
+    store(d1, d2, blocks.get_out());
+}
+
+#[inline(always)]
+fn decrypt<V: Bytes, const RK: usize>(k: &[u64; RK], mut blocks: InOut<'_, '_, Blocks>) {
+    let (mut d1, mut d2) = load::<V>(blocks.get_in());
+
+    xor(&mut d2, splat_key(k[RK - 1]));
+    xor(&mut d1, splat_key(k[RK - 2]));
+
+    for i in (2..RK - 2).rev().step_by(2) {
+        if (i - 1) % 8 == 0 {
+            d1 = fl(d1, k[i]);
+            d2 = flinv(d2, k[i - 1]);
+
+            continue;
+        }
+        xor(&mut d2, f_bytes(d1, k[i]));
+        xor(&mut d1, f_bytes(d2, k[i - 1]));
+    }
+
+    xor(&mut d1, splat_key(k[1]));
+    xor(&mut d2, splat_key(k[0]));
+
+    store(d1, d2, blocks.get_out());
+}
+
+/// F-function applied to a single value, used by the key schedule.
+#[inline(always)]
+fn f_u64<V: Bytes>(x: u64, k: u64) -> u64 {
+    let y = f_bytes(splat_key::<V>(x), k);
+    let mut res = [0u8; 8];
+    for (r, y) in res.iter_mut().zip(y) {
+        let mut t = [0u8; LANES];
+        y.store(&mut t);
+        *r = t[0];
+    }
+    u64::from_be_bytes(res)
+}
+
+/// Process `blocks` one by one using `f`.
+#[inline(always)]
+fn for_each_block(
+    mut blocks: InOut<'_, '_, Blocks>,
+    mut f: impl FnMut(InOut<'_, '_, Array<u8, U16>>),
+) {
+    for i in 0..LANES {
+        f(blocks.get(i));
+    }
+}
+
+/// Encrypt a single block.
+#[inline]
+pub(crate) fn encrypt_block<const RK: usize>(
+    k: &[u64; RK],
+    mut block: InOut<'_, '_, Array<u8, U16>>,
+) {
+    let mut buf = [block.clone_in()];
+    encrypt_tail(k, (&mut buf[..]).into());
+    *block.get_out() = buf[0];
+}
+
+/// Decrypt a single block.
+#[inline]
+pub(crate) fn decrypt_block<const RK: usize>(
+    k: &[u64; RK],
+    mut block: InOut<'_, '_, Array<u8, U16>>,
+) {
+    let mut buf = [block.clone_in()];
+    decrypt_tail(k, (&mut buf[..]).into());
+    *block.get_out() = buf[0];
+}
+
+/// Process fewer than 16 blocks by padding them with zero blocks to a full
+/// batch, which is then processed using `f`.
+#[inline(always)]
+fn with_padding(
+    mut blocks: InOutBuf<'_, '_, Array<u8, U16>>,
+    f: impl FnOnce(InOut<'_, '_, Blocks>),
+) {
+    let n = blocks.len();
+    let mut buf = Blocks::default();
+    buf[..n].copy_from_slice(blocks.get_in());
+    f((&mut buf).into());
+    blocks.get_out().copy_from_slice(&buf[..n]);
+}
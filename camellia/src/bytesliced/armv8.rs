@@ -0,0 +1,184 @@
+//! ARMv8 Cryptography Extensions backend. Support for the AES instructions is
+//! detected at runtime.
+#![allow(unsafe_code, unsafe_op_in_unsafe_fn)]
+
+use super::{
+    Blocks, Bytes, INV_SHIFT_ROWS, LANES, Tables, decrypt, encrypt, f_u64, for_each_block,
+    with_padding,
+};
+use crate::table;
+use cipher::{
+    array::Array,
+    consts::U16,
+    inout::{InOut, InOutBuf},
+};
+use core::arch::aarch64::*;
+use core::ops::{BitAnd, BitOr, BitXor};
+
+cpufeatures::new!(aes_cpuid, "aes");
+
+/// Encrypt 16 blocks.
+#[inline]
+pub(crate) fn encrypt_par<const RK: usize>(k: &[u64; RK], blocks: InOut<'_, '_, Blocks>) {
+    if aes_cpuid::get() {
+        // SAFETY: AES availability was checked at runtime
+        unsafe { encrypt_armv8(k, blocks) }
+    } else {
+        for_each_block(blocks, |block| table::encrypt(k, block));
+    }
+}
+
+/// Decrypt 16 blocks.
+#[inline]
+pub(crate) fn decrypt_par<const RK: usize>(k: &[u64; RK], blocks: InOut<'_, '_, Blocks>) {
+    if aes_cpuid::get() {
+        // SAFETY: AES availability was checked at runtime
+        unsafe { decrypt_armv8(k, blocks) }
+    } else {
+        for_each_block(blocks, |block| table::decrypt(k, block));
+    }
+}
+
+/// Encrypt fewer than 16 blocks.
+#[inline]
+pub(crate) fn encrypt_tail<const RK: usize>(
+    k: &[u64; RK],
+    blocks: InOutBuf<'_, '_, Array<u8, U16>>,
+) {
+    if aes_cpuid::get() {
+        // SAFETY: AES availability was checked at runtime
+        with_padding(blocks, |blocks| unsafe { encrypt_armv8(k, blocks) });
+    } else {
+        blocks
+            .into_iter()
+            .for_each(|block| table::encrypt(k, block));
+    }
+}
+
+/// Decrypt fewer than 16 blocks.
+#[inline]
+pub(crate) fn decrypt_tail<const RK: usize>(
+    k: &[u64; RK],
+    blocks: InOutBuf<'_, '_, Array<u8, U16>>,
+) {
+    if aes_cpuid::get() {
+        // SAFETY: AES availability was checked at runtime
+        with_padding(blocks, |blocks| unsafe { decrypt_armv8(k, blocks) });
+    } else {
+        blocks
+            .into_iter()
+            .for_each(|block| table::decrypt(k, block));
+    }
+}
+
+/// F-function.
+pub(crate) fn f(x: u64, k: u64) -> u64 {
+    if aes_cpuid::get() {
+        // SAFETY: AES availability was checked at runtime
+        unsafe { f_armv8(x, k) }
+    } else {
+        table::f(x, k)
+    }
+}
+
+#[target_feature(enable = "aes")]
+unsafe fn encrypt_armv8<const RK: usize>(k: &[u64; RK], blocks: InOut<'_, '_, Blocks>) {
+    encrypt::<NeonBytes, RK>(k, blocks)
+}
+
+#[target_feature(enable = "aes")]
+unsafe fn decrypt_armv8<const RK: usize>(k: &[u64; RK], blocks: InOut<'_, '_, Blocks>) {
+    decrypt::<NeonBytes, RK>(k, blocks)
+}
+
+#[target_feature(enable = "aes")]
+unsafe fn f_armv8(x: u64, k: u64) -> u64 {
+    f_u64::<NeonBytes>(x, k)
+}
+
+/// `vaeseq_u8` can not be inlined into functions without the `aes` target
+/// feature, while this wrapper is inlined into the functions above.
+#[target_feature(enable = "aes")]
+#[inline]
+unsafe fn aese(data: uint8x16_t, key: uint8x16_t) -> uint8x16_t {
+    vaeseq_u8(data, key)
+}
+
+// The AES intrinsics below are only safe to call when the AES instructions
+// are available, which is ensured by the functions above.
+
+#[derive(Copy, Clone)]
+struct NeonBytes(uint8x16_t);
+
+impl BitAnd for NeonBytes {
+    type Output = Self;
+
+    #[inline(always)]
+    fn bitand(self, rhs: Self) -> Self {
+        Self(unsafe { vandq_u8(self.0, rhs.0) })
+    }
+}
+
+impl BitOr for NeonBytes {
+    type Output = Self;
+
+    #[inline(always)]
+    fn bitor(self, rhs: Self) -> Self {
+        Self(unsafe { vorrq_u8(self.0, rhs.0) })
+    }
+}
+
+impl BitXor for NeonBytes {
+    type Output = Self;
+
+    #[inline(always)]
+    fn bitxor(self, rhs: Self) -> Self {
+        Self(unsafe { veorq_u8(self.0, rhs.0) })
+    }
+}
+
+impl Bytes for NeonBytes {
+    #[inline(always)]
+    fn splat(x: u8) -> Self {
+        Self(unsafe { vdupq_n_u8(x) })
+    }
+
+    #[inline(always)]
+    fn load(bytes: &[u8; LANES]) -> Self {
+        Self(unsafe { vld1q_u8(bytes.as_ptr()) })
+    }
+
+    #[inline(always)]
+    fn store(self, bytes: &mut [u8; LANES]) {
+        unsafe { vst1q_u8(bytes.as_mut_ptr(), self.0) }
+    }
+
+    #[inline(always)]
+    fn affine(self, tables: &Tables) -> Self {
+        let [lo, hi] = tables.map(|t| Self::load(&t).0);
+        unsafe {
+            let x_lo = vandq_u8(self.0, vdupq_n_u8(0x0f));
+            let x_hi = vshrq_n_u8::<4>(self.0);
+            Self(veorq_u8(vqtbl1q_u8(lo, x_lo), vqtbl1q_u8(hi, x_hi)))
+        }
+    }
+
+    #[inline(always)]
+    fn aes_sbox(self) -> Self {
+        let inv_shift_rows = Self::load(&INV_SHIFT_ROWS).0;
+        unsafe {
+            let x = vqtbl1q_u8(self.0, inv_shift_rows);
+            Self(aese(x, vdupq_n_u8(0)))
+        }
+    }
+
+    #[inline(always)]
+    fn shl1(self) -> Self {
+        Self(unsafe { vshlq_n_u8::<1>(self.0) })
+    }
+
+    #[inline(always)]
+    fn shr7(self) -> Self {
+        Self(unsafe { vshrq_n_u8::<7>(self.0) })
+    }
+}
@@ -0,0 +1,183 @@
+//! AES-NI backend. Support for AES-NI and SSSE3 is detected at runtime.
+#![allow(unsafe_code)]
+
+use super::{
+    Blocks, Bytes, INV_SHIFT_ROWS, LANES, Tables, decrypt, encrypt, f_u64, for_each_block,
+    with_padding,
+};
+use crate::table;
+use cipher::{
+    array::Array,
+    consts::U16,
+    inout::{InOut, InOutBuf},
+};
+use core::ops::{BitAnd, BitOr, BitXor};
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::*;
+
+cpufeatures::new!(aes_ssse3_cpuid, "aes", "ssse3");
+
+/// Encrypt 16 blocks.
+#[inline]
+pub(crate) fn encrypt_par<const RK: usize>(k: &[u64; RK], blocks: InOut<'_, '_, Blocks>) {
+    if aes_ssse3_cpuid::get() {
+        // SAFETY: AES-NI and SSSE3 availability was checked at runtime
+        unsafe { encrypt_aesni(k, blocks) }
+    } else {
+        for_each_block(blocks, |block| table::encrypt(k, block));
+    }
+}
+
+/// Decrypt 16 blocks.
+#[inline]
+pub(crate) fn decrypt_par<const RK: usize>(k: &[u64; RK], blocks: InOut<'_, '_, Blocks>) {
+    if aes_ssse3_cpuid::get() {
+        // SAFETY: AES-NI and SSSE3 availability was checked at runtime
+        unsafe { decrypt_aesni(k, blocks) }
+    } else {
+        for_each_block(blocks, |block| table::decrypt(k, block));
+    }
+}
+
+/// Encrypt fewer than 16 blocks.
+#[inline]
+pub(crate) fn encrypt_tail<const RK: usize>(
+    k: &[u64; RK],
+    blocks: InOutBuf<'_, '_, Array<u8, U16>>,
+) {
+    if aes_ssse3_cpuid::get() {
+        // SAFETY: AES-NI and SSSE3 availability was checked at runtime
+        with_padding(blocks, |blocks| unsafe { encrypt_aesni(k, blocks) });
+    } else {
+        blocks
+            .into_iter()
+            .for_each(|block| table::encrypt(k, block));
+    }
+}
+
+/// Decrypt fewer than 16 blocks.
+#[inline]
+pub(crate) fn decrypt_tail<const RK: usize>(
+    k: &[u64; RK],
+    blocks: InOutBuf<'_, '_, Array<u8, U16>>,
+) {
+    if aes_ssse3_cpuid::get() {
+        // SAFETY: AES-NI and SSSE3 availability was checked at runtime
+        with_padding(blocks, |blocks| unsafe { decrypt_aesni(k, blocks) });
+    } else {
+        blocks
+            .into_iter()
+            .for_each(|block| table::decrypt(k, block));
+    }
+}
+
+/// F-function.
+pub(crate) fn f(x: u64, k: u64) -> u64 {
+    if aes_ssse3_cpuid::get() {
+        // SAFETY: AES-NI and SSSE3 availability was checked at runtime
+        unsafe { f_aesni(x, k) }
+    } else {
+        table::f(x, k)
+    }
+}
+
+#[target_feature(enable = "aes,ssse3")]
+unsafe fn encrypt_aesni<const RK: usize>(k: &[u64; RK], blocks: InOut<'_, '_, Blocks>) {
+    encrypt::<AesNiBytes, RK>(k, blocks)
+}
+
+#[target_feature(enable = "aes,ssse3")]
+unsafe fn decrypt_aesni<const RK: usize>(k: &[u64; RK], blocks: InOut<'_, '_, Blocks>) {
+    decrypt::<AesNiBytes, RK>(k, blocks)
+}
+
+#[target_feature(enable = "aes,ssse3")]
+unsafe fn f_aesni(x: u64, k: u64) -> u64 {
+    f_u64::<AesNiBytes>(x, k)
+}
+
+// The intrinsics below are only safe to call when AES-NI and SSSE3 are
+// available, which is ensured by the functions above.
+
+#[derive(Copy, Clone)]
+struct AesNiBytes(__m128i);
+
+impl BitAnd for AesNiBytes {
+    type Output = Self;
+
+    #[inline(always)]
+    fn bitand(self, rhs: Self) -> Self {
+        Self(unsafe { _mm_and_si128(self.0, rhs.0) })
+    }
+}
+
+impl BitOr for AesNiBytes {
+    type Output = Self;
+
+    #[inline(always)]
+    fn bitor(self, rhs: Self) -> Self {
+        Self(unsafe { _mm_or_si128(self.0, rhs.0) })
+    }
+}
+
+impl BitXor for AesNiBytes {
+    type Output = Self;
+
+    #[inline(always)]
+    fn bitxor(self, rhs: Self) -> Self {
+        Self(unsafe { _mm_xor_si128(self.0, rhs.0) })
+    }
+}
+
+impl Bytes for AesNiBytes {
+    #[inline(always)]
+    fn splat(x: u8) -> Self {
+        Self(unsafe { _mm_set1_epi8(x as i8) })
+    }
+
+    #[inline(always)]
+    fn load(bytes: &[u8; LANES]) -> Self {
+        Self(unsafe { _mm_loadu_si128(bytes.as_ptr().cast()) })
+    }
+
+    #[inline(always)]
+    fn store(self, bytes: &mut [u8; LANES]) {
+        unsafe { _mm_storeu_si128(bytes.as_mut_ptr().cast(), self.0) }
+    }
+
+    #[inline(always)]
+    fn affine(self, tables: &Tables) -> Self {
+        let [lo, hi] = tables.map(|t| Self::load(&t).0);
+        unsafe {
+            let mask = _mm_set1_epi8(0x0f);
+            let x_lo = _mm_and_si128(self.0, mask);
+            let x_hi = _mm_and_si128(_mm_srli_epi16(self.0, 4), mask);
+            Self(_mm_xor_si128(
+                _mm_shuffle_epi8(lo, x_lo),
+                _mm_shuffle_epi8(hi, x_hi),
+            ))
+        }
+    }
+
+    #[inline(always)]
+    fn aes_sbox(self) -> Self {
+        let inv_shift_rows = Self::load(&INV_SHIFT_ROWS).0;
+        unsafe {
+            let x = _mm_shuffle_epi8(self.0, inv_shift_rows);
+            Self(_mm_aesenclast_si128(x, _mm_setzero_si128()))
+        }
+    }
+
+    #[inline(always)]
+    fn shl1(self) -> Self {
+        Self(unsafe { _mm_add_epi8(self.0, self.0) })
+    }
+
+    #[inline(always)]
+    fn shr7(self) -> Self {
+        Self(unsafe { _mm_and_si128(_mm_srli_epi16(self.0, 7), _mm_set1_epi8(1)) })
+    }
+}
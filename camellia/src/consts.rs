@@ -15,6 +15,7 @@ pub const SIGMAS: [u64; 6] = [
 ];
 
 /// Sboxes of Camellia defined in RFC 3713.
+#[cfg(not(camellia_backend = "soft_ct"))]
 pub const SBOXES: [[u8; 256]; 4] = [
     // SBOX1
     [
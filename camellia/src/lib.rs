@@ -8,6 +8,34 @@
 //!
 //! USE AT YOUR OWN RISK!
 //!
+//! # Backends
+//! By default Camellia is implemented using lookup tables, which makes the
+//! execution time of encryption and decryption depend on the processed data
+//! and the key and may leak them through cache-timing side channels.
+//!
+//! On x86, x86-64 and AArch64 targets, CPU support for the AES instructions
+//! (AES-NI and SSSE3 on x86) is detected at runtime. If they are available,
+//! blocks are processed in batches of 16 in constant time using the AES S-box,
+//! as are the F-function evaluations of the key schedule. Individual blocks and
+//! the tail of a message shorter than 16 blocks are padded to a full batch, so
+//! they cost as much as 16 blocks. Lookup tables are only used if the AES
+//! instructions are not available.
+//!
+//! # Configuration Flags
+//!
+//! You can modify crate using the `camellia_backend` configuration flag.
+//! It accepts the following values
+//!
+//! - `soft_ct`: use portable bitsliced backend, which processes 8 blocks in
+//!   parallel and does not use any secret-dependent memory accesses or branches.
+//!   Individual blocks and the key schedule are computed in constant time as
+//!   well, but at the cost of processing a full batch of 8 blocks. This backend
+//!   replaces both the lookup tables and the AES-based backend.
+//!
+//! The flag can be enabled using `RUSTFLAGS` environment variable
+//! (e.g. `RUSTFLAGS='--cfg camellia_backend="soft_ct"'`) or by modifying
+//! `.cargo/config`.
+//!
 //! # Examples
 //! ```
 //! use camellia::cipher::{Array, BlockCipherDecrypt, BlockCipherEncrypt, KeyInit};
@@ -52,21 +80,28 @@ use cipher::{
         BlockCipherDecBackend, BlockCipherDecClosure, BlockCipherDecrypt, BlockCipherEncBackend,
         BlockCipherEncClosure, BlockCipherEncrypt,
     },
-    consts::{U1, U16, U24, U32},
+    consts::{U16, U24, U32},
     inout::InOut,
 };
 
 #[cfg(feature = "zeroize")]
 use cipher::zeroize::{Zeroize, ZeroizeOnDrop};
 
+#[cfg(camellia_backend = "soft_ct")]
+mod bitsliced;
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"),
+    not(camellia_backend = "soft_ct")
+))]
+mod bytesliced;
 mod camellia128;
 mod camellia192;
 mod camellia256;
 mod consts;
+#[cfg(not(camellia_backend = "soft_ct"))]
+mod table;
 mod utils;
 
-use utils::{f, fl, flinv};
-
 /// Generic implementation of the Camellia block cipher.
 ///
 /// This type can be initialized only with 3 combinations of `KeySize` and `RK`:
@@ -94,8 +129,27 @@ impl<KeySize: ArraySize, const RK: usize> BlockSizeUser for Camellia<KeySize, RK
     type BlockSize = U16;
 }
 
+#[cfg(camellia_backend = "soft_ct")]
+impl<KeySize: ArraySize, const RK: usize> ParBlocksSizeUser for Camellia<KeySize, RK> {
+    type ParBlocksSize = cipher::consts::U8;
+}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"),
+    not(camellia_backend = "soft_ct")
+))]
 impl<KeySize: ArraySize, const RK: usize> ParBlocksSizeUser for Camellia<KeySize, RK> {
-    type ParBlocksSize = U1;
+    type ParBlocksSize = U16;
+}
+
+#[cfg(not(any(
+    target_arch = "x86",
+    target_arch = "x86_64",
+    target_arch = "aarch64",
+    camellia_backend = "soft_ct",
+)))]
+impl<KeySize: ArraySize, const RK: usize> ParBlocksSizeUser for Camellia<KeySize, RK> {
+    type ParBlocksSize = cipher::consts::U1;
 }
 
 impl<KeySize: ArraySize, const RK: usize> BlockCipherEncrypt for Camellia<KeySize, RK> {
@@ -107,31 +161,51 @@ impl<KeySize: ArraySize, const RK: usize> BlockCipherEncrypt for Camellia<KeySiz
 
 impl<KeySize: ArraySize, const RK: usize> BlockCipherEncBackend for Camellia<KeySize, RK> {
     #[inline]
-    fn encrypt_block(&self, mut block: InOut<'_, '_, Block<Self>>) {
-        let b = block.get_in();
-        let mut d1 = u64::from_be_bytes(b[0..8].try_into().unwrap());
-        let mut d2 = u64::from_be_bytes(b[8..16].try_into().unwrap());
-
-        d1 ^= self.k[0];
-        d2 ^= self.k[1];
-
-        for i in (2..RK - 2).step_by(2) {
-            if i % 8 == 0 {
-                d1 = fl(d1, self.k[i]);
-                d2 = flinv(d2, self.k[i + 1]);
-
-                continue;
-            }
-            d2 ^= f(d1, self.k[i]);
-            d1 ^= f(d2, self.k[i + 1]);
-        }
-
-        d2 ^= self.k[RK - 2];
-        d1 ^= self.k[RK - 1];
-
-        let (b1, b2) = block.get_out().split_at_mut(8);
-        b1.copy_from_slice(&d2.to_be_bytes());
-        b2.copy_from_slice(&d1.to_be_bytes());
+    fn encrypt_block(&self, block: InOut<'_, '_, Block<Self>>) {
+        #[cfg(camellia_backend = "soft_ct")]
+        bitsliced::encrypt_block(&self.k, block);
+        #[cfg(all(
+            any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"),
+            not(camellia_backend = "soft_ct")
+        ))]
+        bytesliced::encrypt_block(&self.k, block);
+        #[cfg(not(any(
+            target_arch = "x86",
+            target_arch = "x86_64",
+            target_arch = "aarch64",
+            camellia_backend = "soft_ct",
+        )))]
+        table::encrypt(&self.k, block);
+    }
+
+    #[cfg(camellia_backend = "soft_ct")]
+    #[inline]
+    fn encrypt_par_blocks(&self, blocks: InOut<'_, '_, cipher::ParBlocks<Self>>) {
+        bitsliced::encrypt_par(&self.k, blocks);
+    }
+
+    #[cfg(camellia_backend = "soft_ct")]
+    #[inline]
+    fn encrypt_tail_blocks(&self, blocks: cipher::inout::InOutBuf<'_, '_, Block<Self>>) {
+        bitsliced::encrypt_tail(&self.k, blocks);
+    }
+
+    #[cfg(all(
+        any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"),
+        not(camellia_backend = "soft_ct")
+    ))]
+    #[inline]
+    fn encrypt_par_blocks(&self, blocks: InOut<'_, '_, cipher::ParBlocks<Self>>) {
+        bytesliced::encrypt_par(&self.k, blocks);
+    }
+
+    #[cfg(all(
+        any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"),
+        not(camellia_backend = "soft_ct")
+    ))]
+    #[inline]
+    fn encrypt_tail_blocks(&self, blocks: cipher::inout::InOutBuf<'_, '_, Block<Self>>) {
+        bytesliced::encrypt_tail(&self.k, blocks);
     }
 }
 
@@ -144,31 +218,51 @@ impl<KeySize: ArraySize, const RK: usize> BlockCipherDecrypt for Camellia<KeySiz
 
 impl<KeySize: ArraySize, const RK: usize> BlockCipherDecBackend for Camellia<KeySize, RK> {
     #[inline]
-    fn decrypt_block(&self, mut block: InOut<'_, '_, Block<Self>>) {
-        let b = block.get_in();
-        let mut d1 = u64::from_be_bytes(b[0..8].try_into().unwrap());
-        let mut d2 = u64::from_be_bytes(b[8..16].try_into().unwrap());
-
-        d2 ^= self.k[RK - 1];
-        d1 ^= self.k[RK - 2];
-
-        for i in (2..RK - 2).rev().step_by(2) {
-            if (i - 1) % 8 == 0 {
-                d1 = fl(d1, self.k[i]);
-                d2 = flinv(d2, self.k[i - 1]);
-
-                continue;
-            }
-            d2 ^= f(d1, self.k[i]);
-            d1 ^= f(d2, self.k[i - 1]);
-        }
-
-        d1 ^= self.k[1];
-        d2 ^= self.k[0];
-
-        let (b1, b2) = block.get_out().split_at_mut(8);
-        b1.copy_from_slice(&d2.to_be_bytes());
-        b2.copy_from_slice(&d1.to_be_bytes());
+    fn decrypt_block(&self, block: InOut<'_, '_, Block<Self>>) {
+        #[cfg(camellia_backend = "soft_ct")]
+        bitsliced::decrypt_block(&self.k, block);
+        #[cfg(all(
+            any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"),
+            not(camellia_backend = "soft_ct")
+        ))]
+        bytesliced::decrypt_block(&self.k, block);
+        #[cfg(not(any(
+            target_arch = "x86",
+            target_arch = "x86_64",
+            target_arch = "aarch64",
+            camellia_backend = "soft_ct",
+        )))]
+        table::decrypt(&self.k, block);
+    }
+
+    #[cfg(camellia_backend = "soft_ct")]
+    #[inline]
+    fn decrypt_par_blocks(&self, blocks: InOut<'_, '_, cipher::ParBlocks<Self>>) {
+        bitsliced::decrypt_par(&self.k, blocks);
+    }
+
+    #[cfg(camellia_backend = "soft_ct")]
+    #[inline]
+    fn decrypt_tail_blocks(&self, blocks: cipher::inout::InOutBuf<'_, '_, Block<Self>>) {
+        bitsliced::decrypt_tail(&self.k, blocks);
+    }
+
+    #[cfg(all(
+        any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"),
+        not(camellia_backend = "soft_ct")
+    ))]
+    #[inline]
+    fn decrypt_par_blocks(&self, blocks: InOut<'_, '_, cipher::ParBlocks<Self>>) {
+        bytesliced::decrypt_par(&self.k, blocks);
+    }
+
+    #[cfg(all(
+        any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"),
+        not(camellia_backend = "soft_ct")
+    ))]
+    #[inline]
+    fn decrypt_tail_blocks(&self, blocks: cipher::inout::InOutBuf<'_, '_, Block<Self>>) {
+        bytesliced::decrypt_tail(&self.k, blocks);
     }
 }
 
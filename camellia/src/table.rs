@@ -0,0 +1,102 @@
+//! Table-based implementation.
+
+use crate::consts::SBOXES;
+use cipher::{array::Array, consts::U16, inout::InOut};
+
+/// F-function of component of Camellia defined in RFC 3713.
+pub(crate) fn f(input: u64, key: u64) -> u64 {
+    let x = (input ^ key).to_be_bytes();
+
+    let z1 = 0x0101_0100_0100_0001 * u64::from(SBOXES[0][usize::from(x[0])]);
+    let z2 = 0x0001_0101_0101_0000 * u64::from(SBOXES[1][usize::from(x[1])]);
+    let z3 = 0x0100_0101_0001_0100 * u64::from(SBOXES[2][usize::from(x[2])]);
+    let z4 = 0x0101_0001_0000_0101 * u64::from(SBOXES[3][usize::from(x[3])]);
+    let z5 = 0x0001_0101_0001_0101 * u64::from(SBOXES[1][usize::from(x[4])]);
+    let z6 = 0x0100_0101_0100_0101 * u64::from(SBOXES[2][usize::from(x[5])]);
+    let z7 = 0x0101_0001_0101_0001 * u64::from(SBOXES[3][usize::from(x[6])]);
+    let z8 = 0x0101_0100_0101_0100 * u64::from(SBOXES[0][usize::from(x[7])]);
+
+    z1 ^ z2 ^ z3 ^ z4 ^ z5 ^ z6 ^ z7 ^ z8
+}
+
+/// FL-function of component of Camellia defined in RFC 3713.
+pub(crate) fn fl(input: u64, key: u64) -> u64 {
+    let mut x1 = u32::try_from(input >> 32).unwrap();
+    let mut x2 = u32::try_from(input & 0xffff_ffff).unwrap();
+
+    let k1 = u32::try_from(key >> 32).unwrap();
+    let k2 = u32::try_from(key & 0xffff_ffff).unwrap();
+
+    x2 ^= (x1 & k1).rotate_left(1);
+    x1 ^= x2 | k2;
+
+    (u64::from(x1) << 32) | u64::from(x2)
+}
+
+/// FLINV-function of component of Camellia defined in RFC 3713.
+pub(crate) fn flinv(input: u64, key: u64) -> u64 {
+    let mut y1 = u32::try_from(input >> 32).unwrap();
+    let mut y2 = u32::try_from(input & 0xffff_ffff).unwrap();
+
+    let k1 = u32::try_from(key >> 32).unwrap();
+    let k2 = u32::try_from(key & 0xffff_ffff).unwrap();
+
+    y1 ^= y2 | k2;
+    y2 ^= (y1 & k1).rotate_left(1);
+
+    (u64::from(y1) << 32) | u64::from(y2)
+}
+
+pub(crate) fn encrypt<const RK: usize>(k: &[u64; RK], mut block: InOut<'_, '_, Array<u8, U16>>) {
+    let b = block.get_in();
+    let mut d1 = u64::from_be_bytes(b[0..8].try_into().unwrap());
+    let mut d2 = u64::from_be_bytes(b[8..16].try_into().unwrap());
+
+    d1 ^= k[0];
+    d2 ^= k[1];
+
+    for i in (2..RK - 2).step_by(2) {
+        if i % 8 == 0 {
+            d1 = fl(d1, k[i]);
+            d2 = flinv(d2, k[i + 1]);
+
+            continue;
+        }
+        d2 ^= f(d1, k[i]);
+        d1 ^= f(d2, k[i + 1]);
+    }
+
+    d2 ^= k[RK - 2];
+    d1 ^= k[RK - 1];
+
+    let (b1, b2) = block.get_out().split_at_mut(8);
+    b1.copy_from_slice(&d2.to_be_bytes());
+    b2.copy_from_slice(&d1.to_be_bytes());
+}
+
+pub(crate) fn decrypt<const RK: usize>(k: &[u64; RK], mut block: InOut<'_, '_, Array<u8, U16>>) {
+    let b = block.get_in();
+    let mut d1 = u64::from_be_bytes(b[0..8].try_into().unwrap());
+    let mut d2 = u64::from_be_bytes(b[8..16].try_into().unwrap());
+
+    d2 ^= k[RK - 1];
+    d1 ^= k[RK - 2];
+
+    for i in (2..RK - 2).rev().step_by(2) {
+        if (i - 1) % 8 == 0 {
+            d1 = fl(d1, k[i]);
+            d2 = flinv(d2, k[i - 1]);
+
+            continue;
+        }
+        d2 ^= f(d1, k[i]);
+        d1 ^= f(d2, k[i - 1]);
+    }
+
+    d1 ^= k[1];
+    d2 ^= k[0];
+
+    let (b1, b2) = block.get_out().split_at_mut(8);
+    b1.copy_from_slice(&d2.to_be_bytes());
+    b2.copy_from_slice(&d1.to_be_bytes());
+}
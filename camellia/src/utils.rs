@@ -1,58 +1,40 @@
-use crate::consts::{SBOXES, SIGMAS};
-
-/// F-function of component of Camellia defined in RFC 3713.
-pub(crate) fn f(input: u64, key: u64) -> u64 {
-    let x = (input ^ key).to_be_bytes();
-
-    let z1 = 0x0101_0100_0100_0001 * u64::from(SBOXES[0][usize::from(x[0])]);
-    let z2 = 0x0001_0101_0101_0000 * u64::from(SBOXES[1][usize::from(x[1])]);
-    let z3 = 0x0100_0101_0001_0100 * u64::from(SBOXES[2][usize::from(x[2])]);
-    let z4 = 0x0101_0001_0000_0101 * u64::from(SBOXES[3][usize::from(x[3])]);
-    let z5 = 0x0001_0101_0001_0101 * u64::from(SBOXES[1][usize::from(x[4])]);
-    let z6 = 0x0100_0101_0100_0101 * u64::from(SBOXES[2][usize::from(x[5])]);
-    let z7 = 0x0101_0001_0101_0001 * u64::from(SBOXES[3][usize::from(x[6])]);
-    let z8 = 0x0101_0100_0101_0100 * u64::from(SBOXES[0][usize::from(x[7])]);
-
-    z1 ^ z2 ^ z3 ^ z4 ^ z5 ^ z6 ^ z7 ^ z8
-}
-
-/// FL-function of component of Camellia defined in RFC 3713.
-pub(crate) fn fl(input: u64, key: u64) -> u64 {
-    let mut x1 = u32::try_from(input >> 32).unwrap();
-    let mut x2 = u32::try_from(input & 0xffff_ffff).unwrap();
-
-    let k1 = u32::try_from(key >> 32).unwrap();
-    let k2 = u32::try_from(key & 0xffff_ffff).unwrap();
-
-    x2 ^= (x1 & k1).rotate_left(1);
-    x1 ^= x2 | k2;
-
-    (u64::from(x1) << 32) | u64::from(x2)
-}
-
-/// FLINV-function of component of Camellia defined in RFC 3713.
-pub(crate) fn flinv(input: u64, key: u64) -> u64 {
-    let mut y1 = u32::try_from(input >> 32).unwrap();
-    let mut y2 = u32::try_from(input & 0xffff_ffff).unwrap();
-
-    let k1 = u32::try_from(key >> 32).unwrap();
-    let k2 = u32::try_from(key & 0xffff_ffff).unwrap();
-
-    y1 ^= y2 | k2;
-    y2 ^= (y1 & k1).rotate_left(1);
-
-    (u64::from(y1) << 32) | u64::from(y2)
+use crate::consts::SIGMAS;
+
+/// F-function used by the key schedule. Avoids secret-dependent table
+/// lookups if supported by the target CPU or if the `soft_ct` backend is
+/// selected.
+fn f_key(input: u64, key: u64) -> u64 {
+    #[cfg(camellia_backend = "soft_ct")]
+    {
+        crate::bitsliced::f_u64(input, key)
+    }
+    #[cfg(all(
+        any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"),
+        not(camellia_backend = "soft_ct"),
+    ))]
+    {
+        crate::bytesliced::f(input, key)
+    }
+    #[cfg(not(any(
+        target_arch = "x86",
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        camellia_backend = "soft_ct",
+    )))]
+    {
+        crate::table::f(input, key)
+    }
 }
 
 pub(crate) fn set_ka(kl: (u64, u64), kr: (u64, u64)) -> (u64, u64) {
     let mut d1 = kl.0 ^ kr.0;
     let mut d2 = kl.1 ^ kr.1;
-    d2 ^= f(d1, SIGMAS[0]);
-    d1 ^= f(d2, SIGMAS[1]);
+    d2 ^= f_key(d1, SIGMAS[0]);
+    d1 ^= f_key(d2, SIGMAS[1]);
     d1 ^= kl.0;
     d2 ^= kl.1;
-    d2 ^= f(d1, SIGMAS[2]);
-    d1 ^= f(d2, SIGMAS[3]);
+    d2 ^= f_key(d1, SIGMAS[2]);
+    d1 ^= f_key(d2, SIGMAS[3]);
 
     (d1, d2)
 }
@@ -60,8 +42,8 @@ pub(crate) fn set_ka(kl: (u64, u64), kr: (u64, u64)) -> (u64, u64) {
 pub(crate) fn set_kb(ka: (u64, u64), kr: (u64, u64)) -> (u64, u64) {
     let mut d1 = ka.0 ^ kr.0;
     let mut d2 = ka.1 ^ kr.1;
-    d2 ^= f(d1, SIGMAS[4]);
-    d1 ^= f(d2, SIGMAS[5]);
+    d2 ^= f_key(d1, SIGMAS[4]);
+    d1 ^= f_key(d2, SIGMAS[5]);
 
     (d1, d2)
 }
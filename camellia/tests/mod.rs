@@ -4,3 +4,39 @@
 cipher::block_cipher_test!(camellia128, camellia::Camellia128);
 cipher::block_cipher_test!(camellia192, camellia::Camellia192);
 cipher::block_cipher_test!(camellia256, camellia::Camellia256);
+
+/// Example data from RFC 3713, Appendix A.
+#[test]
+fn rfc3713() {
+    use cipher::{Array, BlockCipherDecrypt, BlockCipherEncrypt, KeyInit};
+    use hex_literal::hex;
+
+    let pt = hex!("0123456789abcdeffedcba9876543210");
+
+    let key = hex!("0123456789abcdeffedcba9876543210");
+    let ct = hex!("67673138549669730857065648eabe43");
+    let cipher = camellia::Camellia128::new(&key.into());
+    let mut block = Array::from(pt);
+    cipher.encrypt_block(&mut block);
+    assert_eq!(block, ct);
+    cipher.decrypt_block(&mut block);
+    assert_eq!(block, pt);
+
+    let key = hex!("0123456789abcdeffedcba98765432100011223344556677");
+    let ct = hex!("b4993401b3e996f84ee5cee7d79b09b9");
+    let cipher = camellia::Camellia192::new(&key.into());
+    let mut block = Array::from(pt);
+    cipher.encrypt_block(&mut block);
+    assert_eq!(block, ct);
+    cipher.decrypt_block(&mut block);
+    assert_eq!(block, pt);
+
+    let key = hex!("0123456789abcdeffedcba987654321000112233445566778899aabbccddeeff");
+    let ct = hex!("9acc237dff16d76c20ef7c919e3a7509");
+    let cipher = camellia::Camellia256::new(&key.into());
+    let mut block = Array::from(pt);
+    cipher.encrypt_block(&mut block);
+    assert_eq!(block, ct);
+    cipher.decrypt_block(&mut block);
+    assert_eq!(block, pt);
+}
@@ -0,0 +1,163 @@
+//! Bitsliced DES implementation processing 64 blocks in parallel.
+//!
+//! Blocks are transposed so that each `u64` word holds a single bit position
+//! of all 64 blocks. The S-boxes are then evaluated as Boolean circuits,
+//! which avoids secret-dependent table lookups, and the E and P permutations
+//! reduce to selecting words.
+
+use crate::utils::{fp, ip};
+use cipher::{
+    InOut,
+    array::Array,
+    consts::{U8, U64},
+};
+
+mod sboxes;
+
+/// Number of blocks processed in parallel.
+pub(crate) const BLOCKS: usize = 64;
+
+/// Expansion table, 0-based indices into the right half.
+#[rustfmt::skip]
+const E: [usize; 48] = [
+    31,  0,  1,  2,  3,  4,  3,  4,  5,  6,  7,  8,
+     7,  8,  9, 10, 11, 12, 11, 12, 13, 14, 15, 16,
+    15, 16, 17, 18, 19, 20, 19, 20, 21, 22, 23, 24,
+    23, 24, 25, 26, 27, 28, 27, 28, 29, 30, 31,  0,
+];
+
+/// Inverse of the P permutation: S-box output bit `i` is moved to `P_INV[i]`.
+#[rustfmt::skip]
+const P_INV: [usize; 32] = [
+     8, 16, 22, 30, 12, 27,  1, 17, 23, 15, 29,  5, 25, 19,  9,  0,
+     7, 13, 24,  2,  3, 28, 10, 18, 31, 11, 21,  6,  4, 26, 14, 20,
+];
+
+/// Bitsliced S-box mapping six input bit slices to four output bit slices.
+type Sbox = fn(&[u64; 6]) -> [u64; 4];
+
+const SBOXES: [Sbox; 8] = [
+    sboxes::s1,
+    sboxes::s2,
+    sboxes::s3,
+    sboxes::s4,
+    sboxes::s5,
+    sboxes::s6,
+    sboxes::s7,
+    sboxes::s8,
+];
+
+/// State of 64 blocks after the initial permutation in bitsliced form.
+///
+/// Word `i` holds bit `63 - i` of every block, i.e. words `0..32` are the
+/// left half and words `32..64` the right half.
+pub(crate) struct State([u64; BLOCKS]);
+
+impl State {
+    /// Apply the initial permutation to `blocks` and transpose them.
+    #[inline]
+    pub(crate) fn load(blocks: &[u64; BLOCKS]) -> Self {
+        let mut state = blocks.map(ip);
+        transpose(&mut state);
+        Self(state)
+    }
+
+    /// Transpose the state back and apply the final permutation.
+    #[inline]
+    pub(crate) fn store(mut self) -> [u64; BLOCKS] {
+        transpose(&mut self.0);
+        self.0.map(fp)
+    }
+
+    /// Apply 16 DES rounds using the given subkeys.
+    ///
+    /// As in the scalar implementation, the halves are swapped at the end, so
+    /// successive calls can be chained for Triple DES without applying the
+    /// final and initial permutations in between.
+    #[inline]
+    pub(crate) fn encrypt(&mut self, keys: &[u64; 16]) {
+        self.rounds(keys.iter());
+    }
+
+    /// Apply 16 DES rounds using the given subkeys in reverse order.
+    #[inline]
+    pub(crate) fn decrypt(&mut self, keys: &[u64; 16]) {
+        self.rounds(keys.iter().rev());
+    }
+
+    #[inline(always)]
+    fn rounds<'a>(&mut self, mut keys: impl Iterator<Item = &'a u64>) {
+        let (l, r) = self.0.split_at_mut(32);
+        let l: &mut [u64; 32] = l.try_into().unwrap();
+        let r: &mut [u64; 32] = r.try_into().unwrap();
+        while let (Some(k1), Some(k2)) = (keys.next(), keys.next()) {
+            round(l, r, *k1);
+            round(r, l, *k2);
+        }
+        self.0.rotate_left(32);
+    }
+}
+
+/// XOR the round function output `f(r, key)` into `l`.
+#[inline(always)]
+fn round(l: &mut [u64; 32], r: &[u64; 32], key: u64) {
+    for (i, sbox) in SBOXES.iter().enumerate() {
+        let mut x = [0u64; 6];
+        for (j, x) in x.iter_mut().enumerate() {
+            let bit = 6 * i + j;
+            // Broadcast the subkey bit to a full word mask
+            let k = 0u64.wrapping_sub((key >> (63 - bit)) & 1);
+            *x = r[E[bit]] ^ k;
+        }
+        let y = sbox(&x);
+        for (j, y) in y.into_iter().enumerate() {
+            l[P_INV[4 * i + j]] ^= y;
+        }
+    }
+}
+
+/// Encrypt or decrypt 64 blocks by applying `f` to their bitsliced state.
+#[inline]
+pub(crate) fn par_blocks(
+    blocks: InOut<'_, '_, Array<Array<u8, U8>, U64>>,
+    f: impl FnOnce(&mut State),
+) {
+    whitened_par_blocks(blocks, 0, 0, f);
+}
+
+/// Same as [`par_blocks`], but XOR `pre` into the blocks before applying `f`
+/// and `post` after it.
+#[inline]
+pub(crate) fn whitened_par_blocks(
+    mut blocks: InOut<'_, '_, Array<Array<u8, U8>, U64>>,
+    pre: u64,
+    post: u64,
+    f: impl FnOnce(&mut State),
+) {
+    let input = blocks.get_in();
+    let words = core::array::from_fn(|i| u64::from_be_bytes(input[i].0) ^ pre);
+    let mut state = State::load(&words);
+    f(&mut state);
+    let words = state.store();
+    for (block, word) in blocks.get_out().iter_mut().zip(words) {
+        block.copy_from_slice(&(word ^ post).to_be_bytes());
+    }
+}
+
+/// Transpose a 64x64 bit matrix in place.
+#[inline(always)]
+fn transpose(a: &mut [u64; 64]) {
+    let mut j = 32;
+    let mut m = 0x0000_0000_FFFF_FFFF;
+    while j != 0 {
+        let mut k = 0;
+        while k < 64 {
+            let t = (a[k] ^ (a[k + j] >> j)) & m;
+            a[k] ^= t;
+            a[k + j] ^= t << j;
+            k = (k + j + 1) & !j;
+        }
+        j >>= 1;
+        m ^= m << j;
+    }
+}
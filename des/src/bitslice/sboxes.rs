@@ -0,0 +1,818 @@
+//! Bitsliced DES S-boxes.
+//!
+//! Each function evaluates one S-box on 64 blocks at once: `x[0]` holds the
+//! most significant input bit of every block and the returned array the four
+//! output bits, most significant first.
+//!
+//! The circuits were derived from the truth tables in `consts::SBOXES` by
+//! Shannon decomposition into multiplexer trees with shared sub-functions,
+//! using the variable order that minimizes the gate count of each S-box.
+
+pub(super) fn s1(x: &[u64; 6]) -> [u64; 4] {
+    let t0 = !x[1];
+    let t1 = t0 & !x[2];
+    let t2 = x[1] | x[2];
+    let t3 = t1 ^ x[4];
+    let t4 = x[1] ^ x[2];
+    let t5 = t4 ^ x[1];
+    let t6 = t4 ^ (t5 & x[4]);
+    let t7 = t3 ^ t6;
+    let t8 = t3 ^ (t7 & x[0]);
+    let t9 = t2 ^ x[4];
+    let t10 = t0 ^ x[2];
+    let t11 = t0 | !x[2];
+    let t12 = t10 ^ t11;
+    let t13 = t10 ^ (t12 & x[4]);
+    let t14 = t9 ^ t13;
+    let t15 = t9 ^ (t14 & x[0]);
+    let t16 = t8 ^ t15;
+    let t17 = t8 ^ (t16 & x[5]);
+    let t18 = t0 ^ t4;
+    let t19 = t0 ^ (t18 & x[4]);
+    let t20 = !x[2];
+    let t21 = t20 ^ t0;
+    let t22 = t20 ^ (t21 & x[4]);
+    let t23 = t19 ^ t22;
+    let t24 = t19 ^ (t23 & x[0]);
+    let t25 = t1 ^ x[1];
+    let t26 = t1 ^ (t25 & x[4]);
+    let t27 = t6 ^ t26;
+    let t28 = t6 ^ (t27 & x[0]);
+    let t29 = t24 ^ t28;
+    let t30 = t24 ^ (t29 & x[5]);
+    let t31 = t17 ^ t30;
+    let t32 = t17 ^ (t31 & x[3]);
+    let t33 = t10 ^ t0;
+    let t34 = t10 ^ (t33 & x[4]);
+    let t35 = t11 ^ t4;
+    let t36 = t11 ^ (t35 & x[4]);
+    let t37 = t34 ^ t36;
+    let t38 = t34 ^ (t37 & x[0]);
+    let t39 = x[2] & t0;
+    let t40 = t39 ^ x[4];
+    let t41 = t11 ^ t1;
+    let t42 = t11 ^ (t41 & x[4]);
+    let t43 = t40 ^ t42;
+    let t44 = t40 ^ (t43 & x[0]);
+    let t45 = t38 ^ t44;
+    let t46 = t38 ^ (t45 & x[5]);
+    let t47 = t20 ^ x[1];
+    let t48 = t20 ^ (t47 & x[4]);
+    let t49 = x[1] & !x[2];
+    let t50 = t10 ^ t49;
+    let t51 = t10 ^ (t50 & x[4]);
+    let t52 = t48 ^ t51;
+    let t53 = t48 ^ (t52 & x[0]);
+    let t54 = x[2] & x[1];
+    let t55 = t42 ^ x[0];
+    let t56 = t53 ^ t55;
+    let t57 = t53 ^ (t56 & x[5]);
+    let t58 = t46 ^ t57;
+    let t59 = t46 ^ (t58 & x[3]);
+    let t60 = x[1] ^ (t4 & x[4]);
+    let t61 = t36 ^ t60;
+    let t62 = t36 ^ (t61 & x[0]);
+    let t63 = t4 ^ t11;
+    let t64 = t4 ^ (t63 & x[4]);
+    let t65 = t64 ^ t51;
+    let t66 = t64 ^ (t65 & x[0]);
+    let t67 = t62 ^ t66;
+    let t68 = t62 ^ (t67 & x[5]);
+    let t69 = t4 ^ t54;
+    let t70 = t4 ^ (t69 & x[4]);
+    let t71 = t70 ^ t19;
+    let t72 = t70 ^ (t71 & x[0]);
+    let t73 = x[1] ^ t11;
+    let t74 = x[1] ^ (t73 & x[4]);
+    let t75 = t51 ^ t74;
+    let t76 = t51 ^ (t75 & x[0]);
+    let t77 = t72 ^ t76;
+    let t78 = t72 ^ (t77 & x[5]);
+    let t79 = t68 ^ t78;
+    let t80 = t68 ^ (t79 & x[3]);
+    let t81 = t60 ^ t9;
+    let t82 = t60 ^ (t81 & x[0]);
+    let t83 = t54 ^ t10;
+    let t84 = t54 ^ (t83 & x[4]);
+    let t85 = t20 ^ t4;
+    let t86 = t20 ^ (t85 & x[4]);
+    let t87 = t84 ^ t86;
+    let t88 = t84 ^ (t87 & x[0]);
+    let t89 = t82 ^ t88;
+    let t90 = t82 ^ (t89 & x[5]);
+    let t91 = t0 ^ t10;
+    let t92 = t0 ^ (t91 & x[4]);
+    let t93 = t92 ^ x[0];
+    let t94 = t0 | x[2];
+    let t95 = t94 ^ t4;
+    let t96 = t94 ^ (t95 & x[4]);
+    let t97 = t4 ^ x[2];
+    let t98 = t4 ^ (t97 & x[4]);
+    let t99 = t96 ^ t98;
+    let t100 = t96 ^ (t99 & x[0]);
+    let t101 = t93 ^ t100;
+    let t102 = t93 ^ (t101 & x[5]);
+    let t103 = t90 ^ t102;
+    let t104 = t90 ^ (t103 & x[3]);
+    [t32, t59, t80, t104]
+}
+
+pub(super) fn s2(x: &[u64; 6]) -> [u64; 4] {
+    let t0 = !x[5];
+    let t1 = t0 ^ x[2];
+    let t2 = x[5] ^ x[2];
+    let t3 = t1 ^ x[0];
+    let t4 = t2 ^ x[3];
+    let t5 = x[5] | !x[2];
+    let t6 = t0 & !x[2];
+    let t7 = t5 ^ t6;
+    let t8 = t5 ^ (t7 & x[3]);
+    let t9 = t4 ^ t8;
+    let t10 = t4 ^ (t9 & x[0]);
+    let t11 = t3 ^ t10;
+    let t12 = t3 ^ (t11 & x[4]);
+    let t13 = t0 | !x[2];
+    let t14 = x[2] & x[5];
+    let t15 = t13 ^ x[3];
+    let t16 = t15 ^ t4;
+    let t17 = t15 ^ (t16 & x[0]);
+    let t18 = t14 ^ x[3];
+    let t19 = x[5] | x[2];
+    let t20 = t6 ^ x[3];
+    let t21 = t18 ^ t20;
+    let t22 = t18 ^ (t21 & x[0]);
+    let t23 = t17 ^ t22;
+    let t24 = t17 ^ (t23 & x[4]);
+    let t25 = t12 ^ t24;
+    let t26 = t12 ^ (t25 & x[1]);
+    let t27 = t0 | x[2];
+    let t28 = x[5] & !x[2];
+    let t29 = t27 ^ x[3];
+    let t30 = t29 ^ x[0];
+    let t31 = t28 | x[3];
+    let t32 = t31 ^ x[0];
+    let t33 = t30 ^ t32;
+    let t34 = t30 ^ (t33 & x[4]);
+    let t35 = x[2] & t0;
+    let t36 = t19 ^ t35;
+    let t37 = t19 ^ (t36 & x[3]);
+    let t38 = t37 ^ x[0];
+    let t39 = t6 ^ t1;
+    let t40 = t6 ^ (t39 & x[3]);
+    let t41 = x[5] ^ t13;
+    let t42 = x[5] ^ (t41 & x[3]);
+    let t43 = t40 ^ t42;
+    let t44 = t40 ^ (t43 & x[0]);
+    let t45 = t38 ^ t44;
+    let t46 = t38 ^ (t45 & x[4]);
+    let t47 = t34 ^ t46;
+    let t48 = t34 ^ (t47 & x[1]);
+    let t49 = t35 | !x[3];
+    let t50 = x[2] ^ x[3];
+    let t51 = t49 ^ t50;
+    let t52 = t49 ^ (t51 & x[0]);
+    let t53 = x[2] ^ t5;
+    let t54 = x[2] ^ (t53 & x[3]);
+    let t55 = t54 ^ t1;
+    let t56 = t54 ^ (t55 & x[0]);
+    let t57 = t52 ^ t56;
+    let t58 = t52 ^ (t57 & x[4]);
+    let t59 = t14 ^ t1;
+    let t60 = t14 ^ (t59 & x[3]);
+    let t61 = t28 ^ t19;
+    let t62 = t28 ^ (t61 & x[3]);
+    let t63 = t60 ^ t62;
+    let t64 = t60 ^ (t63 & x[0]);
+    let t65 = t6 ^ t2;
+    let t66 = t6 ^ (t65 & x[3]);
+    let t67 = t2 ^ t0;
+    let t68 = t2 ^ (t67 & x[3]);
+    let t69 = t66 ^ t68;
+    let t70 = t66 ^ (t69 & x[0]);
+    let t71 = t64 ^ t70;
+    let t72 = t64 ^ (t71 & x[4]);
+    let t73 = t58 ^ t72;
+    let t74 = t58 ^ (t73 & x[1]);
+    let t75 = t5 ^ x[3];
+    let t76 = x[5] ^ x[3];
+    let t77 = t75 ^ t76;
+    let t78 = t75 ^ (t77 & x[0]);
+    let t79 = t13 ^ t28;
+    let t80 = t13 ^ (t79 & x[3]);
+    let t81 = t80 ^ t18;
+    let t82 = t80 ^ (t81 & x[0]);
+    let t83 = t78 ^ t82;
+    let t84 = t78 ^ (t83 & x[4]);
+    let t85 = t20 ^ t80;
+    let t86 = t20 ^ (t85 & x[0]);
+    let t87 = t1 ^ x[2];
+    let t88 = t1 ^ (t87 & x[0]);
+    let t89 = t86 ^ t88;
+    let t90 = t86 ^ (t89 & x[4]);
+    let t91 = t84 ^ t90;
+    let t92 = t84 ^ (t91 & x[1]);
+    [t26, t48, t74, t92]
+}
+
+pub(super) fn s3(x: &[u64; 6]) -> [u64; 4] {
+    let t0 = !x[3];
+    let t1 = t0 | !x[5];
+    let t2 = x[3] & !x[5];
+    let t3 = t1 ^ t2;
+    let t4 = t1 ^ (t3 & x[2]);
+    let t5 = x[5] & x[3];
+    let t6 = x[3] ^ t5;
+    let t7 = x[3] ^ (t6 & x[2]);
+    let t8 = t4 ^ t7;
+    let t9 = t4 ^ (t8 & x[4]);
+    let t10 = t0 | x[5];
+    let t11 = t2 ^ x[2];
+    let t12 = x[3] ^ x[5];
+    let t13 = t10 ^ t12;
+    let t14 = t10 ^ (t13 & x[2]);
+    let t15 = t11 ^ t14;
+    let t16 = t11 ^ (t15 & x[4]);
+    let t17 = t9 ^ t16;
+    let t18 = t9 ^ (t17 & x[1]);
+    let t19 = t0 ^ x[5];
+    let t20 = t12 ^ t0;
+    let t21 = t12 ^ (t20 & x[2]);
+    let t22 = t19 ^ t21;
+    let t23 = t19 ^ (t22 & x[4]);
+    let t24 = t19 ^ x[2];
+    let t25 = t12 ^ x[2];
+    let t26 = t24 ^ x[4];
+    let t27 = t23 ^ t26;
+    let t28 = t23 ^ (t27 & x[1]);
+    let t29 = t18 ^ t28;
+    let t30 = t18 ^ (t29 & x[0]);
+    let t31 = x[5] & t0;
+    let t32 = x[3] | !x[5];
+    let t33 = t31 ^ x[2];
+    let t34 = t33 ^ t12;
+    let t35 = t33 ^ (t34 & x[4]);
+    let t36 = x[3] ^ (t12 & x[2]);
+    let t37 = t0 & !x[5];
+    let t38 = t32 ^ t37;
+    let t39 = t32 ^ (t38 & x[2]);
+    let t40 = t36 ^ t39;
+    let t41 = t36 ^ (t40 & x[4]);
+    let t42 = t35 ^ t41;
+    let t43 = t35 ^ (t42 & x[1]);
+    let t44 = t32 ^ x[2];
+    let t45 = t37 ^ t19;
+    let t46 = t37 ^ (t45 & x[2]);
+    let t47 = t44 ^ t46;
+    let t48 = t44 ^ (t47 & x[4]);
+    let t49 = x[5] ^ x[2];
+    let t50 = x[3] | x[5];
+    let t51 = t12 ^ t50;
+    let t52 = t12 ^ (t51 & x[2]);
+    let t53 = t49 ^ t52;
+    let t54 = t49 ^ (t53 & x[4]);
+    let t55 = t48 ^ t54;
+    let t56 = t48 ^ (t55 & x[1]);
+    let t57 = t43 ^ t56;
+    let t58 = t43 ^ (t57 & x[0]);
+    let t59 = t37 | x[2];
+    let t60 = t59 ^ t25;
+    let t61 = t59 ^ (t60 & x[4]);
+    let t62 = x[3] ^ t31;
+    let t63 = x[3] ^ (t62 & x[2]);
+    let t64 = t33 ^ t63;
+    let t65 = t33 ^ (t64 & x[4]);
+    let t66 = t61 ^ t65;
+    let t67 = t61 ^ (t66 & x[1]);
+    let t68 = x[2] & t12;
+    let t69 = t0 ^ t19;
+    let t70 = t0 ^ (t69 & x[2]);
+    let t71 = t68 ^ t70;
+    let t72 = t68 ^ (t71 & x[4]);
+    let t73 = t32 ^ t50;
+    let t74 = t32 ^ (t73 & x[2]);
+    let t75 = t74 ^ t49;
+    let t76 = t74 ^ (t75 & x[4]);
+    let t77 = t72 ^ t76;
+    let t78 = t72 ^ (t77 & x[1]);
+    let t79 = t67 ^ t78;
+    let t80 = t67 ^ (t79 & x[0]);
+    let t81 = t12 ^ t49;
+    let t82 = t12 ^ (t81 & x[4]);
+    let t83 = t82 ^ x[1];
+    let t84 = t10 ^ x[2];
+    let t85 = t84 ^ x[4];
+    let t86 = t37 ^ t0;
+    let t87 = t37 ^ (t86 & x[2]);
+    let t88 = t87 ^ t14;
+    let t89 = t87 ^ (t88 & x[4]);
+    let t90 = t85 ^ t89;
+    let t91 = t85 ^ (t90 & x[1]);
+    let t92 = t83 ^ t91;
+    let t93 = t83 ^ (t92 & x[0]);
+    [t30, t58, t80, t93]
+}
+
+pub(super) fn s4(x: &[u64; 6]) -> [u64; 4] {
+    let t0 = !x[2];
+    let t1 = t0 | !x[0];
+    let t2 = x[0] ^ t1;
+    let t3 = x[0] ^ (t2 & x[3]);
+    let t4 = t0 ^ x[0];
+    let t5 = t4 ^ x[2];
+    let t6 = t4 ^ (t5 & x[3]);
+    let t7 = t3 ^ t6;
+    let t8 = t3 ^ (t7 & x[4]);
+    let t9 = x[2] ^ x[0];
+    let t10 = t9 ^ x[3];
+    let t11 = x[2] & !x[0];
+    let t12 = t11 ^ t9;
+    let t13 = t11 ^ (t12 & x[3]);
+    let t14 = t10 ^ t13;
+    let t15 = t10 ^ (t14 & x[4]);
+    let t16 = t8 ^ t15;
+    let t17 = t8 ^ (t16 & x[1]);
+    let t18 = x[0] & x[2];
+    let t19 = t1 ^ x[3];
+    let t20 = t4 ^ t19;
+    let t21 = t4 ^ (t20 & x[4]);
+    let t22 = x[0] ^ t11;
+    let t23 = x[0] ^ (t22 & x[3]);
+    let t24 = t11 | x[3];
+    let t25 = t23 ^ t24;
+    let t26 = t23 ^ (t25 & x[4]);
+    let t27 = t21 ^ t26;
+    let t28 = t21 ^ (t27 & x[1]);
+    let t29 = t17 ^ t28;
+    let t30 = t17 ^ (t29 & x[5]);
+    let t31 = !x[0];
+    let t32 = t31 ^ t18;
+    let t33 = t31 ^ (t32 & x[3]);
+    let t34 = t9 ^ t0;
+    let t35 = t9 ^ (t34 & x[3]);
+    let t36 = t33 ^ t35;
+    let t37 = t33 ^ (t36 & x[4]);
+    let t38 = t4 ^ x[3];
+    let t39 = t0 | x[0];
+    let t40 = t39 ^ t4;
+    let t41 = t39 ^ (t40 & x[3]);
+    let t42 = t38 ^ t41;
+    let t43 = t38 ^ (t42 & x[4]);
+    let t44 = t37 ^ t43;
+    let t45 = t37 ^ (t44 & x[1]);
+    let t46 = t28 ^ t45;
+    let t47 = t28 ^ (t46 & x[5]);
+    let t48 = t0 ^ t4;
+    let t49 = t0 ^ (t48 & x[3]);
+    let t50 = x[2] | x[0];
+    let t51 = t50 ^ t31;
+    let t52 = t50 ^ (t51 & x[3]);
+    let t53 = t49 ^ t52;
+    let t54 = t49 ^ (t53 & x[4]);
+    let t55 = x[0] & t0;
+    let t56 = t9 ^ t55;
+    let t57 = t9 ^ (t56 & x[3]);
+    let t58 = t57 ^ t38;
+    let t59 = t57 ^ (t58 & x[4]);
+    let t60 = t54 ^ t59;
+    let t61 = t54 ^ (t60 & x[1]);
+    let t62 = t0 & !x[0];
+    let t63 = t50 ^ x[3];
+    let t64 = t63 ^ t9;
+    let t65 = t63 ^ (t64 & x[4]);
+    let t66 = x[2] | !x[0];
+    let t67 = x[3] & t66;
+    let t68 = t66 ^ x[0];
+    let t69 = t66 ^ (t68 & x[3]);
+    let t70 = t67 ^ t69;
+    let t71 = t67 ^ (t70 & x[4]);
+    let t72 = t65 ^ t71;
+    let t73 = t65 ^ (t72 & x[1]);
+    let t74 = t61 ^ t73;
+    let t75 = t61 ^ (t74 & x[5]);
+    let t76 = t62 ^ x[3];
+    let t77 = t76 ^ t4;
+    let t78 = t76 ^ (t77 & x[4]);
+    let t79 = t55 | !x[3];
+    let t80 = t55 ^ t31;
+    let t81 = t55 ^ (t80 & x[3]);
+    let t82 = t79 ^ t81;
+    let t83 = t79 ^ (t82 & x[4]);
+    let t84 = t78 ^ t83;
+    let t85 = t78 ^ (t84 & x[1]);
+    let t86 = t85 ^ t61;
+    let t87 = t85 ^ (t86 & x[5]);
+    [t30, t47, t75, t87]
+}
+
+pub(super) fn s5(x: &[u64; 6]) -> [u64; 4] {
+    let t0 = !x[5];
+    let t1 = x[5] ^ x[1];
+    let t2 = x[0] & t0;
+    let t3 = t0 ^ x[0];
+    let t4 = t2 ^ t3;
+    let t5 = t2 ^ (t4 & x[1]);
+    let t6 = t1 ^ t5;
+    let t7 = t1 ^ (t6 & x[2]);
+    let t8 = x[5] | !x[0];
+    let t9 = t8 ^ x[0];
+    let t10 = t8 ^ (t9 & x[1]);
+    let t11 = t0 | x[0];
+    let t12 = x[5] & !x[0];
+    let t13 = t11 ^ x[1];
+    let t14 = t10 ^ t13;
+    let t15 = t10 ^ (t14 & x[2]);
+    let t16 = t7 ^ t15;
+    let t17 = t7 ^ (t16 & x[4]);
+    let t18 = x[0] & x[5];
+    let t19 = x[5] ^ x[0];
+    let t20 = t18 ^ t19;
+    let t21 = t18 ^ (t20 & x[1]);
+    let t22 = !x[0];
+    let t23 = t21 ^ t22;
+    let t24 = t21 ^ (t23 & x[2]);
+    let t25 = t19 ^ t8;
+    let t26 = t19 ^ (t25 & x[1]);
+    let t27 = x[0] ^ t0;
+    let t28 = x[0] ^ (t27 & x[1]);
+    let t29 = t26 ^ t28;
+    let t30 = t26 ^ (t29 & x[2]);
+    let t31 = t24 ^ t30;
+    let t32 = t24 ^ (t31 & x[4]);
+    let t33 = t17 ^ t32;
+    let t34 = t17 ^ (t33 & x[3]);
+    let t35 = x[5] | x[0];
+    let t36 = t19 ^ t35;
+    let t37 = t19 ^ (t36 & x[1]);
+    let t38 = t22 ^ t0;
+    let t39 = t22 ^ (t38 & x[1]);
+    let t40 = t37 ^ t39;
+    let t41 = t37 ^ (t40 & x[2]);
+    let t42 = t0 & !x[0];
+    let t43 = t42 ^ t3;
+    let t44 = t42 ^ (t43 & x[1]);
+    let t45 = t35 ^ t18;
+    let t46 = t35 ^ (t45 & x[1]);
+    let t47 = t44 ^ t46;
+    let t48 = t44 ^ (t47 & x[2]);
+    let t49 = t41 ^ t48;
+    let t50 = t41 ^ (t49 & x[4]);
+    let t51 = t3 ^ x[1];
+    let t52 = t51 ^ x[2];
+    let t53 = t1 ^ t51;
+    let t54 = t1 ^ (t53 & x[2]);
+    let t55 = t52 ^ t54;
+    let t56 = t52 ^ (t55 & x[4]);
+    let t57 = t50 ^ t56;
+    let t58 = t50 ^ (t57 & x[3]);
+    let t59 = t0 ^ t35;
+    let t60 = t0 ^ (t59 & x[1]);
+    let t61 = t10 ^ t60;
+    let t62 = t10 ^ (t61 & x[2]);
+    let t63 = t19 ^ t18;
+    let t64 = t19 ^ (t63 & x[1]);
+    let t65 = t8 ^ x[1];
+    let t66 = t64 ^ t65;
+    let t67 = t64 ^ (t66 & x[2]);
+    let t68 = t62 ^ t67;
+    let t69 = t62 ^ (t68 & x[4]);
+    let t70 = t12 ^ t22;
+    let t71 = t12 ^ (t70 & x[1]);
+    let t72 = t11 ^ t42;
+    let t73 = t11 ^ (t72 & x[1]);
+    let t74 = t71 ^ t73;
+    let t75 = t71 ^ (t74 & x[2]);
+    let t76 = x[0] ^ x[1];
+    let t77 = t42 ^ x[1];
+    let t78 = t76 ^ t77;
+    let t79 = t76 ^ (t78 & x[2]);
+    let t80 = t75 ^ t79;
+    let t81 = t75 ^ (t80 & x[4]);
+    let t82 = t69 ^ t81;
+    let t83 = t69 ^ (t82 & x[3]);
+    let t84 = t3 ^ t22;
+    let t85 = t3 ^ (t84 & x[1]);
+    let t86 = t21 ^ t85;
+    let t87 = t21 ^ (t86 & x[2]);
+    let t88 = t12 ^ x[1];
+    let t89 = t88 ^ t19;
+    let t90 = t88 ^ (t89 & x[2]);
+    let t91 = t87 ^ t90;
+    let t92 = t87 ^ (t91 & x[4]);
+    let t93 = t2 ^ t22;
+    let t94 = t2 ^ (t93 & x[1]);
+    let t95 = t0 | !x[0];
+    let t96 = t95 ^ x[1];
+    let t97 = t94 ^ t96;
+    let t98 = t94 ^ (t97 & x[2]);
+    let t99 = x[5] ^ t3;
+    let t100 = x[5] ^ (t99 & x[1]);
+    let t101 = t11 ^ t100;
+    let t102 = t11 ^ (t101 & x[2]);
+    let t103 = t98 ^ t102;
+    let t104 = t98 ^ (t103 & x[4]);
+    let t105 = t92 ^ t104;
+    let t106 = t92 ^ (t105 & x[3]);
+    [t34, t58, t83, t106]
+}
+
+pub(super) fn s6(x: &[u64; 6]) -> [u64; 4] {
+    let t0 = !x[1];
+    let t1 = !x[5];
+    let t2 = t0 ^ t1;
+    let t3 = t0 ^ (t2 & x[2]);
+    let t4 = t0 ^ x[5];
+    let t5 = x[5] & t0;
+    let t6 = t4 ^ t5;
+    let t7 = t4 ^ (t6 & x[2]);
+    let t8 = t3 ^ t7;
+    let t9 = t3 ^ (t8 & x[0]);
+    let t10 = t0 & !x[5];
+    let t11 = t10 | x[2];
+    let t12 = t7 ^ t11;
+    let t13 = t7 ^ (t12 & x[0]);
+    let t14 = t9 ^ t13;
+    let t15 = t9 ^ (t14 & x[3]);
+    let t16 = x[1] ^ x[5];
+    let t17 = t16 ^ x[5];
+    let t18 = t16 ^ (t17 & x[2]);
+    let t19 = t18 ^ x[0];
+    let t20 = x[1] | !x[5];
+    let t21 = t4 ^ t20;
+    let t22 = t4 ^ (t21 & x[2]);
+    let t23 = t22 ^ t18;
+    let t24 = t22 ^ (t23 & x[0]);
+    let t25 = t19 ^ t24;
+    let t26 = t19 ^ (t25 & x[3]);
+    let t27 = t15 ^ t26;
+    let t28 = t15 ^ (t27 & x[4]);
+    let t29 = t4 ^ x[2];
+    let t30 = x[5] & x[1];
+    let t31 = t16 ^ t30;
+    let t32 = t16 ^ (t31 & x[2]);
+    let t33 = t29 ^ t32;
+    let t34 = t29 ^ (t33 & x[0]);
+    let t35 = x[5] ^ x[2];
+    let t36 = t1 ^ t0;
+    let t37 = t1 ^ (t36 & x[2]);
+    let t38 = t35 ^ t37;
+    let t39 = t35 ^ (t38 & x[0]);
+    let t40 = t34 ^ t39;
+    let t41 = t34 ^ (t40 & x[3]);
+    let t42 = t16 ^ t29;
+    let t43 = t16 ^ (t42 & x[0]);
+    let t44 = t20 ^ x[2];
+    let t45 = t0 | x[5];
+    let t46 = t45 ^ x[1];
+    let t47 = t45 ^ (t46 & x[2]);
+    let t48 = t44 ^ t47;
+    let t49 = t44 ^ (t48 & x[0]);
+    let t50 = t43 ^ t49;
+    let t51 = t43 ^ (t50 & x[3]);
+    let t52 = t41 ^ t51;
+    let t53 = t41 ^ (t52 & x[4]);
+    let t54 = x[5] ^ t16;
+    let t55 = x[5] ^ (t54 & x[2]);
+    let t56 = x[1] ^ t4;
+    let t57 = x[1] ^ (t56 & x[2]);
+    let t58 = t55 ^ t57;
+    let t59 = t55 ^ (t58 & x[0]);
+    let t60 = t59 ^ x[3];
+    let t61 = t5 ^ x[2];
+    let t62 = t45 & !x[2];
+    let t63 = t61 ^ t62;
+    let t64 = t61 ^ (t63 & x[0]);
+    let t65 = t45 ^ x[2];
+    let t66 = t0 | !x[5];
+    let t67 = x[1] ^ t66;
+    let t68 = x[1] ^ (t67 & x[2]);
+    let t69 = t65 ^ t68;
+    let t70 = t65 ^ (t69 & x[0]);
+    let t71 = t64 ^ t70;
+    let t72 = t64 ^ (t71 & x[3]);
+    let t73 = t60 ^ t72;
+    let t74 = t60 ^ (t73 & x[4]);
+    let t75 = x[2] & t0;
+    let t76 = t20 ^ t16;
+    let t77 = t20 ^ (t76 & x[2]);
+    let t78 = t75 ^ t77;
+    let t79 = t75 ^ (t78 & x[0]);
+    let t80 = x[1] | x[5];
+    let t81 = x[1] ^ t80;
+    let t82 = x[1] ^ (t81 & x[2]);
+    let t83 = t82 ^ t29;
+    let t84 = t82 ^ (t83 & x[0]);
+    let t85 = t79 ^ t84;
+    let t86 = t79 ^ (t85 & x[3]);
+    let t87 = x[1] | !x[2];
+    let t88 = t5 ^ t16;
+    let t89 = t5 ^ (t88 & x[2]);
+    let t90 = t87 ^ t89;
+    let t91 = t87 ^ (t90 & x[0]);
+    let t92 = t10 ^ t16;
+    let t93 = t10 ^ (t92 & x[2]);
+    let t94 = t93 ^ t4;
+    let t95 = t93 ^ (t94 & x[0]);
+    let t96 = t91 ^ t95;
+    let t97 = t91 ^ (t96 & x[3]);
+    let t98 = t86 ^ t97;
+    let t99 = t86 ^ (t98 & x[4]);
+    [t28, t53, t74, t99]
+}
+
+pub(super) fn s7(x: &[u64; 6]) -> [u64; 4] {
+    let t0 = x[1] & x[3];
+    let t1 = !x[1];
+    let t2 = t0 ^ t1;
+    let t3 = t0 ^ (t2 & x[2]);
+    let t4 = !x[3];
+    let t5 = t4 | !x[1];
+    let t6 = x[3] ^ x[1];
+    let t7 = t5 ^ t6;
+    let t8 = t5 ^ (t7 & x[2]);
+    let t9 = t3 ^ t8;
+    let t10 = t3 ^ (t9 & x[4]);
+    let t11 = t4 ^ x[1];
+    let t12 = t6 ^ x[2];
+    let t13 = x[3] | x[1];
+    let t14 = x[3] & !x[1];
+    let t15 = t13 ^ t14;
+    let t16 = t13 ^ (t15 & x[2]);
+    let t17 = t12 ^ t16;
+    let t18 = t12 ^ (t17 & x[4]);
+    let t19 = t10 ^ t18;
+    let t20 = t10 ^ (t19 & x[0]);
+    let t21 = t5 ^ x[2];
+    let t22 = t21 ^ x[4];
+    let t23 = x[3] | !x[1];
+    let t24 = t23 ^ t0;
+    let t25 = t23 ^ (t24 & x[2]);
+    let t26 = t6 ^ t25;
+    let t27 = t6 ^ (t26 & x[4]);
+    let t28 = t22 ^ t27;
+    let t29 = t22 ^ (t28 & x[0]);
+    let t30 = t20 ^ t29;
+    let t31 = t20 ^ (t30 & x[5]);
+    let t32 = t4 & !x[1];
+    let t33 = t4 | x[1];
+    let t34 = t32 ^ t33;
+    let t35 = t32 ^ (t34 & x[2]);
+    let t36 = t35 ^ x[4];
+    let t37 = t36 ^ t10;
+    let t38 = t36 ^ (t37 & x[0]);
+    let t39 = t33 ^ t32;
+    let t40 = t33 ^ (t39 & x[2]);
+    let t41 = x[3] ^ (t6 & x[2]);
+    let t42 = t40 ^ t41;
+    let t43 = t40 ^ (t42 & x[4]);
+    let t44 = x[1] & t4;
+    let t45 = t1 ^ t44;
+    let t46 = t1 ^ (t45 & x[2]);
+    let t47 = t46 ^ x[4];
+    let t48 = t43 ^ t47;
+    let t49 = t43 ^ (t48 & x[0]);
+    let t50 = t38 ^ t49;
+    let t51 = t38 ^ (t50 & x[5]);
+    let t52 = t23 ^ x[2];
+    let t53 = t12 ^ t52;
+    let t54 = t12 ^ (t53 & x[4]);
+    let t55 = t44 ^ x[2];
+    let t56 = t16 ^ t55;
+    let t57 = t16 ^ (t56 & x[4]);
+    let t58 = t54 ^ t57;
+    let t59 = t54 ^ (t58 & x[0]);
+    let t60 = t6 ^ t44;
+    let t61 = t6 ^ (t60 & x[2]);
+    let t62 = t6 ^ t13;
+    let t63 = t6 ^ (t62 & x[2]);
+    let t64 = t61 ^ t63;
+    let t65 = t61 ^ (t64 & x[4]);
+    let t66 = t32 ^ x[2];
+    let t67 = t11 ^ x[2];
+    let t68 = t66 ^ t67;
+    let t69 = t66 ^ (t68 & x[4]);
+    let t70 = t65 ^ t69;
+    let t71 = t65 ^ (t70 & x[0]);
+    let t72 = t59 ^ t71;
+    let t73 = t59 ^ (t72 & x[5]);
+    let t74 = x[1] ^ t4;
+    let t75 = x[1] ^ (t74 & x[2]);
+    let t76 = t11 ^ x[3];
+    let t77 = t11 ^ (t76 & x[2]);
+    let t78 = t75 ^ t77;
+    let t79 = t75 ^ (t78 & x[4]);
+    let t80 = t6 ^ t4;
+    let t81 = t6 ^ (t80 & x[2]);
+    let t82 = t79 ^ x[0];
+    let t83 = t23 ^ t14;
+    let t84 = t23 ^ (t83 & x[2]);
+    let t85 = t84 ^ t81;
+    let t86 = t84 ^ (t85 & x[4]);
+    let t87 = t33 ^ x[2];
+    let t88 = t12 ^ t87;
+    let t89 = t12 ^ (t88 & x[4]);
+    let t90 = t86 ^ t89;
+    let t91 = t86 ^ (t90 & x[0]);
+    let t92 = t82 ^ t91;
+    let t93 = t82 ^ (t92 & x[5]);
+    [t31, t51, t73, t93]
+}
+
+pub(super) fn s8(x: &[u64; 6]) -> [u64; 4] {
+    let t0 = !x[1];
+    let t1 = t0 | !x[3];
+    let t2 = t1 ^ x[3];
+    let t3 = t1 ^ (t2 & x[2]);
+    let t4 = x[3] & x[1];
+    let t5 = t4 ^ x[2];
+    let t6 = t3 ^ t5;
+    let t7 = t3 ^ (t6 & x[0]);
+    let t8 = x[1] ^ x[3];
+    let t9 = t0 ^ x[3];
+    let t10 = t8 ^ x[2];
+    let t11 = x[1] | x[3];
+    let t12 = x[3] & t0;
+    let t13 = t11 ^ t12;
+    let t14 = t11 ^ (t13 & x[2]);
+    let t15 = t10 ^ t14;
+    let t16 = t10 ^ (t15 & x[0]);
+    let t17 = t7 ^ t16;
+    let t18 = t7 ^ (t17 & x[5]);
+    let t19 = t0 & !x[3];
+    let t20 = x[1] ^ t19;
+    let t21 = x[1] ^ (t20 & x[2]);
+    let t22 = t21 ^ t9;
+    let t23 = t21 ^ (t22 & x[0]);
+    let t24 = t0 | x[3];
+    let t25 = x[1] & !x[3];
+    let t26 = t24 ^ x[2];
+    let t27 = t25 ^ x[2];
+    let t28 = t26 ^ x[0];
+    let t29 = t23 ^ t28;
+    let t30 = t23 ^ (t29 & x[5]);
+    let t31 = t18 ^ t30;
+    let t32 = t18 ^ (t31 & x[4]);
+    let t33 = x[1] | !x[3];
+    let t34 = t19 ^ t33;
+    let t35 = t19 ^ (t34 & x[2]);
+    let t36 = t0 ^ t11;
+    let t37 = t0 ^ (t36 & x[2]);
+    let t38 = t35 ^ t37;
+    let t39 = t35 ^ (t38 & x[0]);
+    let t40 = t14 ^ t10;
+    let t41 = t14 ^ (t40 & x[0]);
+    let t42 = t39 ^ t41;
+    let t43 = t39 ^ (t42 & x[5]);
+    let t44 = x[3] ^ t9;
+    let t45 = x[3] ^ (t44 & x[2]);
+    let t46 = t45 ^ t21;
+    let t47 = t45 ^ (t46 & x[0]);
+    let t48 = !x[3];
+    let t49 = t48 ^ t8;
+    let t50 = t48 ^ (t49 & x[2]);
+    let t51 = t50 ^ t8;
+    let t52 = t50 ^ (t51 & x[0]);
+    let t53 = t47 ^ t52;
+    let t54 = t47 ^ (t53 & x[5]);
+    let t55 = t43 ^ t54;
+    let t56 = t43 ^ (t55 & x[4]);
+    let t57 = x[1] ^ x[2];
+    let t58 = t9 ^ x[2];
+    let t59 = t57 ^ t58;
+    let t60 = t57 ^ (t59 & x[0]);
+    let t61 = t4 ^ t0;
+    let t62 = t4 ^ (t61 & x[2]);
+    let t63 = t62 ^ x[0];
+    let t64 = t60 ^ t63;
+    let t65 = t60 ^ (t64 & x[5]);
+    let t66 = t9 ^ t50;
+    let t67 = t9 ^ (t66 & x[0]);
+    let t68 = t9 ^ t33;
+    let t69 = t9 ^ (t68 & x[2]);
+    let t70 = t12 ^ t9;
+    let t71 = t12 ^ (t70 & x[2]);
+    let t72 = t69 ^ t71;
+    let t73 = t69 ^ (t72 & x[0]);
+    let t74 = t67 ^ t73;
+    let t75 = t67 ^ (t74 & x[5]);
+    let t76 = t65 ^ t75;
+    let t77 = t65 ^ (t76 & x[4]);
+    let t78 = t58 ^ t35;
+    let t79 = t58 ^ (t78 & x[0]);
+    let t80 = t0 ^ (t9 & x[2]);
+    let t81 = x[1] ^ t25;
+    let t82 = x[1] ^ (t81 & x[2]);
+    let t83 = t80 ^ t82;
+    let t84 = t80 ^ (t83 & x[0]);
+    let t85 = t79 ^ t84;
+    let t86 = t79 ^ (t85 & x[5]);
+    let t87 = t27 ^ x[0];
+    let t88 = t33 ^ t19;
+    let t89 = t33 ^ (t88 & x[2]);
+    let t90 = t89 ^ t37;
+    let t91 = t89 ^ (t90 & x[0]);
+    let t92 = t87 ^ t91;
+    let t93 = t87 ^ (t92 & x[5]);
+    let t94 = t86 ^ t93;
+    let t95 = t86 ^ (t94 & x[4]);
+    [t32, t56, t77, t95]
+}
@@ -0,0 +1,199 @@
+//! Traditional Unix `crypt(3)` and BSDi extended DES password hashing.
+
+use crate::{
+    Des,
+    utils::{fp, gen_keys, ip, salted_round},
+};
+use core::fmt;
+
+/// Alphabet used for salts, round counts and hashes.
+const ALPHABET: &[u8; 64] = b"./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// Number of DES encryptions performed by the traditional algorithm.
+const TRADITIONAL_ROUNDS: u32 = 25;
+
+/// Hash `password` using the traditional Unix DES-based `crypt(3)` algorithm.
+///
+/// Only the first 8 bytes of `password` are used. Like C `crypt()`,
+/// `password` ends at the first NUL byte. The returned string consists of
+/// the 2-character salt followed by the 11-character hash.
+pub fn crypt_traditional(password: &[u8], salt: &[u8; 2]) -> Result<[u8; 13], CryptError> {
+    let salt_bits = decode_b64(salt)?;
+    let password = until_nul(password);
+
+    let mut keybuf = [0u8; 8];
+    for (k, p) in keybuf.iter_mut().zip(password) {
+        *k = p << 1;
+    }
+    let hash = salted_des(u64::from_be_bytes(keybuf), salt_bits, TRADITIONAL_ROUNDS);
+
+    let mut out = [0u8; 13];
+    out[..2].copy_from_slice(salt);
+    encode_hash(hash, &mut out[2..]);
+    Ok(out)
+}
+
+/// Hash `password` using the BSDi extended DES-based `crypt(3)` algorithm.
+///
+/// `rounds` must be in the range `1..2^24` and the whole `password` up to
+/// the first NUL byte (if any) is used.
+/// The returned string has the form `_CCCCSSSSHHHHHHHHHHH`, where `CCCC` is
+/// the encoded round count, `SSSS` the 4-character salt and `HHHHHHHHHHH`
+/// the hash.
+pub fn crypt_extended(
+    password: &[u8],
+    rounds: u32,
+    salt: &[u8; 4],
+) -> Result<[u8; 20], CryptError> {
+    if rounds == 0 || rounds >= 1 << 24 {
+        return Err(CryptError::InvalidRounds);
+    }
+    let salt_bits = decode_b64(salt)?;
+
+    let mut chunks = until_nul(password).chunks(8);
+    let mut keybuf = [0u8; 8];
+    if let Some(chunk) = chunks.next() {
+        for (k, p) in keybuf.iter_mut().zip(chunk) {
+            *k = p << 1;
+        }
+    }
+    let mut key = u64::from_be_bytes(keybuf);
+    // Fold the rest of the password into the key by encrypting the key
+    // with itself and XORing in the next 8 password bytes
+    for chunk in chunks {
+        let des = Des {
+            keys: gen_keys(key),
+        };
+        keybuf = des.encrypt(key).to_be_bytes();
+        for (k, p) in keybuf.iter_mut().zip(chunk) {
+            *k ^= p << 1;
+        }
+        key = u64::from_be_bytes(keybuf);
+    }
+    let hash = salted_des(key, salt_bits, rounds);
+
+    let mut out = [0u8; 20];
+    out[0] = b'_';
+    encode_b64(rounds, &mut out[1..5]);
+    out[5..9].copy_from_slice(salt);
+    encode_hash(hash, &mut out[9..]);
+    Ok(out)
+}
+
+/// Cut `password` at the first NUL byte, where a C string would end.
+fn until_nul(password: &[u8]) -> &[u8] {
+    match password.iter().position(|&b| b == 0) {
+        Some(n) => &password[..n],
+        None => password,
+    }
+}
+
+/// Verify `password` against a traditional or BSDi extended `crypt(3)` hash.
+///
+/// The hash is compared in constant time.
+pub fn crypt_verify(password: &[u8], hash: &[u8]) -> Result<(), CryptError> {
+    match hash.len() {
+        20 if hash[0] == b'_' => {
+            let rounds = decode_b64(&hash[1..5]).map_err(|_| CryptError::InvalidRounds)?;
+            let salt = hash[5..9].try_into().expect("salt length is equal to 4");
+            ct_eq(&crypt_extended(password, rounds, salt)?, hash)
+        }
+        13 => {
+            let salt = hash[..2].try_into().expect("salt length is equal to 2");
+            ct_eq(&crypt_traditional(password, salt)?, hash)
+        }
+        _ => Err(CryptError::InvalidHash),
+    }
+}
+
+/// The error type returned by the `crypt(3)` functions.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[non_exhaustive]
+pub enum CryptError {
+    /// The salt contains characters outside of `[./0-9A-Za-z]`.
+    InvalidSalt,
+    /// The round count is zero or does not fit into 24 bits.
+    InvalidRounds,
+    /// The hash is neither a traditional nor a BSDi extended hash.
+    InvalidHash,
+    /// The password does not match the hash.
+    PasswordMismatch,
+}
+
+impl fmt::Display for CryptError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(match self {
+            Self::InvalidSalt => "invalid salt",
+            Self::InvalidRounds => "invalid round count",
+            Self::InvalidHash => "invalid hash",
+            Self::PasswordMismatch => "password mismatch",
+        })
+    }
+}
+
+impl core::error::Error for CryptError {}
+
+/// Compare `expected` and `hash` in constant time.
+fn ct_eq(expected: &[u8], hash: &[u8]) -> Result<(), CryptError> {
+    let mut diff = 0u8;
+    for (a, b) in expected.iter().zip(hash) {
+        diff |= a ^ b;
+    }
+    match diff {
+        0 => Ok(()),
+        _ => Err(CryptError::PasswordMismatch),
+    }
+}
+
+/// Encrypt an all-zero block `rounds` times with salt-perturbed DES.
+fn salted_des(key: u64, salt_bits: u32, rounds: u32) -> u64 {
+    let keys = gen_keys(key);
+
+    // Salt bit `i` swaps bits `i` and `i + 24` of the E table output
+    let mut salt = 0u64;
+    for i in 0..24 {
+        salt |= u64::from((salt_bits >> i) & 1) << (39 - i);
+    }
+
+    let mut data = 0;
+    for _ in 0..rounds {
+        data = ip(data);
+        for &key in &keys {
+            data = salted_round(data, key, salt);
+        }
+        data = fp(data.rotate_right(32));
+    }
+    data
+}
+
+/// Decode little-endian base-64 encoded digits.
+fn decode_b64(digits: &[u8]) -> Result<u32, CryptError> {
+    let mut value = 0;
+    for (i, &c) in digits.iter().enumerate() {
+        let digit = match c {
+            b'.'..=b'9' => c - b'.',
+            b'A'..=b'Z' => c - b'A' + 12,
+            b'a'..=b'z' => c - b'a' + 38,
+            _ => return Err(CryptError::InvalidSalt),
+        };
+        value |= u32::from(digit) << (6 * i);
+    }
+    Ok(value)
+}
+
+/// Encode `value` as little-endian base-64 digits.
+fn encode_b64(value: u32, out: &mut [u8]) {
+    for (i, c) in out.iter_mut().enumerate() {
+        *c = ALPHABET[((value >> (6 * i)) & 0x3F) as usize];
+    }
+}
+
+/// Encode the 64-bit hash padded with two zero bits as 11 big-endian
+/// base-64 digits.
+fn encode_hash(hash: u64, out: &mut [u8]) {
+    let value = u128::from(hash) << 2;
+    for (i, c) in out.iter_mut().enumerate() {
+        *c = ALPHABET[((value >> (60 - 6 * i)) & 0x3F) as usize];
+    }
+}
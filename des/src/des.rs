@@ -5,14 +5,16 @@
 use cipher::{
     AlgorithmName, Block, BlockCipherDecBackend, BlockCipherDecClosure, BlockCipherDecrypt,
     BlockCipherEncBackend, BlockCipherEncClosure, BlockCipherEncrypt, BlockSizeUser, InOut, Key,
-    KeyInit, KeySizeUser, ParBlocksSizeUser,
-    consts::{U1, U8},
+    KeyInit, KeySizeUser, ParBlocks, ParBlocksSizeUser,
+    consts::{U8, U64},
 };
 use core::fmt;
 
 #[cfg(feature = "zeroize")]
 use cipher::zeroize::{Zeroize, ZeroizeOnDrop};
 
+use crate::bitslice;
+use crate::key_error::{KeyError, check_key};
 use crate::utils::{fp, gen_keys, ip, round};
 
 /// Data Encryption Standard (DES) block cipher.
@@ -51,12 +53,23 @@ impl KeyInit for Des {
     }
 }
 
+impl Des {
+    /// Create new cipher instance, rejecting keys which:
+    /// - do not have odd parity;
+    /// - contain a weak, semi-weak or possibly weak DES key.
+    #[inline]
+    pub fn new_checked(key: &Key<Self>) -> Result<Self, KeyError> {
+        check_key(key)?;
+        Ok(Self::new(key))
+    }
+}
+
 impl BlockSizeUser for Des {
     type BlockSize = U8;
 }
 
 impl ParBlocksSizeUser for Des {
-    type ParBlocksSize = U1;
+    type ParBlocksSize = U64;
 }
 
 impl BlockCipherEncrypt for Des {
@@ -73,6 +86,11 @@ impl BlockCipherEncBackend for Des {
         data = self.encrypt(data);
         block.get_out().copy_from_slice(&data.to_be_bytes());
     }
+
+    #[inline]
+    fn encrypt_par_blocks(&self, blocks: InOut<'_, '_, ParBlocks<Self>>) {
+        bitslice::par_blocks(blocks, |state| state.encrypt(&self.keys));
+    }
 }
 
 impl BlockCipherDecrypt for Des {
@@ -89,6 +107,11 @@ impl BlockCipherDecBackend for Des {
         data = self.decrypt(data);
         block.get_out().copy_from_slice(&data.to_be_bytes());
     }
+
+    #[inline]
+    fn decrypt_par_blocks(&self, blocks: InOut<'_, '_, ParBlocks<Self>>) {
+        bitslice::par_blocks(blocks, |state| state.decrypt(&self.keys));
+    }
 }
 
 impl fmt::Debug for Des {
@@ -0,0 +1,137 @@
+//! DES-X (DESX) block cipher.
+
+use cipher::{
+    AlgorithmName, Block, BlockCipherDecBackend, BlockCipherDecClosure, BlockCipherDecrypt,
+    BlockCipherEncBackend, BlockCipherEncClosure, BlockCipherEncrypt, BlockSizeUser, InOut, Key,
+    KeyInit, KeySizeUser, ParBlocks, ParBlocksSizeUser,
+    consts::{U8, U24, U64},
+};
+use core::fmt;
+
+#[cfg(feature = "zeroize")]
+use cipher::zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::{Des, bitslice, utils::gen_keys};
+
+/// DES-X (DESX) block cipher.
+///
+/// DESX XORs the block with a pre-whitening key before and with
+/// a post-whitening key after encrypting it with DES. The 24-byte key is the
+/// concatenation of the DES key, the pre-whitening key and the post-whitening
+/// key (the layout used by RSAREF and OpenSSL).
+#[derive(Clone)]
+pub struct DesX {
+    des: Des,
+    pre: u64,
+    post: u64,
+}
+
+impl DesX {
+    /// Create new cipher instance from the DES key and the whitening keys.
+    ///
+    /// This allows to use DESX variants which derive the post-whitening key
+    /// from the other two keys, such as the one in RSA's BSAFE. This crate
+    /// does not implement that derivation, the post-whitening key has to be
+    /// computed by the caller.
+    #[inline]
+    pub fn new_from_parts(key: &Key<Des>, pre: &Block<Self>, post: &Block<Self>) -> Self {
+        let des = Des {
+            keys: gen_keys(u64::from_be_bytes(key.0)),
+        };
+        let pre = u64::from_be_bytes(pre.0);
+        let post = u64::from_be_bytes(post.0);
+        Self { des, pre, post }
+    }
+}
+
+impl KeySizeUser for DesX {
+    type KeySize = U24;
+}
+
+impl KeyInit for DesX {
+    #[inline]
+    fn new(key: &Key<Self>) -> Self {
+        let (key, whitening) = key.split_ref::<U8>();
+        let (pre, post) = whitening.split_ref::<U8>();
+        Self::new_from_parts(key, pre, post)
+    }
+}
+
+impl BlockSizeUser for DesX {
+    type BlockSize = U8;
+}
+
+impl ParBlocksSizeUser for DesX {
+    type ParBlocksSize = U64;
+}
+
+impl BlockCipherEncrypt for DesX {
+    #[inline]
+    fn encrypt_with_backend(&self, f: impl BlockCipherEncClosure<BlockSize = Self::BlockSize>) {
+        f.call(self)
+    }
+}
+
+impl BlockCipherEncBackend for DesX {
+    #[inline]
+    fn encrypt_block(&self, mut block: InOut<'_, '_, Block<Self>>) {
+        let mut data = u64::from_be_bytes(block.clone_in().into());
+        data = self.des.encrypt(data ^ self.pre) ^ self.post;
+        block.get_out().copy_from_slice(&data.to_be_bytes());
+    }
+
+    #[inline]
+    fn encrypt_par_blocks(&self, blocks: InOut<'_, '_, ParBlocks<Self>>) {
+        bitslice::whitened_par_blocks(blocks, self.pre, self.post, |state| {
+            state.encrypt(&self.des.keys)
+        });
+    }
+}
+
+impl BlockCipherDecrypt for DesX {
+    #[inline]
+    fn decrypt_with_backend(&self, f: impl BlockCipherDecClosure<BlockSize = Self::BlockSize>) {
+        f.call(self)
+    }
+}
+
+impl BlockCipherDecBackend for DesX {
+    #[inline]
+    fn decrypt_block(&self, mut block: InOut<'_, '_, Block<Self>>) {
+        let mut data = u64::from_be_bytes(block.clone_in().into());
+        data = self.des.decrypt(data ^ self.post) ^ self.pre;
+        block.get_out().copy_from_slice(&data.to_be_bytes());
+    }
+
+    #[inline]
+    fn decrypt_par_blocks(&self, blocks: InOut<'_, '_, ParBlocks<Self>>) {
+        bitslice::whitened_par_blocks(blocks, self.post, self.pre, |state| {
+            state.decrypt(&self.des.keys)
+        });
+    }
+}
+
+impl fmt::Debug for DesX {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DesX { ... }")
+    }
+}
+
+impl AlgorithmName for DesX {
+    fn write_alg_name(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DesX")
+    }
+}
+
+impl Drop for DesX {
+    fn drop(&mut self) {
+        #[cfg(feature = "zeroize")]
+        {
+            self.pre.zeroize();
+            self.post.zeroize();
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl ZeroizeOnDrop for DesX {}
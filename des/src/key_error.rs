@@ -0,0 +1,53 @@
+use crate::{parity::check_odd_parity, weak_key::is_weak_subkey};
+use core::fmt;
+
+/// The error type returned by the `new_checked` constructors.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[non_exhaustive]
+pub enum KeyError {
+    /// At least one key byte does not have odd parity.
+    Parity,
+    /// At least one DES key is weak, semi-weak or possibly weak.
+    Weak,
+    /// Adjacent TDES keys are equal (K1 = K2 or K2 = K3), which reduces the
+    /// cipher strength to that of a single or double DES.
+    Degenerate,
+}
+
+impl fmt::Display for KeyError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(match self {
+            Self::Parity => "key does not have odd parity",
+            Self::Weak => "key is weak",
+            Self::Degenerate => "adjacent TDES keys are equal",
+        })
+    }
+}
+
+impl core::error::Error for KeyError {}
+
+/// Check `key` for odd parity, weak DES keys and degenerate TDES keying.
+///
+/// All checks are always performed. If several of them fail, the error which
+/// comes first in the declaration order of [`KeyError`] is reported.
+pub(crate) fn check_key(key: &[u8]) -> Result<(), KeyError> {
+    let bad_parity = u8::from(check_odd_parity(key).is_err());
+
+    let subkeys = key.chunks_exact(8);
+    let mut is_weak = 0u8;
+    for subkey in subkeys.clone() {
+        is_weak |= is_weak_subkey(subkey.try_into().expect("`subkey` length is equal to 8"));
+    }
+    let mut is_degenerate = 0u8;
+    for (k1, k2) in subkeys.clone().zip(subkeys.skip(1)) {
+        is_degenerate |= u8::from(k1 == k2);
+    }
+
+    match (bad_parity, is_weak, is_degenerate) {
+        (0, 0, 0) => Ok(()),
+        (0, 0, _) => Err(KeyError::Degenerate),
+        (0, _, _) => Err(KeyError::Weak),
+        _ => Err(KeyError::Parity),
+    }
+}
@@ -0,0 +1,176 @@
+//! TDES keying option detection.
+
+use crate::{Des, TdesEde2, TdesEde3, weak_key::PARITY_MASK};
+use cipher::{
+    AlgorithmName, Array, BlockCipherDecClosure, BlockCipherDecrypt, BlockCipherEncClosure,
+    BlockCipherEncrypt, BlockSizeUser, InvalidLength, Key, KeyInit, consts::U8,
+};
+use core::fmt;
+
+#[cfg(feature = "zeroize")]
+use cipher::zeroize::ZeroizeOnDrop;
+
+/// TDES keying option as defined in [NIST SP 800-67] section 3.1.
+///
+/// [NIST SP 800-67]: https://csrc.nist.gov/pubs/sp/800/67/r2/final
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum KeyingOption {
+    /// Three independent keys.
+    One,
+    /// Two independent keys with K1 = K3.
+    Two,
+    /// Equivalent to single DES.
+    ///
+    /// Besides K1 = K2 = K3 this includes keys with K1 = K2 or K2 = K3,
+    /// since EDE encryption with such keys reduces to single DES.
+    Three,
+}
+
+impl KeyingOption {
+    /// Detect the effective keying option of an 8, 16, or 24-byte DES or
+    /// TDES `key`.
+    ///
+    /// Parity bits are ignored when comparing keys.
+    ///
+    /// Returns [`InvalidLength`] if `key` length is not equal to 8, 16, or 24.
+    pub fn detect(key: &[u8]) -> Result<Self, InvalidLength> {
+        let [k1, k2, k3] = split_key(&TdesEde::expand_key(key)?);
+        let eq = |a: u64, b: u64| (a ^ b) & PARITY_MASK == 0;
+        Ok(if eq(k1, k2) || eq(k2, k3) {
+            Self::Three
+        } else if eq(k1, k3) {
+            Self::Two
+        } else {
+            Self::One
+        })
+    }
+}
+
+impl fmt::Display for KeyingOption {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(match self {
+            Self::One => "keying option 1",
+            Self::Two => "keying option 2",
+            Self::Three => "keying option 3",
+        })
+    }
+}
+
+/// Triple DES (3DES) block cipher in EDE mode with the keying option
+/// detected from the key.
+///
+/// Keys are reduced to the cheapest equivalent cipher: [`TdesEde3`] for
+/// [`KeyingOption::One`], [`TdesEde2`] for [`KeyingOption::Two`] and [`Des`]
+/// for [`KeyingOption::Three`].
+#[derive(Clone)]
+pub enum TdesEde {
+    /// Three-key TDES.
+    Ede3(TdesEde3),
+    /// Two-key TDES.
+    Ede2(TdesEde2),
+    /// Single DES.
+    Des(Des),
+}
+
+impl TdesEde {
+    /// Create new cipher instance from an 8, 16, or 24-byte `key`.
+    ///
+    /// Returns [`InvalidLength`] if `key` length is not equal to 8, 16, or 24.
+    pub fn new_from_slice(key: &[u8]) -> Result<Self, InvalidLength> {
+        let key = Self::expand_key(key)?;
+        Ok(match KeyingOption::detect(&key)? {
+            KeyingOption::One => Self::Ede3(TdesEde3::new(&key)),
+            KeyingOption::Two => Self::Ede2(TdesEde2::new(key[..16].try_into().unwrap())),
+            KeyingOption::Three => {
+                // With K1 = K2 the first two stages cancel out and K3 is
+                // used, otherwise K2 = K3 and K1 is used
+                let [k1, k2, _] = split_key(&key);
+                let k = if (k1 ^ k2) & PARITY_MASK == 0 {
+                    &key[16..]
+                } else {
+                    &key[..8]
+                };
+                Self::Des(Des::new(k.try_into().unwrap()))
+            }
+        })
+    }
+
+    /// Expand an 8 or 16-byte key to the 24-byte `K1 || K2 || K3` form.
+    ///
+    /// 8-byte keys are expanded to `K1 || K1 || K1` and 16-byte keys to
+    /// `K1 || K2 || K1`. 24-byte keys are returned unchanged.
+    ///
+    /// Returns [`InvalidLength`] if `key` length is not equal to 8, 16, or 24.
+    pub fn expand_key(key: &[u8]) -> Result<Key<TdesEde3>, InvalidLength> {
+        let mut res = Key::<TdesEde3>::default();
+        match key.len() {
+            8 => res.chunks_exact_mut(8).for_each(|k| k.copy_from_slice(key)),
+            16 => {
+                res[..16].copy_from_slice(key);
+                res[16..].copy_from_slice(&key[..8]);
+            }
+            24 => res.copy_from_slice(key),
+            _ => return Err(InvalidLength),
+        }
+        Ok(res)
+    }
+
+    /// Keying option of this cipher instance.
+    #[inline]
+    pub fn keying_option(&self) -> KeyingOption {
+        match self {
+            Self::Ede3(_) => KeyingOption::One,
+            Self::Ede2(_) => KeyingOption::Two,
+            Self::Des(_) => KeyingOption::Three,
+        }
+    }
+}
+
+/// Split a 24-byte key into its DES keys.
+fn split_key(key: &Key<TdesEde3>) -> [u64; 3] {
+    let (k1, rest) = key.split_ref::<U8>();
+    let (k2, k3) = rest.split_ref::<U8>();
+    [k1, k2, k3].map(|k: &Array<u8, U8>| u64::from_be_bytes(k.0))
+}
+
+impl BlockSizeUser for TdesEde {
+    type BlockSize = U8;
+}
+
+impl BlockCipherEncrypt for TdesEde {
+    #[inline]
+    fn encrypt_with_backend(&self, f: impl BlockCipherEncClosure<BlockSize = Self::BlockSize>) {
+        match self {
+            Self::Ede3(c) => c.encrypt_with_backend(f),
+            Self::Ede2(c) => c.encrypt_with_backend(f),
+            Self::Des(c) => c.encrypt_with_backend(f),
+        }
+    }
+}
+
+impl BlockCipherDecrypt for TdesEde {
+    #[inline]
+    fn decrypt_with_backend(&self, f: impl BlockCipherDecClosure<BlockSize = Self::BlockSize>) {
+        match self {
+            Self::Ede3(c) => c.decrypt_with_backend(f),
+            Self::Ede2(c) => c.decrypt_with_backend(f),
+            Self::Des(c) => c.decrypt_with_backend(f),
+        }
+    }
+}
+
+impl fmt::Debug for TdesEde {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TdesEde {{ {} }}", self.keying_option())
+    }
+}
+
+impl AlgorithmName for TdesEde {
+    fn write_alg_name(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TdesEde")
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl ZeroizeOnDrop for TdesEde {}
@@ -1,5 +1,5 @@
 //! Pure Rust implementation of the [Data Encryption Standard][DES] (DES),
-//! including [Triple DES] (TDES, 3DES) block ciphers.
+//! including [Triple DES] (TDES, 3DES) and [DES-X] block ciphers.
 //!
 //! # ⚠️ Security Warning: Hazmat!
 //!
@@ -9,8 +9,20 @@
 //!
 //! USE AT YOUR OWN RISK!
 //!
+//! # Password hashing
+//! Enabling the `crypt` feature provides the traditional Unix `crypt(3)` and
+//! the BSDi extended DES-based password hashing functions, which can be used
+//! to verify legacy password hashes.
+//!
+//! # Parallel processing
+//! Single blocks are processed using table-based S-boxes. When encrypting or
+//! decrypting multiple blocks (e.g. via `encrypt_blocks`), batches of 64 blocks
+//! are processed by a bitsliced implementation which evaluates the S-boxes as
+//! Boolean circuits instead of performing secret-dependent table lookups.
+//!
 //! [DES]: https://en.wikipedia.org/wiki/Data_Encryption_Standard
 //! [Triple DES]: https://en.wikipedia.org/wiki/Triple_DES
+//! [DES-X]: https://en.wikipedia.org/wiki/DES-X
 
 #![no_std]
 #![doc(
@@ -23,12 +35,27 @@
 
 pub use cipher;
 
+mod bitslice;
 mod consts;
+#[cfg(feature = "crypt")]
+mod crypt;
 mod des;
+mod desx;
+mod key_error;
+mod keying;
+mod parity;
 mod tdes;
 mod utils;
 mod weak_key;
 
+#[cfg(feature = "crypt")]
+pub use crate::crypt::{CryptError, crypt_extended, crypt_traditional, crypt_verify};
 pub use crate::des::Des;
+pub use crate::desx::DesX;
+pub use crate::key_error::KeyError;
+pub use crate::keying::{KeyingOption, TdesEde};
+pub use crate::parity::{ParityError, check_odd_parity, set_odd_parity};
 pub use crate::tdes::{TdesEde2, TdesEde3, TdesEee2, TdesEee3};
-pub use weak_key::{WeakKeyError, weak_key_test};
+pub use weak_key::{
+    DesKeyClass, WeakKeyError, WeakKeyReason, WeakKeyReport, classify_key, weak_key_test,
+};
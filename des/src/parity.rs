@@ -0,0 +1,54 @@
+use core::fmt;
+
+/// Set the least significant bit of every byte of `key` so that each byte
+/// has odd parity, as required by FIPS 46-3.
+///
+/// # Panics
+/// If `key` length is not equal to 8, 16, or 24.
+#[inline]
+pub fn set_odd_parity(key: &mut [u8]) {
+    assert!(
+        matches!(key.len(), 8 | 16 | 24),
+        "key size is not equal to 8, 16, or 24 bytes"
+    );
+
+    for byte in key {
+        let parity = ((*byte >> 1).count_ones() as u8 & 1) ^ 1;
+        *byte = (*byte & 0xFE) | parity;
+    }
+}
+
+/// Checks whether every byte of `key` has odd parity.
+///
+/// # Panics
+/// If `key` length is not equal to 8, 16, or 24.
+#[inline]
+pub fn check_odd_parity(key: &[u8]) -> Result<(), ParityError> {
+    assert!(
+        matches!(key.len(), 8 | 16 | 24),
+        "key size is not equal to 8, 16, or 24 bytes"
+    );
+
+    let mut is_even = 0u8;
+    for byte in key {
+        is_even |= (byte.count_ones() as u8 & 1) ^ 1;
+    }
+
+    match is_even {
+        0 => Ok(()),
+        _ => Err(ParityError),
+    }
+}
+
+/// The error type returned when a key does not have odd parity.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct ParityError;
+
+impl fmt::Display for ParityError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str("key does not have odd parity")
+    }
+}
+
+impl core::error::Error for ParityError {}
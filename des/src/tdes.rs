@@ -1,11 +1,15 @@
 //! Triple DES (3DES) block ciphers.
 
-use crate::{Des, utils::gen_keys};
+use crate::{
+    Des, bitslice,
+    key_error::{KeyError, check_key},
+    utils::gen_keys,
+};
 use cipher::{
     AlgorithmName, Block, BlockCipherDecBackend, BlockCipherDecClosure, BlockCipherDecrypt,
     BlockCipherEncBackend, BlockCipherEncClosure, BlockCipherEncrypt, BlockSizeUser, InOut, Key,
-    KeyInit, KeySizeUser, ParBlocksSizeUser,
-    consts::{U1, U8, U16, U24},
+    KeyInit, KeySizeUser, ParBlocks, ParBlocksSizeUser,
+    consts::{U8, U16, U24, U64},
 };
 use core::fmt;
 
@@ -37,12 +41,24 @@ impl KeyInit for TdesEde3 {
     }
 }
 
+impl TdesEde3 {
+    /// Create new cipher instance, rejecting keys which:
+    /// - do not have odd parity;
+    /// - contain a weak, semi-weak or possibly weak DES key;
+    /// - have equal adjacent keys (K1 = K2 or K2 = K3).
+    #[inline]
+    pub fn new_checked(key: &Key<Self>) -> Result<Self, KeyError> {
+        check_key(key)?;
+        Ok(Self::new(key))
+    }
+}
+
 impl BlockSizeUser for TdesEde3 {
     type BlockSize = U8;
 }
 
 impl ParBlocksSizeUser for TdesEde3 {
-    type ParBlocksSize = U1;
+    type ParBlocksSize = U64;
 }
 
 impl BlockCipherEncrypt for TdesEde3 {
@@ -61,6 +77,15 @@ impl BlockCipherEncBackend for TdesEde3 {
         data = self.d3.encrypt(data);
         block.get_out().copy_from_slice(&data.to_be_bytes());
     }
+
+    #[inline]
+    fn encrypt_par_blocks(&self, blocks: InOut<'_, '_, ParBlocks<Self>>) {
+        bitslice::par_blocks(blocks, |state| {
+            state.encrypt(&self.d1.keys);
+            state.decrypt(&self.d2.keys);
+            state.encrypt(&self.d3.keys);
+        });
+    }
 }
 
 impl BlockCipherDecrypt for TdesEde3 {
@@ -79,6 +104,15 @@ impl BlockCipherDecBackend for TdesEde3 {
         data = self.d1.decrypt(data);
         block.get_out().copy_from_slice(&data.to_be_bytes());
     }
+
+    #[inline]
+    fn decrypt_par_blocks(&self, blocks: InOut<'_, '_, ParBlocks<Self>>) {
+        bitslice::par_blocks(blocks, |state| {
+            state.decrypt(&self.d3.keys);
+            state.encrypt(&self.d2.keys);
+            state.decrypt(&self.d1.keys);
+        });
+    }
 }
 
 impl fmt::Debug for TdesEde3 {
@@ -121,12 +155,24 @@ impl KeyInit for TdesEee3 {
     }
 }
 
+impl TdesEee3 {
+    /// Create new cipher instance, rejecting keys which:
+    /// - do not have odd parity;
+    /// - contain a weak, semi-weak or possibly weak DES key;
+    /// - have equal adjacent keys (K1 = K2 or K2 = K3).
+    #[inline]
+    pub fn new_checked(key: &Key<Self>) -> Result<Self, KeyError> {
+        check_key(key)?;
+        Ok(Self::new(key))
+    }
+}
+
 impl BlockSizeUser for TdesEee3 {
     type BlockSize = U8;
 }
 
 impl ParBlocksSizeUser for TdesEee3 {
-    type ParBlocksSize = U1;
+    type ParBlocksSize = U64;
 }
 
 impl BlockCipherEncrypt for TdesEee3 {
@@ -145,6 +191,15 @@ impl BlockCipherEncBackend for TdesEee3 {
         data = self.d3.encrypt(data);
         block.get_out().copy_from_slice(&data.to_be_bytes());
     }
+
+    #[inline]
+    fn encrypt_par_blocks(&self, blocks: InOut<'_, '_, ParBlocks<Self>>) {
+        bitslice::par_blocks(blocks, |state| {
+            state.encrypt(&self.d1.keys);
+            state.encrypt(&self.d2.keys);
+            state.encrypt(&self.d3.keys);
+        });
+    }
 }
 
 impl BlockCipherDecrypt for TdesEee3 {
@@ -163,6 +218,15 @@ impl BlockCipherDecBackend for TdesEee3 {
         data = self.d1.decrypt(data);
         block.get_out().copy_from_slice(&data.to_be_bytes());
     }
+
+    #[inline]
+    fn decrypt_par_blocks(&self, blocks: InOut<'_, '_, ParBlocks<Self>>) {
+        bitslice::par_blocks(blocks, |state| {
+            state.decrypt(&self.d3.keys);
+            state.decrypt(&self.d2.keys);
+            state.decrypt(&self.d1.keys);
+        });
+    }
 }
 
 impl fmt::Debug for TdesEee3 {
@@ -202,12 +266,24 @@ impl KeyInit for TdesEde2 {
     }
 }
 
+impl TdesEde2 {
+    /// Create new cipher instance, rejecting keys which:
+    /// - do not have odd parity;
+    /// - contain a weak, semi-weak or possibly weak DES key;
+    /// - have equal keys (K1 = K2).
+    #[inline]
+    pub fn new_checked(key: &Key<Self>) -> Result<Self, KeyError> {
+        check_key(key)?;
+        Ok(Self::new(key))
+    }
+}
+
 impl BlockSizeUser for TdesEde2 {
     type BlockSize = U8;
 }
 
 impl ParBlocksSizeUser for TdesEde2 {
-    type ParBlocksSize = U1;
+    type ParBlocksSize = U64;
 }
 
 impl BlockCipherEncrypt for TdesEde2 {
@@ -226,6 +302,15 @@ impl BlockCipherEncBackend for TdesEde2 {
         data = self.d1.encrypt(data);
         block.get_out().copy_from_slice(&data.to_be_bytes());
     }
+
+    #[inline]
+    fn encrypt_par_blocks(&self, blocks: InOut<'_, '_, ParBlocks<Self>>) {
+        bitslice::par_blocks(blocks, |state| {
+            state.encrypt(&self.d1.keys);
+            state.decrypt(&self.d2.keys);
+            state.encrypt(&self.d1.keys);
+        });
+    }
 }
 
 impl BlockCipherDecrypt for TdesEde2 {
@@ -244,6 +329,15 @@ impl BlockCipherDecBackend for TdesEde2 {
         data = self.d1.decrypt(data);
         block.get_out().copy_from_slice(&data.to_be_bytes());
     }
+
+    #[inline]
+    fn decrypt_par_blocks(&self, blocks: InOut<'_, '_, ParBlocks<Self>>) {
+        bitslice::par_blocks(blocks, |state| {
+            state.decrypt(&self.d1.keys);
+            state.encrypt(&self.d2.keys);
+            state.decrypt(&self.d1.keys);
+        });
+    }
 }
 
 impl fmt::Debug for TdesEde2 {
@@ -283,12 +377,24 @@ impl KeyInit for TdesEee2 {
     }
 }
 
+impl TdesEee2 {
+    /// Create new cipher instance, rejecting keys which:
+    /// - do not have odd parity;
+    /// - contain a weak, semi-weak or possibly weak DES key;
+    /// - have equal keys (K1 = K2).
+    #[inline]
+    pub fn new_checked(key: &Key<Self>) -> Result<Self, KeyError> {
+        check_key(key)?;
+        Ok(Self::new(key))
+    }
+}
+
 impl BlockSizeUser for TdesEee2 {
     type BlockSize = U8;
 }
 
 impl ParBlocksSizeUser for TdesEee2 {
-    type ParBlocksSize = U1;
+    type ParBlocksSize = U64;
 }
 
 impl BlockCipherEncrypt for TdesEee2 {
@@ -307,6 +413,15 @@ impl BlockCipherEncBackend for TdesEee2 {
         data = self.d1.encrypt(data);
         block.get_out().copy_from_slice(&data.to_be_bytes());
     }
+
+    #[inline]
+    fn encrypt_par_blocks(&self, blocks: InOut<'_, '_, ParBlocks<Self>>) {
+        bitslice::par_blocks(blocks, |state| {
+            state.encrypt(&self.d1.keys);
+            state.encrypt(&self.d2.keys);
+            state.encrypt(&self.d1.keys);
+        });
+    }
 }
 
 impl BlockCipherDecrypt for TdesEee2 {
@@ -325,6 +440,15 @@ impl BlockCipherDecBackend for TdesEee2 {
         data = self.d1.decrypt(data);
         block.get_out().copy_from_slice(&data.to_be_bytes());
     }
+
+    #[inline]
+    fn decrypt_par_blocks(&self, blocks: InOut<'_, '_, ParBlocks<Self>>) {
+        bitslice::par_blocks(blocks, |state| {
+            state.decrypt(&self.d1.keys);
+            state.decrypt(&self.d2.keys);
+            state.decrypt(&self.d1.keys);
+        });
+    }
 }
 
 impl fmt::Debug for TdesEee2 {
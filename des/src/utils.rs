@@ -132,6 +132,25 @@ pub(crate) fn round(input: u64, key: u64) -> u64 {
     r | ((f(r, key) ^ l) >> 32)
 }
 
+/// Same as [`round`], but swaps the bits of the E table output selected by
+/// `salt` between its two 24-bit halves, as done by Unix `crypt(3)`.
+///
+/// `salt` must only have bits set in the lower half of the E table output,
+/// i.e. in `0x0000_00FF_FFFF_0000`.
+#[cfg(feature = "crypt")]
+pub(crate) fn salted_round(input: u64, key: u64, salt: u64) -> u64 {
+    let l = input & (0xFFFF_FFFF << 32);
+    let r = input << 32;
+
+    let mut val = e(r);
+    let swap = ((val >> 24) ^ val) & salt;
+    val ^= swap | (swap << 24);
+    val ^= key;
+    val = apply_sboxes(val);
+
+    r | ((p(val) ^ l) >> 32)
+}
+
 fn f(input: u64, key: u64) -> u64 {
     let mut val = e(input);
     val ^= key;
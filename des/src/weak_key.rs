@@ -1,56 +1,215 @@
+use cipher::InvalidLength;
 use core::fmt;
 
+/// Mask which clears the parity bit (least significant bit) of every key byte.
+pub(crate) const PARITY_MASK: u64 = 0xFEFE_FEFE_FEFE_FEFE;
+
 /// Checks whether `key` contains one of the known [weak DES keys][TCG].
 ///
+/// Parity bits are ignored. Use [`classify_key`] to find out why a key was
+/// rejected without panicking on invalid key lengths.
+///
 /// [TCG]: https://trustedcomputinggroup.org/wp-content/uploads/TPM-2.0-1.83-Part-1-Architecture.pdf#page=82
 ///
 /// # Panics
 /// If `key` length is not equal to 8, 16, or 24.
 #[inline]
 pub fn weak_key_test(key: &[u8]) -> Result<(), WeakKeyError> {
-    assert!(
-        matches!(key.len(), 8 | 16 | 24),
-        "key size is not equal to 8, 16, or 24 bytes"
-    );
+    let report = classify_key(key).expect("key size is not equal to 8, 16, or 24 bytes");
+    match report.reason() {
+        None => Ok(()),
+        Some(reason) => Err(WeakKeyError { reason }),
+    }
+}
 
-    let mut is_weak = 0u8;
+/// Classify every DES sub-key of a DES or TDES `key` and check the TDES
+/// sub-keys for degenerate configurations.
+///
+/// Parity bits are ignored. All sub-keys and sub-key pairs are always
+/// checked, independently of their values.
+///
+/// Returns [`InvalidLength`] if `key` length is not equal to 8, 16, or 24.
+pub fn classify_key(key: &[u8]) -> Result<WeakKeyReport, InvalidLength> {
+    if !matches!(key.len(), 8 | 16 | 24) {
+        return Err(InvalidLength);
+    }
 
-    for subkey in key.chunks_exact(8) {
-        let key = u64::from_ne_bytes(subkey.try_into().expect("`subkey` length is equal to 8"));
-        for &weak_key in WEAK_KEYS {
-            is_weak |= u8::from(key == weak_key);
-        }
+    let mut subkeys = [0u64; 3];
+    let mut classes = [None; 3];
+    for (i, chunk) in key.chunks_exact(8).enumerate() {
+        let subkey: [u8; 8] = chunk.try_into().expect("`chunk` length is equal to 8");
+        subkeys[i] = u64::from_ne_bytes(subkey) & PARITY_MASK;
+        classes[i] = classify_subkey(subkeys[i]);
     }
+    let len = key.len() / 8;
 
-    match key.len() {
-        16 => {
-            is_weak |= u8::from(key[..8] == key[8..]);
-        }
-        24 => {
-            let k1 = &key[..8];
-            let k2 = &key[8..16];
-            let k3 = &key[16..];
-            is_weak |= u8::from(k1 == k2);
-            is_weak |= u8::from(k1 == k3);
-            is_weak |= u8::from(k2 == k3);
+    let mut equal = [false; 3];
+    for (eq, (a, b)) in equal.iter_mut().zip([(0, 1), (1, 2), (0, 2)]) {
+        *eq = b < len && subkeys[a] == subkeys[b];
+    }
+
+    let reason = classes[..len]
+        .iter()
+        .enumerate()
+        .find_map(|(index, class)| class.map(|class| WeakKeyReason::WeakSubkey { index, class }))
+        .or(match equal {
+            [true, _, _] => Some(WeakKeyReason::Degenerate {
+                first: 0,
+                second: 1,
+            }),
+            [_, true, _] => Some(WeakKeyReason::Degenerate {
+                first: 1,
+                second: 2,
+            }),
+            [_, _, true] => Some(WeakKeyReason::EqualOuterKeys),
+            _ => None,
+        });
+
+    Ok(WeakKeyReport {
+        classes,
+        len,
+        reason,
+    })
+}
+
+/// Returns 1 if `key` is one of [`WEAK_KEYS`] (ignoring parity bits) and 0 otherwise.
+#[inline]
+pub(crate) fn is_weak_subkey(key: [u8; 8]) -> u8 {
+    u8::from(classify_subkey(u64::from_ne_bytes(key) & PARITY_MASK).is_some())
+}
+
+/// Classify a DES key with cleared parity bits.
+///
+/// The key is compared against every entry of [`WEAK_KEYS`].
+fn classify_subkey(key: u64) -> Option<DesKeyClass> {
+    let mut class = 0u8;
+    for (i, &weak_key) in WEAK_KEYS.iter().enumerate() {
+        let kind = match i {
+            0..4 => 1,
+            4..16 => 2,
+            _ => 3,
+        };
+        class |= u8::from(key == weak_key & PARITY_MASK) * kind;
+    }
+    match class {
+        0 => None,
+        1 => Some(DesKeyClass::Weak),
+        2 => Some(DesKeyClass::SemiWeak),
+        _ => Some(DesKeyClass::PossiblyWeak),
+    }
+}
+
+/// Class of a weak DES key.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[non_exhaustive]
+pub enum DesKeyClass {
+    /// One of the 4 weak keys, for which encryption is an involution.
+    Weak,
+    /// One of the 12 semi-weak keys, which come in pairs where one key
+    /// decrypts what the other encrypts.
+    SemiWeak,
+    /// One of the 48 possibly weak keys, which generate only four distinct
+    /// round keys.
+    PossiblyWeak,
+}
+
+impl fmt::Display for DesKeyClass {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(match self {
+            Self::Weak => "weak",
+            Self::SemiWeak => "semi-weak",
+            Self::PossiblyWeak => "possibly weak",
+        })
+    }
+}
+
+/// Reason for a DES or TDES key being rejected.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[non_exhaustive]
+pub enum WeakKeyReason {
+    /// The DES sub-key with zero-based index `index` is weak.
+    WeakSubkey {
+        /// Index of the sub-key.
+        index: usize,
+        /// Class of the sub-key.
+        class: DesKeyClass,
+    },
+    /// Adjacent TDES sub-keys are equal (K1 = K2 or K2 = K3), which reduces
+    /// TDES to single DES.
+    Degenerate {
+        /// Zero-based index of the first sub-key.
+        first: usize,
+        /// Zero-based index of the second sub-key.
+        second: usize,
+    },
+    /// The outer sub-keys of a three-key TDES key are equal (K1 = K3), which
+    /// reduces it to two-key TDES.
+    EqualOuterKeys,
+}
+
+impl fmt::Display for WeakKeyReason {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            Self::WeakSubkey { index, class } => write!(f, "sub-key {index} is {class}"),
+            Self::Degenerate { first, second } => {
+                write!(f, "sub-keys {first} and {second} are equal")
+            }
+            Self::EqualOuterKeys => f.write_str("outer sub-keys are equal"),
         }
-        _ => {}
     }
+}
+
+/// Weak key classification of a DES or TDES key returned by [`classify_key`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct WeakKeyReport {
+    classes: [Option<DesKeyClass>; 3],
+    len: usize,
+    reason: Option<WeakKeyReason>,
+}
 
-    match is_weak {
-        0 => Ok(()),
-        _ => Err(WeakKeyError),
+impl WeakKeyReport {
+    /// Class of every DES sub-key in key order, `None` for keys which are not weak.
+    #[inline]
+    pub fn subkeys(&self) -> &[Option<DesKeyClass>] {
+        &self.classes[..self.len]
+    }
+
+    /// Reason for rejecting the key, `None` if the key is not weak.
+    ///
+    /// If the key is rejected for several reasons, weak sub-keys are reported
+    /// first (in key order), followed by degenerate TDES configurations.
+    #[inline]
+    pub fn reason(&self) -> Option<WeakKeyReason> {
+        self.reason
+    }
+
+    /// Returns `true` if the key is weak for any reason.
+    #[inline]
+    pub fn is_weak(&self) -> bool {
+        self.reason.is_some()
     }
 }
 
 /// The error type returned when a key is found to be weak.
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
-pub struct WeakKeyError;
+pub struct WeakKeyError {
+    reason: WeakKeyReason,
+}
+
+impl WeakKeyError {
+    /// Reason for the key being rejected.
+    #[inline]
+    pub fn reason(&self) -> WeakKeyReason {
+        self.reason
+    }
+}
 
 impl fmt::Display for WeakKeyError {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        f.write_str("WeakKey")
+        write!(f, "weak key: {}", self.reason)
     }
 }
 
@@ -62,6 +221,7 @@ macro_rules! as_ne_u64 {
     };
 }
 
+/// Weak (0..4), semi-weak (4..16) and possibly weak (16..64) DES keys.
 pub(crate) static WEAK_KEYS: &[u64; 64] = &as_ne_u64![
     [0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01],
     [0xFE, 0xFE, 0xFE, 0xFE, 0xFE, 0xFE, 0xFE, 0xFE],
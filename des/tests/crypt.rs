@@ -0,0 +1,80 @@
+//! Test vectors were generated using `crypt(3)` from libxcrypt.
+#![cfg(feature = "crypt")]
+
+use des::{CryptError, crypt_extended, crypt_traditional, crypt_verify};
+
+const TRADITIONAL: &[(&[u8], &str)] = &[
+    (b"", "..X8NBuQ4l6uQ"),
+    (b"test", "abgOeLfPimXQo"),
+    (b"password", "zzXUHfURnGg8I"),
+    (b"longerthan8chars", "Xy85q7XZycXzE"),
+    (b"\xff\x80high", ".//HumfQlabA."),
+    (b"U*U*U*U*", "CCNf8Sbh3HDfQ"),
+    // the password ends at the first NUL byte as in C
+    (b"test\0junk", "abgOeLfPimXQo"),
+];
+
+const EXTENDED: &[(&[u8], u32, &str)] = &[
+    (b"", 725, "_J9..CCCCBeguG7nmIew"),
+    (b"test", 725, "_J9..CCCCZBIc.TMGpK."),
+    (b"U*U*U*U*", 725, "_J9..CCCCXBrJUJV154M"),
+    (b"U*U*U*U*U*U*U*U*", 725, "_J9..CCCCU/lJbdRq55w"),
+    (
+        b"a very long password that is folded several times",
+        5000,
+        "_6C/.SALTBn1/KRSuZY2",
+    ),
+    (b"short", 1, "_/...abcdF6nD3ARO7pI"),
+    (b"\x80\xff", 262144, "_.../....1z7eFaW015Q"),
+    // the password ends at the first NUL byte as in C
+    (b"U*U*U*U*\0U*U*U*U*", 725, "_J9..CCCCXBrJUJV154M"),
+];
+
+#[test]
+fn traditional() {
+    for &(password, hash) in TRADITIONAL {
+        let salt = hash.as_bytes()[..2].try_into().unwrap();
+        let out = crypt_traditional(password, salt).unwrap();
+        assert_eq!(out, hash.as_bytes());
+        assert_eq!(crypt_verify(password, hash.as_bytes()), Ok(()));
+    }
+}
+
+#[test]
+fn extended() {
+    for &(password, rounds, hash) in EXTENDED {
+        let salt = hash.as_bytes()[5..9].try_into().unwrap();
+        let out = crypt_extended(password, rounds, salt).unwrap();
+        assert_eq!(out, hash.as_bytes());
+        assert_eq!(crypt_verify(password, hash.as_bytes()), Ok(()));
+    }
+}
+
+#[test]
+fn errors() {
+    assert_eq!(
+        crypt_verify(b"tset", b"abgOeLfPimXQo"),
+        Err(CryptError::PasswordMismatch)
+    );
+    assert_eq!(
+        crypt_verify(b"test", b"_J9..CCCCZBIc.TMGpK/"),
+        Err(CryptError::PasswordMismatch)
+    );
+    assert_eq!(
+        crypt_verify(b"test", b"abgOeLfPimXQ"),
+        Err(CryptError::InvalidHash)
+    );
+    assert_eq!(crypt_traditional(b"x", b"!!"), Err(CryptError::InvalidSalt));
+    assert_eq!(
+        crypt_extended(b"x", 0, b"CCCC"),
+        Err(CryptError::InvalidRounds)
+    );
+    assert_eq!(
+        crypt_extended(b"x", 1 << 24, b"CCCC"),
+        Err(CryptError::InvalidRounds)
+    );
+    assert_eq!(
+        crypt_extended(b"x", 1, b"CC!C"),
+        Err(CryptError::InvalidSalt)
+    );
+}
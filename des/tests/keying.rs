@@ -0,0 +1,72 @@
+use cipher::{Array, BlockCipherDecrypt, BlockCipherEncrypt, KeyInit};
+use des::{KeyingOption, TdesEde, TdesEde3};
+use hex_literal::hex;
+
+const K1: [u8; 8] = hex!("0123456789abcdef");
+const K2: [u8; 8] = hex!("23456789abcdef01");
+const K3: [u8; 8] = hex!("456789abcdef0123");
+// K1 with flipped parity bits
+const K1P: [u8; 8] = hex!("0022446688aaccee");
+
+#[test]
+fn detect() {
+    for (key, option) in [
+        ([K1].concat(), KeyingOption::Three),
+        ([K1, K2].concat(), KeyingOption::Two),
+        ([K1, K1P].concat(), KeyingOption::Three),
+        ([K1, K2, K3].concat(), KeyingOption::One),
+        ([K1, K2, K1].concat(), KeyingOption::Two),
+        ([K1, K2, K1P].concat(), KeyingOption::Two),
+        ([K1, K1, K1].concat(), KeyingOption::Three),
+        ([K1, K1, K3].concat(), KeyingOption::Three),
+        ([K1, K3, K3].concat(), KeyingOption::Three),
+    ] {
+        assert_eq!(KeyingOption::detect(&key), Ok(option), "{key:02x?}");
+        let cipher = TdesEde::new_from_slice(&key).unwrap();
+        assert_eq!(cipher.keying_option(), option);
+    }
+}
+
+#[test]
+fn invalid_length() {
+    for len in [0, 7, 9, 15, 17, 23, 25, 32] {
+        let key = vec![0x42; len];
+        assert!(KeyingOption::detect(&key).is_err());
+        assert!(TdesEde::new_from_slice(&key).is_err());
+        assert!(TdesEde::expand_key(&key).is_err());
+    }
+}
+
+#[test]
+fn expand_key() {
+    let expand = |key: &[u8]| TdesEde::expand_key(key).unwrap().to_vec();
+    assert_eq!(expand(&K1), [K1, K1, K1].concat());
+    assert_eq!(expand(&[K1, K2].concat()), [K1, K2, K1].concat());
+    assert_eq!(expand(&[K1, K2, K3].concat()), [K1, K2, K3].concat());
+}
+
+#[test]
+fn equivalent_to_ede3() {
+    let mut blocks: Vec<_> = (0..100u8).map(|i| Array([i; 8])).collect();
+    blocks[1] = Array(hex!("4e6f772069732074"));
+
+    for key in [
+        [K1].concat(),
+        [K1, K2].concat(),
+        [K1, K2, K3].concat(),
+        [K1, K1P, K3].concat(),
+        [K1, K3, K3].concat(),
+    ] {
+        let reference = TdesEde3::new(&TdesEde::expand_key(&key).unwrap());
+        let cipher = TdesEde::new_from_slice(&key).unwrap();
+
+        let mut expected = blocks.clone();
+        reference.encrypt_blocks(&mut expected);
+        let mut buf = blocks.clone();
+        cipher.encrypt_blocks(&mut buf);
+        assert_eq!(buf, expected, "{key:02x?}");
+
+        cipher.decrypt_blocks(&mut buf);
+        assert_eq!(buf, blocks, "{key:02x?}");
+    }
+}
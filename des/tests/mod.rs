@@ -4,3 +4,29 @@
 cipher::block_cipher_test!(des, des::Des);
 cipher::block_cipher_test!(tdes, des::TdesEde3);
 cipher::block_cipher_test!(tdes2, des::TdesEde2);
+
+macro_rules! par_blocks_test {
+    ($name:ident, $cipher:ty) => {
+        #[test]
+        fn $name() {
+            use cipher::{Array, BlockCipherDecrypt, BlockCipherEncrypt, KeyInit};
+
+            let key = Array::from_fn(|i| (i as u8).wrapping_mul(0x3d));
+            let cipher = <$cipher>::new(&key);
+
+            let plaintext: [_; 129] =
+                core::array::from_fn(|i| Array::from((i as u64).to_be_bytes()));
+            let mut blocks1 = plaintext;
+            let mut blocks2 = plaintext;
+            cipher.encrypt_blocks(&mut blocks1);
+            blocks2.iter_mut().for_each(|b| cipher.encrypt_block(b));
+            assert_eq!(blocks1, blocks2);
+
+            cipher.decrypt_blocks(&mut blocks1);
+            assert_eq!(blocks1, plaintext);
+        }
+    };
+}
+
+par_blocks_test!(tdes_eee2_par_blocks, des::TdesEee2);
+par_blocks_test!(tdes_eee3_par_blocks, des::TdesEee3);
@@ -0,0 +1,74 @@
+use cipher::Array;
+use des::{Des, KeyError, ParityError, TdesEde2, TdesEde3, check_odd_parity, set_odd_parity};
+use hex_literal::hex;
+
+#[test]
+fn odd_parity() {
+    let mut key = hex!("0022446688aaccee");
+    assert_eq!(check_odd_parity(&key), Err(ParityError));
+    set_odd_parity(&mut key);
+    assert_eq!(key, hex!("0123456789abcdef"));
+    assert_eq!(check_odd_parity(&key), Ok(()));
+
+    let mut key = hex!("133457799bbcdff1 0000000000000000 fefefefefefefefe");
+    set_odd_parity(&mut key);
+    assert_eq!(
+        key,
+        hex!("133457799bbcdff1 0101010101010101 fefefefefefefefe")
+    );
+    assert_eq!(check_odd_parity(&key), Ok(()));
+}
+
+#[test]
+fn des_new_checked() {
+    let key = Array::from(hex!("133457799bbcdff1"));
+    assert!(Des::new_checked(&key).is_ok());
+
+    let key = Array::from(hex!("133457799bbcdff0"));
+    assert_eq!(Des::new_checked(&key).err(), Some(KeyError::Parity));
+
+    let key = Array::from(hex!("0101010101010101"));
+    assert_eq!(Des::new_checked(&key).err(), Some(KeyError::Weak));
+
+    let key = Array::from(hex!("01fe01fe01fe01fe"));
+    assert_eq!(Des::new_checked(&key).err(), Some(KeyError::Weak));
+}
+
+#[test]
+fn tdes_new_checked() {
+    let k1 = hex!("0123456789abcdef");
+    let k2 = hex!("23456789abcdef01");
+    let k3 = hex!("456789abcdef0123");
+
+    let key = Array::try_from(&[k1, k2, k3].concat()[..]).unwrap();
+    assert!(TdesEde3::new_checked(&key).is_ok());
+    // Keying option 2
+    let key = Array::try_from(&[k1, k2, k1].concat()[..]).unwrap();
+    assert!(TdesEde3::new_checked(&key).is_ok());
+
+    let key = Array::try_from(&[k1, k1, k3].concat()[..]).unwrap();
+    assert_eq!(
+        TdesEde3::new_checked(&key).err(),
+        Some(KeyError::Degenerate)
+    );
+    let key = Array::try_from(&[k1, k2, k2].concat()[..]).unwrap();
+    assert_eq!(
+        TdesEde3::new_checked(&key).err(),
+        Some(KeyError::Degenerate)
+    );
+
+    let key = Array::try_from(&[k1, k1].concat()[..]).unwrap();
+    assert_eq!(
+        TdesEde2::new_checked(&key).err(),
+        Some(KeyError::Degenerate)
+    );
+    let key = Array::try_from(&[k1, hex!("e0e0e0e0f1f1f1f1")].concat()[..]).unwrap();
+    assert_eq!(TdesEde2::new_checked(&key).err(), Some(KeyError::Weak));
+
+    // Parity errors take precedence
+    let key = Array::try_from(&[k1, k1, hex!("0000000000000000")].concat()[..]).unwrap();
+    assert_eq!(TdesEde3::new_checked(&key).err(), Some(KeyError::Parity));
+
+    let key = Array::try_from(&[k1, k2].concat()[..]).unwrap();
+    assert!(TdesEde2::new_checked(&key).is_ok());
+}
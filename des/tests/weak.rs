@@ -1,4 +1,4 @@
-use des::weak_key_test;
+use des::{DesKeyClass, WeakKeyReason, classify_key, weak_key_test};
 use hex_literal::hex;
 
 #[test]
@@ -31,3 +31,96 @@ fn weak_des() {
         assert!(weak_key_test(k).is_err());
     }
 }
+
+#[test]
+fn classify_des() {
+    for (k, class) in [
+        (hex!("0101010101010101"), DesKeyClass::Weak),
+        (hex!("0000000000000000"), DesKeyClass::Weak),
+        (hex!("1f1f1f1f0e0e0e0e"), DesKeyClass::Weak),
+        (hex!("01fe01fe01fe01fe"), DesKeyClass::SemiWeak),
+        (hex!("e0fee0fef1fef1fe"), DesKeyClass::SemiWeak),
+        (hex!("1f1f01010e0e0101"), DesKeyClass::PossiblyWeak),
+        (hex!("fee0e0fefef1f1fe"), DesKeyClass::PossiblyWeak),
+    ] {
+        let report = classify_key(&k).unwrap();
+        assert_eq!(report.subkeys(), &[Some(class)]);
+        let reason = WeakKeyReason::WeakSubkey { index: 0, class };
+        assert_eq!(report.reason(), Some(reason));
+        assert_eq!(weak_key_test(&k).unwrap_err().reason(), reason);
+    }
+
+    let report = classify_key(&hex!("0123456789abcdef")).unwrap();
+    assert_eq!(report.subkeys(), &[None]);
+    assert!(!report.is_weak());
+    assert!(weak_key_test(&hex!("0123456789abcdef")).is_ok());
+}
+
+#[test]
+fn classify_tdes() {
+    let report = classify_key(&hex!("0123456789abcdef01fe01fe01fe01fe1f1f01010e0e0101")).unwrap();
+    assert_eq!(
+        report.subkeys(),
+        &[
+            None,
+            Some(DesKeyClass::SemiWeak),
+            Some(DesKeyClass::PossiblyWeak)
+        ]
+    );
+    assert_eq!(
+        report.reason(),
+        Some(WeakKeyReason::WeakSubkey {
+            index: 1,
+            class: DesKeyClass::SemiWeak
+        })
+    );
+
+    for (k, reason) in [
+        (
+            &hex!("0123456789abcdef0123456789abcdef")[..],
+            WeakKeyReason::Degenerate {
+                first: 0,
+                second: 1,
+            },
+        ),
+        (
+            &hex!("0123456789abcdef0123456789abcdef1112131415161718"),
+            WeakKeyReason::Degenerate {
+                first: 0,
+                second: 1,
+            },
+        ),
+        (
+            &hex!("11121314151617180123456789abcdef0123456789abcdef"),
+            WeakKeyReason::Degenerate {
+                first: 1,
+                second: 2,
+            },
+        ),
+        (
+            &hex!("0123456789abcdef11121314151617180123456789abcdef"),
+            WeakKeyReason::EqualOuterKeys,
+        ),
+        (
+            // Keys differing only in parity bits are equal
+            &hex!("0123456789abcdef11121314151617180022446688aaccee"),
+            WeakKeyReason::EqualOuterKeys,
+        ),
+    ] {
+        let report = classify_key(k).unwrap();
+        assert!(report.subkeys().iter().all(Option::is_none));
+        assert_eq!(report.reason(), Some(reason));
+        assert_eq!(weak_key_test(k).unwrap_err().reason(), reason);
+    }
+
+    let k = hex!("0123456789abcdef1112131415161718fedcba9876543210");
+    assert!(!classify_key(&k).unwrap().is_weak());
+    assert!(weak_key_test(&k).is_ok());
+}
+
+#[test]
+fn classify_invalid_length() {
+    for len in [0, 7, 9, 15, 17, 23, 25, 32] {
+        assert!(classify_key(&vec![0x42; len]).is_err());
+    }
+}
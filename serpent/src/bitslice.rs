@@ -11,10 +11,51 @@
 //! "Speeding Up Serpent", Osvik
 //! <https://www.ii.uib.no/~osvik/pub/aes3.pdf>
 
-use crate::Words;
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Shl};
 
-#[inline]
-pub fn linear_transform(mut words: Words) -> Words {
+/// Word on which the S-box circuits and the linear transformation operate.
+///
+/// Every bit position of a word corresponds to an independent S-box input,
+/// so the same circuits process one block with `u32` words or several blocks
+/// with SIMD vectors of 32-bit lanes.
+pub trait Word:
+    Copy
+    + BitAnd<Output = Self>
+    + BitAndAssign
+    + BitOr<Output = Self>
+    + BitOrAssign
+    + BitXor<Output = Self>
+    + BitXorAssign
+    + Not<Output = Self>
+    + Shl<u32, Output = Self>
+{
+    /// Create a word with every 32-bit lane set to `x`.
+    fn splat(x: u32) -> Self;
+    /// Rotate every 32-bit lane left by `n` bits.
+    fn rotate_left(self, n: u32) -> Self;
+    /// Rotate every 32-bit lane right by `n` bits.
+    fn rotate_right(self, n: u32) -> Self;
+}
+
+impl Word for u32 {
+    #[inline(always)]
+    fn splat(x: u32) -> Self {
+        x
+    }
+
+    #[inline(always)]
+    fn rotate_left(self, n: u32) -> Self {
+        u32::rotate_left(self, n)
+    }
+
+    #[inline(always)]
+    fn rotate_right(self, n: u32) -> Self {
+        u32::rotate_right(self, n)
+    }
+}
+
+#[inline(always)]
+pub fn linear_transform<W: Word>(mut words: [W; 4]) -> [W; 4] {
     words[0] = words[0].rotate_left(13);
     words[2] = words[2].rotate_left(3);
     words[1] ^= words[0] ^ words[2];
@@ -28,8 +69,8 @@ pub fn linear_transform(mut words: Words) -> Words {
     words
 }
 
-#[inline]
-pub fn linear_transform_inv(mut words: Words) -> Words {
+#[inline(always)]
+pub fn linear_transform_inv<W: Word>(mut words: [W; 4]) -> [W; 4] {
     words[2] = words[2].rotate_right(22);
     words[0] = words[0].rotate_right(5);
     words[2] = words[2] ^ words[3] ^ (words[1] << 7);
@@ -43,8 +84,8 @@ pub fn linear_transform_inv(mut words: Words) -> Words {
     words
 }
 
-#[inline]
-pub fn apply_s(index: usize, [w1, w2, w3, w4]: Words) -> Words {
+#[inline(always)]
+pub fn apply_s<W: Word>(index: usize, [w1, w2, w3, w4]: [W; 4]) -> [W; 4] {
     match index % 8 {
         0 => sbox_e0([w1, w2, w3, w4]),
         1 => sbox_e1([w1, w2, w3, w4]),
@@ -58,8 +99,8 @@ pub fn apply_s(index: usize, [w1, w2, w3, w4]: Words) -> Words {
     }
 }
 
-#[inline]
-pub fn apply_s_inv(index: usize, [w1, w2, w3, w4]: Words) -> Words {
+#[inline(always)]
+pub fn apply_s_inv<W: Word>(index: usize, [w1, w2, w3, w4]: [W; 4]) -> [W; 4] {
     match index % 8 {
         0 => sbox_d0([w1, w2, w3, w4]),
         1 => sbox_d1([w1, w2, w3, w4]),
@@ -73,8 +114,8 @@ pub fn apply_s_inv(index: usize, [w1, w2, w3, w4]: Words) -> Words {
     }
 }
 
-#[inline]
-const fn sbox_e0([mut w1, mut w2, mut w3, mut w4]: Words) -> Words {
+#[inline(always)]
+fn sbox_e0<W: Word>([mut w1, mut w2, mut w3, mut w4]: [W; 4]) -> [W; 4] {
     w4 ^= w1;
     let mut t0 = w2;
     w2 &= w4;
@@ -96,8 +137,8 @@ const fn sbox_e0([mut w1, mut w2, mut w3, mut w4]: Words) -> Words {
     [w2, t0, w3, w1]
 }
 
-#[inline]
-const fn sbox_e1([mut w1, mut w2, mut w3, mut w4]: Words) -> Words {
+#[inline(always)]
+fn sbox_e1<W: Word>([mut w1, mut w2, mut w3, mut w4]: [W; 4]) -> [W; 4] {
     w1 = !w1;
     w3 = !w3;
     let mut t0 = w1;
@@ -119,8 +160,8 @@ const fn sbox_e1([mut w1, mut w2, mut w3, mut w4]: Words) -> Words {
     [w3, t0, w4, w2]
 }
 
-#[inline]
-const fn sbox_e2([mut w1, mut w2, mut w3, mut w4]: Words) -> Words {
+#[inline(always)]
+fn sbox_e2<W: Word>([mut w1, mut w2, mut w3, mut w4]: [W; 4]) -> [W; 4] {
     let mut t0 = w1;
     w1 &= w3;
     w1 ^= w4;
@@ -140,8 +181,8 @@ const fn sbox_e2([mut w1, mut w2, mut w3, mut w4]: Words) -> Words {
     [w3, w4, w2, t0]
 }
 
-#[inline]
-const fn sbox_e3([mut w1, mut w2, mut w3, mut w4]: Words) -> Words {
+#[inline(always)]
+fn sbox_e3<W: Word>([mut w1, mut w2, mut w3, mut w4]: [W; 4]) -> [W; 4] {
     let mut t0 = w1;
     w1 |= w4;
     w4 ^= w2;
@@ -164,8 +205,8 @@ const fn sbox_e3([mut w1, mut w2, mut w3, mut w4]: Words) -> Words {
     [w1, w3, w4, t0]
 }
 
-#[inline]
-const fn sbox_e4([mut w1, mut w2, mut w3, mut w4]: Words) -> Words {
+#[inline(always)]
+fn sbox_e4<W: Word>([mut w1, mut w2, mut w3, mut w4]: [W; 4]) -> [W; 4] {
     w2 ^= w4;
     w4 = !w4;
     w3 ^= w4;
@@ -189,8 +230,8 @@ const fn sbox_e4([mut w1, mut w2, mut w3, mut w4]: Words) -> Words {
     [w2, t0, w1, w4]
 }
 
-#[inline]
-const fn sbox_e5([mut w1, mut w2, mut w3, mut w4]: Words) -> Words {
+#[inline(always)]
+fn sbox_e5<W: Word>([mut w1, mut w2, mut w3, mut w4]: [W; 4]) -> [W; 4] {
     w1 ^= w2;
     w2 ^= w4;
     w4 = !w4;
@@ -217,8 +258,8 @@ const fn sbox_e5([mut w1, mut w2, mut w3, mut w4]: Words) -> Words {
     [w1, w2, w3, w4]
 }
 
-#[inline]
-const fn sbox_e6([mut w1, mut w2, mut w3, mut w4]: Words) -> Words {
+#[inline(always)]
+fn sbox_e6<W: Word>([mut w1, mut w2, mut w3, mut w4]: [W; 4]) -> [W; 4] {
     w3 = !w3;
     let mut t0 = w4;
     w4 &= w1;
@@ -241,8 +282,8 @@ const fn sbox_e6([mut w1, mut w2, mut w3, mut w4]: Words) -> Words {
     [w1, w2, w3, w4]
 }
 
-#[inline]
-const fn sbox_e7([mut w1, mut w2, mut w3, mut w4]: Words) -> Words {
+#[inline(always)]
+fn sbox_e7<W: Word>([mut w1, mut w2, mut w3, mut w4]: [W; 4]) -> [W; 4] {
     let mut t0 = w2;
     w2 |= w3;
     w2 ^= w4;
@@ -266,8 +307,8 @@ const fn sbox_e7([mut w1, mut w2, mut w3, mut w4]: Words) -> Words {
     [t0, w4, w2, w1]
 }
 
-#[inline]
-const fn sbox_d0([mut w1, mut w2, mut w3, mut w4]: Words) -> Words {
+#[inline(always)]
+fn sbox_d0<W: Word>([mut w1, mut w2, mut w3, mut w4]: [W; 4]) -> [W; 4] {
     w3 = !w3;
     let mut t0 = w2;
     w2 |= w1;
@@ -290,8 +331,8 @@ const fn sbox_d0([mut w1, mut w2, mut w3, mut w4]: Words) -> Words {
     [w1, t0, w2, w4]
 }
 
-#[inline]
-const fn sbox_d1([mut w1, mut w2, mut w3, mut w4]: Words) -> Words {
+#[inline(always)]
+fn sbox_d1<W: Word>([mut w1, mut w2, mut w3, mut w4]: [W; 4]) -> [W; 4] {
     let mut t0 = w2;
     w2 ^= w4;
     w4 &= w2;
@@ -314,8 +355,8 @@ const fn sbox_d1([mut w1, mut w2, mut w3, mut w4]: Words) -> Words {
     [t0, w1, w4, w3]
 }
 
-#[inline]
-const fn sbox_d2([mut w1, mut w2, mut w3, mut w4]: Words) -> Words {
+#[inline(always)]
+fn sbox_d2<W: Word>([mut w1, mut w2, mut w3, mut w4]: [W; 4]) -> [W; 4] {
     w3 ^= w4;
     w4 ^= w1;
     let mut t0 = w4;
@@ -338,8 +379,8 @@ const fn sbox_d2([mut w1, mut w2, mut w3, mut w4]: Words) -> Words {
     [w2, t0, w3, w4]
 }
 
-#[inline]
-const fn sbox_d3([mut w1, mut w2, mut w3, mut w4]: Words) -> Words {
+#[inline(always)]
+fn sbox_d3<W: Word>([mut w1, mut w2, mut w3, mut w4]: [W; 4]) -> [W; 4] {
     let mut t0 = w3;
     w3 ^= w2;
     w1 ^= w3;
@@ -361,8 +402,8 @@ const fn sbox_d3([mut w1, mut w2, mut w3, mut w4]: Words) -> Words {
     [w3, w2, w4, w1]
 }
 
-#[inline]
-const fn sbox_d4([mut w1, mut w2, mut w3, mut w4]: Words) -> Words {
+#[inline(always)]
+fn sbox_d4<W: Word>([mut w1, mut w2, mut w3, mut w4]: [W; 4]) -> [W; 4] {
     let mut t0 = w3;
     w3 &= w4;
     w3 ^= w2;
@@ -386,8 +427,8 @@ const fn sbox_d4([mut w1, mut w2, mut w3, mut w4]: Words) -> Words {
     [w1, w4, w3, t0]
 }
 
-#[inline]
-const fn sbox_d5([w1, mut w2, mut w3, mut w4]: Words) -> Words {
+#[inline(always)]
+fn sbox_d5<W: Word>([w1, mut w2, mut w3, mut w4]: [W; 4]) -> [W; 4] {
     w2 = !w2;
     let mut t0 = w4;
     w3 ^= w2;
@@ -410,8 +451,8 @@ const fn sbox_d5([w1, mut w2, mut w3, mut w4]: Words) -> Words {
     [w2, t0, w4, w3]
 }
 
-#[inline]
-const fn sbox_d6([mut w1, mut w2, mut w3, mut w4]: Words) -> Words {
+#[inline(always)]
+fn sbox_d6<W: Word>([mut w1, mut w2, mut w3, mut w4]: [W; 4]) -> [W; 4] {
     w1 ^= w3;
     let mut t0 = w3;
     w3 &= w1;
@@ -432,8 +473,8 @@ const fn sbox_d6([mut w1, mut w2, mut w3, mut w4]: Words) -> Words {
     [w2, w3, t0, w4]
 }
 
-#[inline]
-const fn sbox_d7([mut w1, mut w2, mut w3, mut w4]: Words) -> Words {
+#[inline(always)]
+fn sbox_d7<W: Word>([mut w1, mut w2, mut w3, mut w4]: [W; 4]) -> [W; 4] {
     let mut t0 = w3;
     w3 ^= w1;
     w1 &= w4;
@@ -0,0 +1,104 @@
+use crate::Serpent;
+use cipher::{
+    AlgorithmName, BlockCipherDecClosure, BlockCipherDecrypt, BlockCipherEncClosure,
+    BlockCipherEncrypt, BlockSizeUser, InvalidLength, Key, KeyInit, KeySizeUser,
+    array::ArraySize,
+    consts::{U16, U24, U32},
+};
+use core::{fmt, marker::PhantomData};
+
+#[cfg(feature = "zeroize")]
+use cipher::zeroize::ZeroizeOnDrop;
+
+/// Serpent-128 block cipher.
+pub type Serpent128 = SerpentFixed<U16>;
+/// Serpent-192 block cipher.
+pub type Serpent192 = SerpentFixed<U24>;
+/// Serpent-256 block cipher.
+pub type Serpent256 = SerpentFixed<U32>;
+
+/// Key size supported by [`SerpentFixed`].
+///
+/// This trait is sealed and implemented only for [`U16`], [`U24`] and [`U32`].
+pub trait SerpentKeySize: ArraySize + private::Sealed {}
+
+impl SerpentKeySize for U16 {}
+impl SerpentKeySize for U24 {}
+impl SerpentKeySize for U32 {}
+
+mod private {
+    use cipher::consts::{U16, U24, U32};
+
+    pub trait Sealed {}
+
+    impl Sealed for U16 {}
+    impl Sealed for U24 {}
+    impl Sealed for U32 {}
+}
+
+/// Serpent block cipher which accepts only keys of exactly `KS` bytes.
+///
+/// Unlike [`Serpent`], which accepts any key of 16 to 32 bytes in
+/// [`KeyInit::new_from_slice`], this type rejects keys of any other length.
+/// Use the [`Serpent128`], [`Serpent192`] and [`Serpent256`] aliases.
+#[derive(Clone)]
+pub struct SerpentFixed<KS: SerpentKeySize> {
+    inner: Serpent,
+    _ks: PhantomData<KS>,
+}
+
+impl<KS: SerpentKeySize> KeySizeUser for SerpentFixed<KS> {
+    type KeySize = KS;
+}
+
+impl<KS: SerpentKeySize> KeyInit for SerpentFixed<KS> {
+    #[inline]
+    fn new(key: &Key<Self>) -> Self {
+        Self::new_from_slice(key).unwrap()
+    }
+
+    #[inline]
+    fn new_from_slice(key: &[u8]) -> Result<Self, InvalidLength> {
+        if key.len() != KS::USIZE {
+            return Err(InvalidLength);
+        }
+        let inner = Serpent::new_with_key_bits(key, 8 * key.len())?;
+        Ok(Self {
+            inner,
+            _ks: PhantomData,
+        })
+    }
+}
+
+impl<KS: SerpentKeySize> BlockSizeUser for SerpentFixed<KS> {
+    type BlockSize = U16;
+}
+
+impl<KS: SerpentKeySize> BlockCipherEncrypt for SerpentFixed<KS> {
+    #[inline]
+    fn encrypt_with_backend(&self, f: impl BlockCipherEncClosure<BlockSize = Self::BlockSize>) {
+        self.inner.encrypt_with_backend(f)
+    }
+}
+
+impl<KS: SerpentKeySize> BlockCipherDecrypt for SerpentFixed<KS> {
+    #[inline]
+    fn decrypt_with_backend(&self, f: impl BlockCipherDecClosure<BlockSize = Self::BlockSize>) {
+        self.inner.decrypt_with_backend(f)
+    }
+}
+
+impl<KS: SerpentKeySize> fmt::Debug for SerpentFixed<KS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Serpent{} {{ ... }}", 8 * KS::USIZE)
+    }
+}
+
+impl<KS: SerpentKeySize> AlgorithmName for SerpentFixed<KS> {
+    fn write_alg_name(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Serpent{}", 8 * KS::USIZE)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<KS: SerpentKeySize> ZeroizeOnDrop for SerpentFixed<KS> {}
@@ -8,6 +8,19 @@
 //!
 //! USE AT YOUR OWN RISK!
 //!
+//! # Parallel processing
+//! When encrypting or decrypting multiple blocks (e.g. via `encrypt_blocks`),
+//! batches of 8 blocks are processed at once by evaluating the bitsliced
+//! S-box circuits on SIMD lanes. On x86 targets AVX2 is used if it is
+//! detected at runtime.
+//!
+//! # Byte order
+//! [`Serpent`] uses the byte order of the NESSIE test vectors, which is also
+//! used by libgcrypt, Nettle and the Linux `serpent` driver. The AES
+//! submission reference implementation and the Linux `tnepres` driver use
+//! the reversed byte order for keys and blocks, which is implemented by
+//! [`Tnepres`].
+//!
 //! [Serpent]: https://en.wikipedia.org/wiki/Serpent_(cipher)
 
 #![no_std]
@@ -26,14 +39,29 @@ use cipher::{
     AlgorithmName, Block, BlockCipherDecBackend, BlockCipherDecClosure, BlockCipherDecrypt,
     BlockCipherEncBackend, BlockCipherEncClosure, BlockCipherEncrypt, BlockSizeUser, InOut,
     InvalidLength, KeyInit, KeySizeUser, ParBlocksSizeUser,
-    consts::{U1, U16},
+    consts::{U8, U16},
 };
 use core::fmt;
 
 mod bitslice;
+mod fixed;
+#[cfg(any(
+    all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "sse2"
+    ),
+    all(target_arch = "aarch64", target_feature = "neon"),
+))]
+mod simd;
+mod tnepres;
 #[macro_use]
 mod unroll;
 
+use bitslice::Word;
+
+pub use fixed::{Serpent128, Serpent192, Serpent256, SerpentFixed, SerpentKeySize};
+pub use tnepres::Tnepres;
+
 #[cfg(feature = "zeroize")]
 use cipher::zeroize::{Zeroize, ZeroizeOnDrop};
 
@@ -50,40 +78,76 @@ pub struct Serpent {
 }
 
 #[inline(always)]
-fn xor(b1: Words, k: Words) -> Words {
-    let mut res = [0u32; 4];
+fn xor<W: Word>(b1: [W; 4], k: Words) -> [W; 4] {
+    let mut res = b1;
     for (i, _) in b1.iter().enumerate() {
-        res[i] = b1[i] ^ k[i];
+        res[i] = b1[i] ^ W::splat(k[i]);
     }
     res
 }
 
+#[inline(always)]
+fn encrypt<W: Word>(round_keys: &RoundKeys, mut b: [W; 4]) -> [W; 4] {
+    unroll31!(i, {
+        let xb = xor(b, round_keys[i]);
+        let s = bitslice::apply_s(i, xb);
+        b = bitslice::linear_transform(s);
+    });
+
+    let xb = xor(b, round_keys[ROUNDS - 1]);
+    let s = bitslice::apply_s(ROUNDS - 1, xb);
+    xor(s, round_keys[ROUNDS])
+}
+
+#[inline(always)]
+fn decrypt<W: Word>(round_keys: &RoundKeys, mut b: [W; 4]) -> [W; 4] {
+    let s = xor(b, round_keys[ROUNDS]);
+    let xb = bitslice::apply_s_inv(ROUNDS - 1, s);
+    b = xor(xb, round_keys[ROUNDS - 1]);
+
+    unroll31!(i, {
+        let i = 30 - i;
+        let s = bitslice::linear_transform_inv(b);
+        let xb = bitslice::apply_s_inv(i, s);
+        b = xor(xb, round_keys[i]);
+    });
+
+    b
+}
+
+/// Pad a key of `len_bits` bits to 256 bits by appending a single one bit.
+/// Bits of `source` above `len_bits` are ignored.
 fn expand_key(source: &[u8], len_bits: usize) -> [u8; 32] {
     let mut key = [0u8; 32];
     key[..source.len()].copy_from_slice(source);
     if len_bits < 256 {
         let byte_i = len_bits / 8;
         let bit_i = len_bits % 8;
+        key[byte_i] &= (1 << bit_i) - 1;
         key[byte_i] |= 1 << bit_i;
     }
     key
 }
 
-impl KeySizeUser for Serpent {
-    type KeySize = U16;
-}
-
-impl KeyInit for Serpent {
-    fn new(key: &cipher::Key<Self>) -> Self {
-        Self::new_from_slice(key).unwrap()
-    }
-
-    fn new_from_slice(key: &[u8]) -> Result<Self, InvalidLength> {
-        if key.len() < 16 || key.len() > 32 {
+impl Serpent {
+    /// Create a new Serpent instance from a key of `key_bits` bits.
+    ///
+    /// Serpent accepts any key length up to 256 bits, including lengths
+    /// which are not a multiple of 8. `key` must be exactly
+    /// `key_bits.div_ceil(8)` bytes long, bits are numbered starting from
+    /// the least significant bit of the first byte (i.e. the same byte order
+    /// as used by [`KeyInit`]), and unused bits of the last byte are ignored.
+    ///
+    /// This is intended for interoperability with legacy short keys, e.g.
+    /// 40-bit keys; such keys offer no meaningful security.
+    pub fn new_with_key_bits(key: &[u8], key_bits: usize) -> Result<Self, InvalidLength> {
+        if key_bits > 256 || key.len() != key_bits.div_ceil(8) {
             return Err(InvalidLength);
         }
-        let key = expand_key(key, key.len() * 8);
+        Ok(Self::from_expanded_key(&expand_key(key, key_bits)))
+    }
 
+    fn from_expanded_key(key: &[u8; 32]) -> Self {
         let mut words = [0u32; 140];
 
         for (src, dst) in key.chunks_exact(4).zip(words[..8].iter_mut()) {
@@ -119,7 +183,24 @@ impl KeyInit for Serpent {
             dst.copy_from_slice(src);
         }
 
-        Ok(Serpent { round_keys })
+        Serpent { round_keys }
+    }
+}
+
+impl KeySizeUser for Serpent {
+    type KeySize = U16;
+}
+
+impl KeyInit for Serpent {
+    fn new(key: &cipher::Key<Self>) -> Self {
+        Self::new_from_slice(key).unwrap()
+    }
+
+    fn new_from_slice(key: &[u8]) -> Result<Self, InvalidLength> {
+        if key.len() < 16 || key.len() > 32 {
+            return Err(InvalidLength);
+        }
+        Ok(Self::from_expanded_key(&expand_key(key, key.len() * 8)))
     }
 }
 
@@ -128,7 +209,7 @@ impl BlockSizeUser for Serpent {
 }
 
 impl ParBlocksSizeUser for Serpent {
-    type ParBlocksSize = U1;
+    type ParBlocksSize = U8;
 }
 
 impl BlockCipherEncrypt for Serpent {
@@ -141,19 +222,20 @@ impl BlockCipherEncrypt for Serpent {
 impl BlockCipherEncBackend for Serpent {
     #[inline]
     fn encrypt_block(&self, mut block: InOut<'_, '_, Block<Self>>) {
-        let mut b: [u32; 4] = read_words(block.get_in().into());
-
-        unroll31!(i, {
-            let xb = xor(b, self.round_keys[i]);
-            let s = bitslice::apply_s(i, xb);
-            b = bitslice::linear_transform(s);
-        });
-
-        let xb = xor(b, self.round_keys[ROUNDS - 1]);
-        let s = bitslice::apply_s(ROUNDS - 1, xb);
-        b = xor(s, self.round_keys[ROUNDS]);
+        let b: [u32; 4] = read_words(block.get_in().into());
+        write_words(&encrypt(&self.round_keys, b), block.get_out().into());
+    }
 
-        write_words(&b, block.get_out().into());
+    #[cfg(any(
+        all(
+            any(target_arch = "x86", target_arch = "x86_64"),
+            target_feature = "sse2"
+        ),
+        all(target_arch = "aarch64", target_feature = "neon"),
+    ))]
+    #[inline]
+    fn encrypt_par_blocks(&self, blocks: InOut<'_, '_, cipher::ParBlocks<Self>>) {
+        simd::encrypt_par(&self.round_keys, blocks);
     }
 }
 
@@ -167,20 +249,20 @@ impl BlockCipherDecrypt for Serpent {
 impl BlockCipherDecBackend for Serpent {
     #[inline]
     fn decrypt_block(&self, mut block: InOut<'_, '_, Block<Self>>) {
-        let mut b: [u32; 4] = read_words(block.get_in().into());
-
-        let s = xor(b, self.round_keys[ROUNDS]);
-        let xb = bitslice::apply_s_inv(ROUNDS - 1, s);
-        b = xor(xb, self.round_keys[ROUNDS - 1]);
-
-        unroll31!(i, {
-            let i = 30 - i;
-            let s = bitslice::linear_transform_inv(b);
-            let xb = bitslice::apply_s_inv(i, s);
-            b = xor(xb, self.round_keys[i]);
-        });
+        let b: [u32; 4] = read_words(block.get_in().into());
+        write_words(&decrypt(&self.round_keys, b), block.get_out().into());
+    }
 
-        write_words(&b, block.get_out().into());
+    #[cfg(any(
+        all(
+            any(target_arch = "x86", target_arch = "x86_64"),
+            target_feature = "sse2"
+        ),
+        all(target_arch = "aarch64", target_feature = "neon"),
+    ))]
+    #[inline]
+    fn decrypt_par_blocks(&self, blocks: InOut<'_, '_, cipher::ParBlocks<Self>>) {
+        simd::decrypt_par(&self.round_keys, blocks);
     }
 }
 
@@ -0,0 +1,196 @@
+//! Multi-block processing using SIMD registers.
+//!
+//! The bitsliced S-box circuits operate on every bit position independently,
+//! so 8 blocks can be processed at once by replacing each `u32` word with a
+//! vector of 8 32-bit lanes holding the corresponding word of every block.
+
+use crate::{RoundKeys, Serpent, bitslice::Word, decrypt, encrypt};
+use cipher::{InOut, ParBlocks};
+
+/// Implement [`Word`] and [`Lanes`] for a type wrapping `$len` vectors of
+/// type `$vec`. The vector operations are passed as functions or closures
+/// calling the target-specific intrinsics. They are only safe to call when
+/// the required target features are enabled for the caller, which is why
+/// every call is wrapped into `unsafe`.
+macro_rules! impl_lanes {
+    (
+        $name:ident, $vec:ty, $len:expr,
+        and: $and:expr, or: $or:expr, xor: $xor:expr, not: $not:expr,
+        shl: $shl:expr, shr: $shr:expr, splat: $splat:expr,
+        load: $load:expr, store: $store:expr $(,)?
+    ) => {
+        #[derive(Copy, Clone)]
+        struct $name([$vec; $len]);
+
+        impl $name {
+            /// Number of 32-bit lanes per vector.
+            const STEP: usize = N / $len;
+
+            #[inline(always)]
+            fn map2(self, rhs: Self, f: impl Fn($vec, $vec) -> $vec) -> Self {
+                Self(core::array::from_fn(|i| f(self.0[i], rhs.0[i])))
+            }
+        }
+
+        impl core::ops::BitAnd for $name {
+            type Output = Self;
+
+            #[inline(always)]
+            fn bitand(self, rhs: Self) -> Self {
+                self.map2(rhs, |a, b| unsafe { ($and)(a, b) })
+            }
+        }
+
+        impl core::ops::BitOr for $name {
+            type Output = Self;
+
+            #[inline(always)]
+            fn bitor(self, rhs: Self) -> Self {
+                self.map2(rhs, |a, b| unsafe { ($or)(a, b) })
+            }
+        }
+
+        impl core::ops::BitXor for $name {
+            type Output = Self;
+
+            #[inline(always)]
+            fn bitxor(self, rhs: Self) -> Self {
+                self.map2(rhs, |a, b| unsafe { ($xor)(a, b) })
+            }
+        }
+
+        impl core::ops::BitAndAssign for $name {
+            #[inline(always)]
+            fn bitand_assign(&mut self, rhs: Self) {
+                *self = *self & rhs;
+            }
+        }
+
+        impl core::ops::BitOrAssign for $name {
+            #[inline(always)]
+            fn bitor_assign(&mut self, rhs: Self) {
+                *self = *self | rhs;
+            }
+        }
+
+        impl core::ops::BitXorAssign for $name {
+            #[inline(always)]
+            fn bitxor_assign(&mut self, rhs: Self) {
+                *self = *self ^ rhs;
+            }
+        }
+
+        impl core::ops::Not for $name {
+            type Output = Self;
+
+            #[inline(always)]
+            fn not(self) -> Self {
+                Self(self.0.map(|a| unsafe { ($not)(a) }))
+            }
+        }
+
+        impl core::ops::Shl<u32> for $name {
+            type Output = Self;
+
+            #[inline(always)]
+            fn shl(self, n: u32) -> Self {
+                Self(self.0.map(|a| unsafe { ($shl)(a, n) }))
+            }
+        }
+
+        impl Word for $name {
+            #[inline(always)]
+            fn splat(x: u32) -> Self {
+                Self([unsafe { ($splat)(x) }; $len])
+            }
+
+            #[inline(always)]
+            fn rotate_left(self, n: u32) -> Self {
+                Self(
+                    self.0
+                        .map(|a| unsafe { ($or)(($shl)(a, n), ($shr)(a, 32 - n)) }),
+                )
+            }
+
+            #[inline(always)]
+            fn rotate_right(self, n: u32) -> Self {
+                self.rotate_left(32 - n)
+            }
+        }
+
+        impl Lanes for $name {
+            #[inline(always)]
+            fn load(lanes: &[u32; N]) -> Self {
+                // SAFETY: `lanes` holds exactly `$len` vectors
+                Self(core::array::from_fn(|i| unsafe {
+                    ($load)(lanes.as_ptr().add(i * Self::STEP))
+                }))
+            }
+
+            #[inline(always)]
+            fn store(self, lanes: &mut [u32; N]) {
+                for (i, v) in self.0.into_iter().enumerate() {
+                    // SAFETY: `lanes` holds exactly `$len` vectors
+                    unsafe { ($store)(lanes.as_mut_ptr().add(i * Self::STEP), v) };
+                }
+            }
+        }
+    };
+}
+
+#[cfg(target_arch = "aarch64")]
+mod aarch64;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod x86;
+
+#[cfg(target_arch = "aarch64")]
+pub(crate) use aarch64::{decrypt_par, encrypt_par};
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub(crate) use x86::{decrypt_par, encrypt_par};
+
+/// Number of blocks processed in parallel.
+const N: usize = 8;
+
+/// Vector of `N` 32-bit lanes.
+trait Lanes: Word {
+    fn load(lanes: &[u32; N]) -> Self;
+    fn store(self, lanes: &mut [u32; N]);
+}
+
+/// Load word `j` of block `i` into lane `i` of vector `j`.
+#[inline(always)]
+fn load<L: Lanes>(blocks: &ParBlocks<Serpent>) -> [L; 4] {
+    let mut words = [[0u32; N]; 4];
+    for (i, block) in blocks.iter().enumerate() {
+        for (j, word) in block.chunks_exact(4).enumerate() {
+            words[j][i] = u32::from_le_bytes(word.try_into().unwrap());
+        }
+    }
+    words.map(|w| L::load(&w))
+}
+
+/// Inverse of [`load`].
+#[inline(always)]
+fn store<L: Lanes>(lanes: [L; 4], blocks: &mut ParBlocks<Serpent>) {
+    let mut words = [[0u32; N]; 4];
+    for (l, w) in lanes.into_iter().zip(words.iter_mut()) {
+        l.store(w);
+    }
+    for (i, block) in blocks.iter_mut().enumerate() {
+        for (j, word) in block.chunks_exact_mut(4).enumerate() {
+            word.copy_from_slice(&words[j][i].to_le_bytes());
+        }
+    }
+}
+
+#[inline(always)]
+fn encrypt_lanes<L: Lanes>(round_keys: &RoundKeys, mut blocks: InOut<'_, '_, ParBlocks<Serpent>>) {
+    let words = encrypt(round_keys, load::<L>(blocks.get_in()));
+    store(words, blocks.get_out());
+}
+
+#[inline(always)]
+fn decrypt_lanes<L: Lanes>(round_keys: &RoundKeys, mut blocks: InOut<'_, '_, ParBlocks<Serpent>>) {
+    let words = decrypt(round_keys, load::<L>(blocks.get_in()));
+    store(words, blocks.get_out());
+}
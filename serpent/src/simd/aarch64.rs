@@ -0,0 +1,30 @@
+//! NEON lanes.
+#![allow(unsafe_code)]
+
+use super::{Lanes, N, decrypt_lanes, encrypt_lanes};
+use crate::{RoundKeys, Serpent, bitslice::Word};
+use cipher::{InOut, ParBlocks};
+use core::arch::aarch64::*;
+
+/// Encrypt 8 blocks.
+#[inline]
+pub(crate) fn encrypt_par(round_keys: &RoundKeys, blocks: InOut<'_, '_, ParBlocks<Serpent>>) {
+    encrypt_lanes::<NeonLanes>(round_keys, blocks)
+}
+
+/// Decrypt 8 blocks.
+#[inline]
+pub(crate) fn decrypt_par(round_keys: &RoundKeys, blocks: InOut<'_, '_, ParBlocks<Serpent>>) {
+    decrypt_lanes::<NeonLanes>(round_keys, blocks)
+}
+
+// `vshlq_u32` shifts right for negative shift amounts
+impl_lanes!(
+    NeonLanes, uint32x4_t, 2,
+    and: vandq_u32, or: vorrq_u32, xor: veorq_u32, not: vmvnq_u32,
+    shl: |a, n: u32| vshlq_u32(a, vdupq_n_s32(n as i32)),
+    shr: |a, n: u32| vshlq_u32(a, vdupq_n_s32(-(n as i32))),
+    splat: vdupq_n_u32,
+    load: |p: *const u32| vld1q_u32(p),
+    store: |p: *mut u32, v| vst1q_u32(p, v),
+);
@@ -0,0 +1,70 @@
+//! SSE2 and AVX2 lanes. AVX2 support is detected at runtime.
+#![allow(unsafe_code)]
+
+use super::{Lanes, N, decrypt_lanes, encrypt_lanes};
+use crate::{RoundKeys, Serpent, bitslice::Word};
+use cipher::{InOut, ParBlocks};
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::*;
+
+cpufeatures::new!(avx2_cpuid, "avx2");
+
+#[cfg(test)]
+mod tests;
+
+/// Encrypt 8 blocks.
+#[inline]
+pub(crate) fn encrypt_par(round_keys: &RoundKeys, blocks: InOut<'_, '_, ParBlocks<Serpent>>) {
+    if avx2_cpuid::get() {
+        // SAFETY: AVX2 availability was checked at runtime
+        unsafe { encrypt_avx2(round_keys, blocks) }
+    } else {
+        encrypt_lanes::<Sse2Lanes>(round_keys, blocks)
+    }
+}
+
+/// Decrypt 8 blocks.
+#[inline]
+pub(crate) fn decrypt_par(round_keys: &RoundKeys, blocks: InOut<'_, '_, ParBlocks<Serpent>>) {
+    if avx2_cpuid::get() {
+        // SAFETY: AVX2 availability was checked at runtime
+        unsafe { decrypt_avx2(round_keys, blocks) }
+    } else {
+        decrypt_lanes::<Sse2Lanes>(round_keys, blocks)
+    }
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn encrypt_avx2(round_keys: &RoundKeys, blocks: InOut<'_, '_, ParBlocks<Serpent>>) {
+    encrypt_lanes::<Avx2Lanes>(round_keys, blocks)
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn decrypt_avx2(round_keys: &RoundKeys, blocks: InOut<'_, '_, ParBlocks<Serpent>>) {
+    decrypt_lanes::<Avx2Lanes>(round_keys, blocks)
+}
+
+impl_lanes!(
+    Sse2Lanes, __m128i, 2,
+    and: _mm_and_si128, or: _mm_or_si128, xor: _mm_xor_si128,
+    not: |a| _mm_xor_si128(a, _mm_set1_epi32(-1)),
+    shl: |a, n: u32| _mm_sll_epi32(a, _mm_cvtsi32_si128(n as i32)),
+    shr: |a, n: u32| _mm_srl_epi32(a, _mm_cvtsi32_si128(n as i32)),
+    splat: |x: u32| _mm_set1_epi32(x as i32),
+    load: |p: *const u32| _mm_loadu_si128(p.cast()),
+    store: |p: *mut u32, v| _mm_storeu_si128(p.cast(), v),
+);
+
+impl_lanes!(
+    Avx2Lanes, __m256i, 1,
+    and: _mm256_and_si256, or: _mm256_or_si256, xor: _mm256_xor_si256,
+    not: |a| _mm256_xor_si256(a, _mm256_set1_epi32(-1)),
+    shl: |a, n: u32| _mm256_sll_epi32(a, _mm_cvtsi32_si128(n as i32)),
+    shr: |a, n: u32| _mm256_srl_epi32(a, _mm_cvtsi32_si128(n as i32)),
+    splat: |x: u32| _mm256_set1_epi32(x as i32),
+    load: |p: *const u32| _mm256_loadu_si256(p.cast()),
+    store: |p: *mut u32, v| _mm256_storeu_si256(p.cast(), v),
+);
@@ -0,0 +1,42 @@
+use super::*;
+use cipher::{Array, BlockCipherEncrypt, KeyInit};
+
+/// Check `L` against the scalar implementation. `encrypt_par` always picks
+/// the AVX2 lanes on CPUs which support them, so the lanes are called directly.
+fn check<L: Lanes>() {
+    let cipher = Serpent::new_from_slice(&[0x42; 32]).unwrap();
+    let mut pt = ParBlocks::<Serpent>::default();
+    for (i, block) in pt.iter_mut().enumerate() {
+        *block = Array::from(core::array::from_fn(|j| (16 * i + j) as u8));
+    }
+
+    let mut ct = ParBlocks::<Serpent>::default();
+    encrypt_lanes::<L>(&cipher.round_keys, (&pt, &mut ct).into());
+    for (block, pt_block) in ct.iter().zip(pt.iter()) {
+        let mut expected = *pt_block;
+        cipher.encrypt_block(&mut expected);
+        assert_eq!(block, &expected);
+    }
+
+    let mut out = ParBlocks::<Serpent>::default();
+    decrypt_lanes::<L>(&cipher.round_keys, (&ct, &mut out).into());
+    assert_eq!(out, pt);
+}
+
+#[test]
+fn sse2_lanes() {
+    check::<Sse2Lanes>();
+}
+
+#[test]
+fn avx2_lanes() {
+    if avx2_cpuid::get() {
+        // SAFETY: AVX2 availability was checked at runtime
+        unsafe { check_avx2() }
+    }
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn check_avx2() {
+    check::<Avx2Lanes>();
+}
@@ -0,0 +1,136 @@
+use crate::Serpent;
+use cipher::{
+    AlgorithmName, Block, BlockCipherDecBackend, BlockCipherDecClosure, BlockCipherDecrypt,
+    BlockCipherEncBackend, BlockCipherEncClosure, BlockCipherEncrypt, BlockSizeUser, InOut,
+    InvalidLength, Key, KeyInit, KeySizeUser, ParBlocks, ParBlocksSizeUser,
+    consts::{U8, U16},
+};
+use core::fmt;
+
+#[cfg(feature = "zeroize")]
+use cipher::zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Serpent block cipher with reversed byte order ("Tnepres").
+///
+/// The Serpent AES submission reference implementation and its test vectors
+/// treat keys and blocks as big numbers written with the most significant
+/// byte first, while NESSIE, libgcrypt, Nettle and the Linux `serpent`
+/// driver (and [`Serpent`]) use the reversed, least significant byte first
+/// order. This type implements the former convention, which is also used by
+/// the Linux `tnepres` driver: the bytes of the key, input blocks and output
+/// blocks are reversed compared to [`Serpent`].
+#[derive(Clone)]
+pub struct Tnepres {
+    inner: Serpent,
+}
+
+impl KeySizeUser for Tnepres {
+    type KeySize = U16;
+}
+
+impl KeyInit for Tnepres {
+    #[inline]
+    fn new(key: &Key<Self>) -> Self {
+        Self::new_from_slice(key).unwrap()
+    }
+
+    fn new_from_slice(key: &[u8]) -> Result<Self, InvalidLength> {
+        if key.len() > 32 {
+            return Err(InvalidLength);
+        }
+        let mut rev_key = [0u8; 32];
+        let rev_key_slice = &mut rev_key[..key.len()];
+        rev_key_slice.copy_from_slice(key);
+        rev_key_slice.reverse();
+        let res = Serpent::new_from_slice(rev_key_slice).map(|inner| Self { inner });
+        #[cfg(feature = "zeroize")]
+        rev_key.zeroize();
+        res
+    }
+}
+
+impl From<Serpent> for Tnepres {
+    /// Reuse the key schedule of `cipher`. Note that the key must be
+    /// reversed to obtain the same cipher using [`Tnepres::new`].
+    #[inline]
+    fn from(cipher: Serpent) -> Self {
+        Self { inner: cipher }
+    }
+}
+
+impl BlockSizeUser for Tnepres {
+    type BlockSize = U16;
+}
+
+impl ParBlocksSizeUser for Tnepres {
+    type ParBlocksSize = U8;
+}
+
+impl BlockCipherEncrypt for Tnepres {
+    #[inline]
+    fn encrypt_with_backend(&self, f: impl BlockCipherEncClosure<BlockSize = Self::BlockSize>) {
+        f.call(self)
+    }
+}
+
+impl BlockCipherEncBackend for Tnepres {
+    #[inline]
+    fn encrypt_block(&self, mut block: InOut<'_, '_, Block<Self>>) {
+        let mut b = block.clone_in();
+        b.reverse();
+        BlockCipherEncBackend::encrypt_block(&self.inner, (&mut b).into());
+        b.reverse();
+        *block.get_out() = b;
+    }
+
+    #[inline]
+    fn encrypt_par_blocks(&self, mut blocks: InOut<'_, '_, ParBlocks<Self>>) {
+        let mut b = blocks.clone_in();
+        b.iter_mut().for_each(|b| b.reverse());
+        self.inner.encrypt_par_blocks((&mut b).into());
+        b.iter_mut().for_each(|b| b.reverse());
+        *blocks.get_out() = b;
+    }
+}
+
+impl BlockCipherDecrypt for Tnepres {
+    #[inline]
+    fn decrypt_with_backend(&self, f: impl BlockCipherDecClosure<BlockSize = Self::BlockSize>) {
+        f.call(self)
+    }
+}
+
+impl BlockCipherDecBackend for Tnepres {
+    #[inline]
+    fn decrypt_block(&self, mut block: InOut<'_, '_, Block<Self>>) {
+        let mut b = block.clone_in();
+        b.reverse();
+        BlockCipherDecBackend::decrypt_block(&self.inner, (&mut b).into());
+        b.reverse();
+        *block.get_out() = b;
+    }
+
+    #[inline]
+    fn decrypt_par_blocks(&self, mut blocks: InOut<'_, '_, ParBlocks<Self>>) {
+        let mut b = blocks.clone_in();
+        b.iter_mut().for_each(|b| b.reverse());
+        self.inner.decrypt_par_blocks((&mut b).into());
+        b.iter_mut().for_each(|b| b.reverse());
+        *blocks.get_out() = b;
+    }
+}
+
+impl fmt::Debug for Tnepres {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Tnepres { ... }")
+    }
+}
+
+impl AlgorithmName for Tnepres {
+    fn write_alg_name(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Tnepres")
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl ZeroizeOnDrop for Tnepres {}
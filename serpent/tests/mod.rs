@@ -4,3 +4,219 @@
 cipher::block_cipher_test!(serpent128, serpent::Serpent);
 cipher::block_cipher_test!(serpent192, serpent::Serpent);
 cipher::block_cipher_test!(serpent256, serpent::Serpent);
+cipher::block_cipher_test!(serpent128_fixed, "serpent128", serpent::Serpent128);
+cipher::block_cipher_test!(serpent192_fixed, "serpent192", serpent::Serpent192);
+cipher::block_cipher_test!(serpent256_fixed, "serpent256", serpent::Serpent256);
+
+#[test]
+fn fixed_key_size() {
+    use cipher::KeyInit;
+
+    let key = [0u8; 33];
+    for len in 0..=32 {
+        let key = &key[..len];
+        assert_eq!(serpent::Serpent128::new_from_slice(key).is_ok(), len == 16);
+        assert_eq!(serpent::Serpent192::new_from_slice(key).is_ok(), len == 24);
+        assert_eq!(serpent::Serpent256::new_from_slice(key).is_ok(), len == 32);
+    }
+}
+
+/// Keys of arbitrary bit length against the Serpent AES submission
+/// `ecb_vk.txt` (I=1 for KEYSIZE=128, 192 and 256), with key and ciphertext
+/// bytes reversed. A short key is padded with a single one bit, so the
+/// all-zero 255-bit key is the 256-bit key with only its top bit set.
+#[test]
+fn key_bits_kat() {
+    use cipher::{Array, BlockCipherDecrypt, BlockCipherEncrypt};
+    use hex_literal::hex;
+
+    let top_bit = |len: usize| {
+        let mut key = vec![0; len];
+        key[len - 1] = 0x80;
+        key
+    };
+    let vectors: &[(usize, Vec<u8>, [u8; 16])] = &[
+        (128, top_bit(16), hex!("ddd26b98a5ffd82c05345a9dadbfaf49")),
+        (192, top_bit(24), hex!("660cf6a3f77836ac685519c702548ee7")),
+        (256, top_bit(32), hex!("1908ef821ad2ebc0cb28bf66e796edab")),
+        (255, vec![0; 32], hex!("1908ef821ad2ebc0cb28bf66e796edab")),
+    ];
+
+    for (bits, key, ciphertext) in vectors {
+        let cipher = serpent::Serpent::new_with_key_bits(key, *bits).unwrap();
+        let mut block = Array::from([0; 16]);
+        cipher.encrypt_block(&mut block);
+        assert_eq!(block, *ciphertext, "key bits: {bits}");
+        cipher.decrypt_block(&mut block);
+        assert_eq!(block, [0; 16]);
+    }
+}
+
+/// Keys of arbitrary bit length. Generated with GNU Nettle by padding the
+/// keys to 256 bits manually, so these only cross-check the padding rule
+/// tested by `key_bits_kat`.
+#[test]
+fn key_bits() {
+    use cipher::{Array, BlockCipherDecrypt, BlockCipherEncrypt};
+    use hex_literal::hex;
+
+    let vectors: &[(usize, &[u8], [u8; 16])] = &[
+        (0, &[], hex!("1207fcce9bd0d6476ae98fbed143a0e2")),
+        (1, &hex!("01"), hex!("00a0805de677cf8b1e69b8b0f08c9529")),
+        (
+            40,
+            &hex!("074481befb"),
+            hex!("c1bbe7a30feb492825172b981dc0753d"),
+        ),
+        (
+            64,
+            &hex!("074481befb3875b2"),
+            hex!("059d50c43abe56f186f305ae2f3790d3"),
+        ),
+        (
+            100,
+            &hex!("074481befb3875b2ef2c69a603"),
+            hex!("2429243f4409174049ccd87132facd88"),
+        ),
+        (
+            127,
+            &hex!("074481befb3875b2ef2c69a6e3205d1a"),
+            hex!("23bb7880609736e00fb79d8cfd43540e"),
+        ),
+        (
+            128,
+            &hex!("074481befb3875b2ef2c69a6e3205d9a"),
+            hex!("03c70b9abbfd0c908b7a19fe9ed6ef4c"),
+        ),
+        (
+            129,
+            &hex!("074481befb3875b2ef2c69a6e3205d9a01"),
+            hex!("7ddb910916d80902ba1fa34b98f73f2c"),
+        ),
+        (
+            200,
+            &hex!("074481befb3875b2ef2c69a6e3205d9ad714518ecb084582bf"),
+            hex!("a1c9d307b343aac3ee311caf060b878c"),
+        ),
+        (
+            255,
+            &hex!("074481befb3875b2ef2c69a6e3205d9ad714518ecb084582bffc3976b3f02d6a"),
+            hex!("9160a0cc3ea82c6e1fbd4d7817bda3ae"),
+        ),
+        (
+            256,
+            &hex!("074481befb3875b2ef2c69a6e3205d9ad714518ecb084582bffc3976b3f02d6a"),
+            hex!("40a5cf2dd08fc88a92f3caa87dc0f696"),
+        ),
+    ];
+    let plaintext = hex!("000102030405060708090a0b0c0d0e0f");
+
+    for &(bits, key, ciphertext) in vectors {
+        let cipher = serpent::Serpent::new_with_key_bits(key, bits).unwrap();
+        let mut block = Array::from(plaintext);
+        cipher.encrypt_block(&mut block);
+        assert_eq!(block, ciphertext, "key bits: {bits}");
+        cipher.decrypt_block(&mut block);
+        assert_eq!(block, plaintext);
+    }
+
+    // unused bits of the last byte are ignored
+    let a = serpent::Serpent::new_with_key_bits(&hex!("074481befb3875b2ef2c69a603"), 100);
+    let b = serpent::Serpent::new_with_key_bits(&hex!("074481befb3875b2ef2c69a6f3"), 100);
+    let mut block1 = Array::from(plaintext);
+    let mut block2 = Array::from(plaintext);
+    a.unwrap().encrypt_block(&mut block1);
+    b.unwrap().encrypt_block(&mut block2);
+    assert_eq!(block1, block2);
+}
+
+#[test]
+fn key_bits_invalid() {
+    use serpent::Serpent;
+
+    assert!(Serpent::new_with_key_bits(&[0; 4], 33).is_err());
+    assert!(Serpent::new_with_key_bits(&[0; 5], 41).is_err());
+    assert!(Serpent::new_with_key_bits(&[0; 33], 257).is_err());
+    assert!(Serpent::new_with_key_bits(&[0; 33], 264).is_err());
+    assert!(Serpent::new_with_key_bits(&[0; 6], 41).is_ok());
+}
+
+/// Tnepres known-answer test from the Serpent AES submission `ecb_vk.txt`
+/// (KEYSIZE=128, I=1).
+#[test]
+fn tnepres_kat() {
+    use cipher::{Array, BlockCipherDecrypt, BlockCipherEncrypt, KeyInit};
+    use hex_literal::hex;
+    use serpent::Tnepres;
+
+    let key = hex!("80000000000000000000000000000000");
+    let ciphertext = hex!("49afbfad9d5a34052cd8ffa5986bd2dd");
+
+    let cipher = Tnepres::new_from_slice(&key).unwrap();
+    let mut block = Array::from([0; 16]);
+    cipher.encrypt_block(&mut block);
+    assert_eq!(block, ciphertext);
+    cipher.decrypt_block(&mut block);
+    assert_eq!(block, [0; 16]);
+}
+
+/// Consistency checks between Tnepres and Serpent. Generated with GNU Nettle
+/// by reversing the key, plaintext and ciphertext bytes, so these only show
+/// that `Tnepres` is byte-reversed `Serpent`; see `tnepres_kat` for an
+/// independent vector.
+#[test]
+fn tnepres() {
+    use cipher::{Array, BlockCipherDecrypt, BlockCipherEncrypt, KeyInit};
+    use hex_literal::hex;
+    use serpent::{Serpent, Tnepres};
+
+    let vectors: &[(&[u8], [u8; 16], [u8; 16])] = &[
+        (&[0; 16], [0; 16], hex!("e9ba668276b81896d093a9e67ab12036")),
+        (&[0; 24], [0; 16], hex!("42046b25c85dbd6b402b296a97ef83a5")),
+        (&[0; 32], [0; 16], hex!("8910494504181950f98dd998a82b6749")),
+        (
+            &hex!("000102030405060708090a0b0c0d0e0f"),
+            hex!("000102030405060708090a0b0c0d0e0f"),
+            hex!("eaf4d7fcd801344781450bfa0cd6ad6e"),
+        ),
+        (
+            &hex!("000102030405060708090a0b0c0d0e0f1011121314151617"),
+            hex!("000102030405060708090a0b0c0d0e0f"),
+            hex!("9b208d5a1bc421eceeb9e7fdfb1b11b8"),
+        ),
+        (
+            &hex!("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"),
+            hex!("000102030405060708090a0b0c0d0e0f"),
+            hex!("64a91a37ed9fe749a84e76d6f50d78ee"),
+        ),
+    ];
+
+    for &(key, plaintext, ciphertext) in vectors {
+        let cipher = Tnepres::new_from_slice(key).unwrap();
+        let mut block = Array::from(plaintext);
+        cipher.encrypt_block(&mut block);
+        assert_eq!(block, ciphertext);
+        cipher.decrypt_block(&mut block);
+        assert_eq!(block, plaintext);
+
+        // Serpent with reversed byte order
+        let mut rev_key = key.to_vec();
+        rev_key.reverse();
+        let serpent = Serpent::new_from_slice(&rev_key).unwrap();
+        let mut block = Array::from(plaintext);
+        block.reverse();
+        serpent.encrypt_block(&mut block);
+        block.reverse();
+        assert_eq!(block, ciphertext);
+    }
+
+    let cipher = Tnepres::new_from_slice(&[0x42; 32]).unwrap();
+    let plaintext: [_; 19] = core::array::from_fn(|i| Array::from([i as u8; 16]));
+    let mut blocks1 = plaintext;
+    let mut blocks2 = plaintext;
+    cipher.encrypt_blocks(&mut blocks1);
+    blocks2.iter_mut().for_each(|b| cipher.encrypt_block(b));
+    assert_eq!(blocks1, blocks2);
+    cipher.decrypt_blocks(&mut blocks1);
+    assert_eq!(blocks1, plaintext);
+}
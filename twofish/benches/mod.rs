@@ -1,8 +1,31 @@
 #![feature(test)]
 extern crate test;
 
-use cipher::{block_decryptor_bench, block_encryptor_bench};
-use twofish::Twofish;
+use cipher::{KeyInit, block_decryptor_bench, block_encryptor_bench};
+use twofish::{Twofish, TwofishFullKey};
 
 block_encryptor_bench!(Key: Twofish, twofish_encrypt_block, twofish_encrypt_blocks);
 block_decryptor_bench!(Key: Twofish, twofish_decrypt_block, twofish_decrypt_blocks);
+
+block_encryptor_bench!(
+    Key: TwofishFullKey,
+    twofish_full_key_encrypt_block,
+    twofish_full_key_encrypt_blocks,
+);
+block_decryptor_bench!(
+    Key: TwofishFullKey,
+    twofish_full_key_decrypt_block,
+    twofish_full_key_decrypt_blocks,
+);
+
+#[bench]
+fn twofish_new(bh: &mut test::Bencher) {
+    let key = Default::default();
+    bh.iter(|| Twofish::new(test::black_box(&key)));
+}
+
+#[bench]
+fn twofish_full_key_new(bh: &mut test::Bencher) {
+    let key = Default::default();
+    bh.iter(|| TwofishFullKey::new(test::black_box(&key)));
+}
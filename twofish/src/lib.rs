@@ -8,6 +8,11 @@
 //!
 //! USE AT YOUR OWN RISK!
 //!
+//! # Key-dependent S-boxes
+//! [`Twofish`] computes the key-dependent S-boxes on the fly, which keeps the
+//! cipher state small. [`TwofishFullKey`] precomputes them at key setup,
+//! trading setup time and memory for much faster block processing.
+//!
 //! [Twofish]: https://en.wikipedia.org/wiki/Twofish
 
 #![no_std]
@@ -34,8 +39,11 @@ use core::fmt;
 use cipher::zeroize::{Zeroize, ZeroizeOnDrop};
 
 mod consts;
+mod full_key;
 use crate::consts::{MDS_POLY, QBOX, QORD, RS, RS_POLY};
 
+pub use crate::full_key::TwofishFullKey;
+
 /// Twofish block cipher
 #[derive(Clone)]
 pub struct Twofish {
@@ -131,22 +139,108 @@ fn h(x: u32, m: &[u8], k: usize, offset: usize) -> u32 {
     mds_mult(y)
 }
 
+/// Encrypt `block` using subkeys `k` and the `g` function.
+#[inline(always)]
+fn encrypt(k: &[u32; 40], g: impl Fn(u32) -> u32, mut block: InOut<'_, '_, Block<Twofish>>) {
+    let b = block.get_in();
+    let mut p = [
+        u32::from_le_bytes(b[0..4].try_into().unwrap()),
+        u32::from_le_bytes(b[4..8].try_into().unwrap()),
+        u32::from_le_bytes(b[8..12].try_into().unwrap()),
+        u32::from_le_bytes(b[12..16].try_into().unwrap()),
+    ];
+
+    // Input whitening
+    for i in 0..4 {
+        p[i] ^= k[i];
+    }
+
+    for r in 0..8 {
+        let rk = 4 * r + 8;
+
+        let t1 = g(p[1].rotate_left(8));
+        let t0 = g(p[0]).wrapping_add(t1);
+        p[2] = (p[2] ^ (t0.wrapping_add(k[rk]))).rotate_right(1);
+        let t2 = t1.wrapping_add(t0).wrapping_add(k[rk + 1]);
+        p[3] = p[3].rotate_left(1) ^ t2;
+
+        let t1 = g(p[3].rotate_left(8));
+        let t0 = g(p[2]).wrapping_add(t1);
+        p[0] = (p[0] ^ (t0.wrapping_add(k[rk + 2]))).rotate_right(1);
+        let t2 = t1.wrapping_add(t0).wrapping_add(k[rk + 3]);
+        p[1] = (p[1].rotate_left(1)) ^ t2;
+    }
+
+    // Undo last swap and output whitening
+    p[2] ^= k[4];
+    p[3] ^= k[5];
+    p[0] ^= k[6];
+    p[1] ^= k[7];
+
+    let block = block.get_out();
+    block[0..4].copy_from_slice(&p[2].to_le_bytes());
+    block[4..8].copy_from_slice(&p[3].to_le_bytes());
+    block[8..12].copy_from_slice(&p[0].to_le_bytes());
+    block[12..16].copy_from_slice(&p[1].to_le_bytes());
+}
+
+/// Decrypt `block` using subkeys `k` and the `g` function.
+#[inline(always)]
+fn decrypt(k: &[u32; 40], g: impl Fn(u32) -> u32, mut block: InOut<'_, '_, Block<Twofish>>) {
+    let b = block.get_in();
+    let mut c = [
+        u32::from_le_bytes(b[8..12].try_into().unwrap()) ^ k[6],
+        u32::from_le_bytes(b[12..16].try_into().unwrap()) ^ k[7],
+        u32::from_le_bytes(b[0..4].try_into().unwrap()) ^ k[4],
+        u32::from_le_bytes(b[4..8].try_into().unwrap()) ^ k[5],
+    ];
+
+    for r in (0..8).rev() {
+        let rk = 4 * r + 8;
+
+        let t1 = g(c[3].rotate_left(8));
+        let t0 = g(c[2]).wrapping_add(t1);
+        c[0] = c[0].rotate_left(1) ^ (t0.wrapping_add(k[rk + 2]));
+        let t2 = t1.wrapping_add(t0).wrapping_add(k[rk + 3]);
+        c[1] = (c[1] ^ t2).rotate_right(1);
+
+        let t1 = g(c[1].rotate_left(8));
+        let t0 = g(c[0]).wrapping_add(t1);
+        c[2] = c[2].rotate_left(1) ^ (t0.wrapping_add(k[rk]));
+        let t2 = t1.wrapping_add(t0).wrapping_add(k[rk + 1]);
+        c[3] = (c[3] ^ t2).rotate_right(1);
+    }
+
+    for i in 0..4 {
+        c[i] ^= k[i];
+    }
+
+    let block = block.get_out();
+    block[0..4].copy_from_slice(&c[0].to_le_bytes());
+    block[4..8].copy_from_slice(&c[1].to_le_bytes());
+    block[8..12].copy_from_slice(&c[2].to_le_bytes());
+    block[12..16].copy_from_slice(&c[3].to_le_bytes());
+}
+
 impl Twofish {
     fn g_func(&self, x: u32) -> u32 {
         let mut result: u32 = 0;
         for y in 0..4 {
-            let mut g = sbox(QORD[y][self.start], (x >> (8 * y)) as u8);
-
-            for z in self.start + 1..5 {
-                g ^= self.s[4 * (z - self.start - 1) + y];
-                g = sbox(QORD[y][z], g);
-            }
-
-            result ^= mds_column_mult(g, y);
+            result ^= mds_column_mult(self.key_sbox(y, (x >> (8 * y)) as u8), y);
         }
         result
     }
 
+    // Key-dependent S-box applied to the byte `y` of the `g` function input
+    fn key_sbox(&self, y: usize, x: u8) -> u8 {
+        let mut g = sbox(QORD[y][self.start], x);
+        for z in self.start + 1..5 {
+            g ^= self.s[4 * (z - self.start - 1) + y];
+            g = sbox(QORD[y][z], g);
+        }
+        g
+    }
+
     fn key_schedule(&mut self, key: &[u8]) {
         let k = key.len() / 8;
 
@@ -216,47 +310,8 @@ impl BlockCipherEncrypt for Twofish {
 
 impl BlockCipherEncBackend for Twofish {
     #[inline]
-    fn encrypt_block(&self, mut block: InOut<'_, '_, Block<Self>>) {
-        let b = block.get_in();
-        let mut p = [
-            u32::from_le_bytes(b[0..4].try_into().unwrap()),
-            u32::from_le_bytes(b[4..8].try_into().unwrap()),
-            u32::from_le_bytes(b[8..12].try_into().unwrap()),
-            u32::from_le_bytes(b[12..16].try_into().unwrap()),
-        ];
-
-        // Input whitening
-        for i in 0..4 {
-            p[i] ^= self.k[i];
-        }
-
-        for r in 0..8 {
-            let k = 4 * r + 8;
-
-            let t1 = self.g_func(p[1].rotate_left(8));
-            let t0 = self.g_func(p[0]).wrapping_add(t1);
-            p[2] = (p[2] ^ (t0.wrapping_add(self.k[k]))).rotate_right(1);
-            let t2 = t1.wrapping_add(t0).wrapping_add(self.k[k + 1]);
-            p[3] = p[3].rotate_left(1) ^ t2;
-
-            let t1 = self.g_func(p[3].rotate_left(8));
-            let t0 = self.g_func(p[2]).wrapping_add(t1);
-            p[0] = (p[0] ^ (t0.wrapping_add(self.k[k + 2]))).rotate_right(1);
-            let t2 = t1.wrapping_add(t0).wrapping_add(self.k[k + 3]);
-            p[1] = (p[1].rotate_left(1)) ^ t2;
-        }
-
-        // Undo last swap and output whitening
-        p[2] ^= self.k[4];
-        p[3] ^= self.k[5];
-        p[0] ^= self.k[6];
-        p[1] ^= self.k[7];
-
-        let block = block.get_out();
-        block[0..4].copy_from_slice(&p[2].to_le_bytes());
-        block[4..8].copy_from_slice(&p[3].to_le_bytes());
-        block[8..12].copy_from_slice(&p[0].to_le_bytes());
-        block[12..16].copy_from_slice(&p[1].to_le_bytes());
+    fn encrypt_block(&self, block: InOut<'_, '_, Block<Self>>) {
+        encrypt(&self.k, |x| self.g_func(x), block);
     }
 }
 
@@ -269,40 +324,8 @@ impl BlockCipherDecrypt for Twofish {
 
 impl BlockCipherDecBackend for Twofish {
     #[inline]
-    fn decrypt_block(&self, mut block: InOut<'_, '_, Block<Self>>) {
-        let b = block.get_in();
-        let mut c = [
-            u32::from_le_bytes(b[8..12].try_into().unwrap()) ^ self.k[6],
-            u32::from_le_bytes(b[12..16].try_into().unwrap()) ^ self.k[7],
-            u32::from_le_bytes(b[0..4].try_into().unwrap()) ^ self.k[4],
-            u32::from_le_bytes(b[4..8].try_into().unwrap()) ^ self.k[5],
-        ];
-
-        for r in (0..8).rev() {
-            let k = 4 * r + 8;
-
-            let t1 = self.g_func(c[3].rotate_left(8));
-            let t0 = self.g_func(c[2]).wrapping_add(t1);
-            c[0] = c[0].rotate_left(1) ^ (t0.wrapping_add(self.k[k + 2]));
-            let t2 = t1.wrapping_add(t0).wrapping_add(self.k[k + 3]);
-            c[1] = (c[1] ^ t2).rotate_right(1);
-
-            let t1 = self.g_func(c[1].rotate_left(8));
-            let t0 = self.g_func(c[0]).wrapping_add(t1);
-            c[2] = c[2].rotate_left(1) ^ (t0.wrapping_add(self.k[k]));
-            let t2 = t1.wrapping_add(t0).wrapping_add(self.k[k + 1]);
-            c[3] = (c[3] ^ t2).rotate_right(1);
-        }
-
-        for i in 0..4 {
-            c[i] ^= self.k[i];
-        }
-
-        let block = block.get_out();
-        block[0..4].copy_from_slice(&c[0].to_le_bytes());
-        block[4..8].copy_from_slice(&c[1].to_le_bytes());
-        block[8..12].copy_from_slice(&c[2].to_le_bytes());
-        block[12..16].copy_from_slice(&c[3].to_le_bytes());
+    fn decrypt_block(&self, block: InOut<'_, '_, Block<Self>>) {
+        decrypt(&self.k, |x| self.g_func(x), block);
     }
 }
 
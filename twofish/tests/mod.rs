@@ -1,6 +1,6 @@
 use cipher::{BlockCipherDecrypt, BlockCipherEncrypt, KeyInit, array::Array};
 use hex_literal::hex;
-use twofish::Twofish;
+use twofish::{Twofish, TwofishFullKey};
 
 macro_rules! new_test {
     (
@@ -23,6 +23,13 @@ macro_rules! new_test {
                 twofish.decrypt_block(&mut buf);
                 assert_eq!(plain, buf);
 
+                let full_key = TwofishFullKey::new_from_slice(&key).unwrap();
+                let mut buf = plain;
+                full_key.encrypt_block(&mut buf);
+                assert_eq!(cipher, buf);
+                full_key.decrypt_block(&mut buf);
+                assert_eq!(plain, buf);
+
                 let correct = match i {
                     1 => Some(hex!($r1)),
                     2 => Some(hex!($r2)),